@@ -39,7 +39,7 @@ pub async fn decode_account_data(
     params: ParamsOfDecodeAccountData,
 ) -> ClientResult<ResultOfDecodeAccountData> {
     let (_, data) = deserialize_cell_from_boc(&context, &params.data, "contract data").await?;
-    let abi = params.abi.abi()?;
+    let abi = params.abi.abi(&context)?;
 
     let tokens = abi.decode_storage_fields(data.into(), params.allow_partial)
         .map_err(|e| Error::invalid_data_for_decode(e))?;
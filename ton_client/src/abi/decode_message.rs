@@ -86,7 +86,7 @@ pub async fn decode_message(
 ) -> ClientResult<DecodedMessageBody> {
     let (abi, message) = prepare_decode(&context, &params).await?;
     if let Some(body) = message.body() {
-        decode_body(abi, body, message.is_internal(), params.allow_partial)
+        decode_body(&abi, body, message.is_internal(), params.allow_partial)
     } else {
         Err(Error::invalid_message_for_decode(
             "The message body is empty",
@@ -121,18 +121,16 @@ pub async fn decode_message_body(
     context: Arc<ClientContext>,
     params: ParamsOfDecodeMessageBody,
 ) -> ClientResult<DecodedMessageBody> {
-    let abi = params.abi.json_string()?;
-    let abi = AbiContract::load(abi.as_bytes()).map_err(|x| Error::invalid_json(x))?;
+    let abi = params.abi.abi(&context)?;
     let (_, body) = deserialize_cell_from_boc(&context, &params.body, "message body").await?;
-    decode_body(abi, body.into(), params.is_internal, params.allow_partial)
+    decode_body(&abi, body.into(), params.is_internal, params.allow_partial)
 }
 
 async fn prepare_decode(
     context: &ClientContext,
     params: &ParamsOfDecodeMessage,
-) -> ClientResult<(AbiContract, ton_block::Message)> {
-    let abi = params.abi.json_string()?;
-    let abi = AbiContract::load(abi.as_bytes()).map_err(|x| Error::invalid_json(x))?;
+) -> ClientResult<(Arc<AbiContract>, ton_block::Message)> {
+    let abi = params.abi.abi(context)?;
     let message = deserialize_object_from_boc(context, &params.message, "message")
         .await
         .map_err(|x| Error::invalid_message_for_decode(x))?;
@@ -140,7 +138,7 @@ async fn prepare_decode(
 }
 
 fn decode_body(
-    abi: AbiContract,
+    abi: &AbiContract,
     body: SliceData,
     is_internal: bool,
     allow_partial: bool,
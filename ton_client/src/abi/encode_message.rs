@@ -1,8 +1,10 @@
 use crate::abi;
 use crate::abi::internal::{add_sign_to_message, add_sign_to_message_body, create_tvc_image, try_to_sign_message, update_pubkey};
 use crate::abi::{Abi, Error, FunctionHeader, Signer};
-use crate::boc::internal::{get_boc_hash, deserialize_cell_from_boc};
+use crate::boc::internal::{get_boc_hash, deserialize_cell_from_boc, serialize_cell_to_bytes_ex};
+use crate::boc::BocSerializationOptions;
 use crate::client::ClientContext;
+use crate::crypto::boxes::signing_box::SigningBoxMessage;
 use crate::encoding::{account_decode, account_encode, decode_abi_number, hex_decode};
 use crate::error::ClientResult;
 use serde_json::Value;
@@ -145,10 +147,9 @@ impl CallSet {
         pubkey: Option<&str>,
         processing_try_index: Option<u8>,
         context: &Arc<ClientContext>,
-        abi: &str,
+        abi: &Arc<Contract>,
         internal: bool,
     ) -> ClientResult<FunctionCallSet> {
-        let contract = Contract::load(abi.as_bytes()).map_err(|x| Error::invalid_json(x))?;
         let header = if internal {
             None
         } else {
@@ -157,13 +158,13 @@ impl CallSet {
                 pubkey,
                 processing_try_index,
                 context,
-                &contract,
+                abi,
             )?
         };
 
         let func = match decode_abi_number::<u32>(&self.function_name) {
             Ok(id) => {
-                &contract
+                &abi
                     .function_by_id(id, true)
                     .map_err(|e| Error::invalid_function_id(&self.function_name, e))?
                     .name
@@ -173,7 +174,7 @@ impl CallSet {
         .clone();
 
         Ok(FunctionCallSet {
-            abi: abi.to_string(),
+            abi: Arc::clone(abi),
             func,
             header: header.as_ref().map(|x| header_to_string(x)),
             input: self
@@ -225,6 +226,9 @@ pub struct ParamsOfEncodeMessage {
     ///
     /// Default value is 0.
     pub processing_try_index: Option<u8>,
+
+    /// Message BOC serialization options.
+    pub boc_serialization: Option<BocSerializationOptions>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -258,7 +262,7 @@ fn required_public_key(public_key: Option<String>) -> ClientResult<String> {
 
 fn encode_deploy(
     context: std::sync::Arc<ClientContext>,
-    abi: &str,
+    abi: &Arc<Contract>,
     image: ContractImage,
     workchain: i32,
     call_set: &CallSet,
@@ -280,7 +284,7 @@ fn encode_deploy(
         }
         _ => {
             let unsigned = ton_sdk::Contract::get_deploy_message_bytes_for_signing(
-                call_set.to_function_call_set(pubkey, processing_try_index, &context, &abi, false)?,
+                call_set.to_function_call_set(pubkey, processing_try_index, &context, abi, false)?,
                 image,
                 workchain,
             )
@@ -293,7 +297,7 @@ fn encode_deploy(
 fn encode_int_deploy(
     src: Option<MsgAddressInt>,
     context: std::sync::Arc<ClientContext>,
-    abi: &str,
+    abi: &Arc<Contract>,
     image: ContractImage,
     workchain_id: i32,
     call_set: &CallSet,
@@ -304,7 +308,7 @@ fn encode_int_deploy(
     let address = image.msg_address(workchain_id);
     let message = ton_sdk::Contract::get_int_deploy_message_bytes(
         src,
-        call_set.to_function_call_set(pubkey, None, &context, &abi, true)?,
+        call_set.to_function_call_set(pubkey, None, &context, abi, true)?,
         image,
         workchain_id,
         ihr_disabled,
@@ -359,7 +363,7 @@ fn encode_empty_int_deploy(
 fn encode_run(
     context: std::sync::Arc<ClientContext>,
     params: &ParamsOfEncodeMessage,
-    abi: &str,
+    abi: &Arc<Contract>,
     call_set: &CallSet,
     pubkey: Option<&str>,
     processing_try_index: Option<u8>,
@@ -429,7 +433,7 @@ pub async fn encode_message(
     context: std::sync::Arc<ClientContext>,
     params: ParamsOfEncodeMessage,
 ) -> ClientResult<ResultOfEncodeMessage> {
-    let abi = params.abi.json_string()?;
+    let abi = params.abi.abi(&context)?;
 
     let public = params.signer.resolve_public_key(context.clone()).await?;
     let (message, data_to_sign, address) = if let Some(deploy_set) = params.deploy_set {
@@ -438,7 +442,7 @@ pub async fn encode_message(
             .unwrap_or(context.config.abi.workchain);
         let mut image = create_tvc_image(
             &context,
-            &abi,
+            &params.abi,
             deploy_set.initial_data.as_ref(),
             &deploy_set.tvc,
         ).await?;
@@ -475,6 +479,13 @@ pub async fn encode_message(
     let (message, data_to_sign) = try_to_sign_message(
         context, &abi, message, data_to_sign, &params.signer
     ).await?;
+    let message = if params.boc_serialization.is_some() {
+        let cell = ton_types::deserialize_tree_of_cells(&mut std::io::Cursor::new(&message))
+            .map_err(|err| crate::boc::Error::invalid_boc(err))?;
+        serialize_cell_to_bytes_ex(&cell, "message", params.boc_serialization.as_ref())?
+    } else {
+        message
+    };
 
     Ok(ResultOfEncodeMessage {
         message: base64::encode(&message),
@@ -565,8 +576,7 @@ pub async  fn encode_internal_message(
 
     let (message, address) = if let Some(deploy_set) = params.deploy_set {
         let abi = params.abi
-            .ok_or_else(|| Error::invalid_abi("abi is undefined"))?
-            .json_string()?;
+            .ok_or_else(|| Error::invalid_abi("abi is undefined"))?;
 
         let workchain_id = deploy_set
             .workchain_id
@@ -584,7 +594,7 @@ pub async  fn encode_internal_message(
             encode_int_deploy(
                 src_address,
                 Arc::clone(&context),
-                &abi,
+                &abi.abi(&context)?,
                 image,
                 workchain_id,
                 call_set,
@@ -612,14 +622,14 @@ pub async  fn encode_internal_message(
         if let Some(call_set) = &params.call_set {
             let abi = params.abi
                 .ok_or_else(|| Error::invalid_abi("abi is undefined"))?
-                .json_string()?;
+                .abi(&context)?;
             let message = ton_sdk::Contract::construct_call_int_message_json(
                 address.clone(),
                 src_address,
                 ihr_disabled,
                 bounce,
                 value,
-                call_set.to_function_call_set(None, None, &context, &abi, true)?,
+                call_set.to_function_call_set(None, None, &context, abi, true)?,
             )
             .map_err(|err| abi::Error::encode_run_message_failed(err, Some(&call_set.function_name)))?;
 
@@ -704,7 +714,7 @@ pub async fn encode_message_body(
     context: std::sync::Arc<ClientContext>,
     params: ParamsOfEncodeMessageBody,
 ) -> ClientResult<ResultOfEncodeMessageBody> {
-    let abi = params.abi.json_string()?;
+    let abi = params.abi.abi(&context)?;
 
     let public = params.signer.resolve_public_key(context.clone()).await?;
     let call = params.call_set.to_function_call_set(
@@ -715,51 +725,40 @@ pub async fn encode_message_body(
         params.is_internal,
     )?;
     let func = call.func.clone();
+    let dst = params.address
+        .as_ref()
+        .map(|address| account_decode(address))
+        .transpose()?;
     let (body, data_to_sign) = match params.signer {
         Signer::None => {
-            let body = ton_abi::encode_function_call(
-                abi.clone(),
-                func.clone(),
-                call.header,
-                call.input.clone(),
-                params.is_internal,
-                None,
-                params.address,
-            )
-            .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
+            let body = call
+                .encode_input(params.is_internal, None, dst.clone())
+                .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
             (body, None)
         }
         _ => {
             if params.is_internal {
-                ton_abi::encode_function_call(
-                    abi.clone(),
-                    func.clone(),
-                    None,
-                    call.input,
-                    true,
-                    None,
-                    params.address,
-                ).map(|body| (body, None))
+                call.encode_input(true, None, dst.clone())
+                    .map(|body| (body, None))
             } else {
-                ton_abi::prepare_function_call_for_sign(
-                    abi.clone(),
-                    func.clone(),
-                    call.header,
-                    call.input,
-                    params.address,
-                ).map(|(body, data_to_sign)| (body, Some(data_to_sign)))
+                call.encode_unsigned(dst.clone())
+                    .map(|(body, data_to_sign)| (body, Some(data_to_sign)))
             }.map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?
         }
     };
-    let body: Vec<u8> = ton_types::serialize_toc(
-        &body
-            .clone()
-            .into_cell()
-            .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?,
-    )
-    .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
+    let body_cell = body
+        .clone()
+        .into_cell()
+        .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
+    let body: Vec<u8> = ton_types::serialize_toc(&body_cell)
+        .map_err(|err| Error::encode_run_message_failed(err, Some(&func)))?;
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = params.signer.sign(context.clone(), unsigned).await? {
+        let signing_message = SigningBoxMessage { dst, unsigned_body: body_cell };
+        if let Some(signature) = params.signer.sign_message(
+            context.clone(),
+            &signing_message,
+            unsigned,
+        ).await? {
             let pubkey = public
                 .map(|string| hex_decode(&string))
                 .transpose()?;
@@ -815,7 +814,7 @@ pub async fn attach_signature(
 ) -> ClientResult<ResultOfAttachSignature> {
     let (boc, _) = deserialize_cell_from_boc(&context, &params.message, "message").await?;
     let signed = add_sign_to_message(
-        &params.abi.json_string()?,
+        &params.abi.abi(&context)?,
         &hex_decode(&params.signature)?,
         Some(&hex_decode(&params.public_key)?),
         &boc.bytes("message")?,
@@ -856,7 +855,7 @@ pub async fn attach_signature_to_message_body(
 ) -> ClientResult<ResultOfAttachSignatureToMessageBody> {
     let (boc, _) = deserialize_cell_from_boc(&context, &params.message, "message body").await?;
     let signed = add_sign_to_message_body(
-        &params.abi.json_string()?,
+        &params.abi.abi(&context)?,
         &hex_decode(&params.signature)?,
         Some(&hex_decode(&params.public_key)?),
         &boc.bytes("message body")?,
@@ -18,6 +18,7 @@ pub enum ErrorCode {
     InvalidData = 313,
     EncodeInitialDataFailed = 314,
     InvalidFunctionName = 315,
+    AbiHandleNotRegistered = 316,
 }
 
 pub struct Error;
@@ -126,4 +127,11 @@ impl Error {
             format!("Function {} is not found in contract ABI", func_name),
         )
     }
+
+    pub fn abi_handle_not_registered(handle: u32) -> ClientError {
+        error(
+            ErrorCode::AbiHandleNotRegistered,
+            format!("ABI handle is not registered: {}", handle),
+        )
+    }
 }
@@ -24,10 +24,10 @@ pub struct ResultOfCalcFunctionId {
 /// Calculates contract function ID by contract ABI
 #[api_function]
 pub fn calc_function_id(
-    _context: Arc<ClientContext>,
+    context: Arc<ClientContext>,
     params: ParamsOfCalcFunctionId,
 ) -> ClientResult<ResultOfCalcFunctionId> {
-    let abi = params.abi.abi()?;
+    let abi = params.abi.abi(&context)?;
     let function = abi.function(&params.function_name)
         .map_err(|_| Error::invalid_function_name(&params.function_name))?;
 
@@ -42,7 +42,7 @@ pub async fn update_initial_data(
 ) -> ClientResult<ResultOfUpdateInitialData> {
     let (_, mut data) = deserialize_cell_from_boc(&context, &params.data, "contract data").await?;
 
-    data = update_initial_data_internal(&params.initial_data, &params.abi, &params.initial_pubkey, data)?;
+    data = update_initial_data_internal(&context, &params.initial_data, &params.abi, &params.initial_pubkey, data)?;
 
     Ok(ResultOfUpdateInitialData {
         data: serialize_cell_to_boc(&context, data, "contract data", params.boc_cache).await?
@@ -50,6 +50,7 @@ pub async fn update_initial_data(
 }
 
 fn update_initial_data_internal(
+    context: &ClientContext,
     initial_data: &Option<Value>,
     abi: &Option<Abi>,
     initial_pubkey: &Option<String>,
@@ -59,7 +60,7 @@ fn update_initial_data_internal(
         Some(init_data) => {
             let abi = abi.as_ref()
                 .ok_or_else(|| Error::encode_init_data_failed("contract ABI required to set initial data"))?
-                .json_string()?;
+                .json_string(context)?;
             ton_abi::json_abi::update_contract_data(&abi, &init_data.to_string(), data.into())
                 .map_err(|err| Error::encode_init_data_failed(err))?
                 .into_cell()
@@ -107,6 +108,7 @@ pub async fn encode_initial_data(
     params: ParamsOfEncodeInitialData,
 ) -> ClientResult<ResultOfEncodeInitialData> {
     let data = update_initial_data_internal(
+        &context,
         &params.initial_data,
         &params.abi,
         &params.initial_pubkey,
@@ -156,7 +158,7 @@ pub async fn decode_initial_data(
         .ok_or_else(|| Error::invalid_data_for_decode("no public key in contract data"))?;
 
     let initial_data = if let Some(abi) = params.abi {
-        let abi = abi.abi()?;
+        let abi = abi.abi(&context)?;
 
         let tokens = abi.decode_data(data, params.allow_partial)
             .map_err(|e| Error::invalid_data_for_decode(e))?;
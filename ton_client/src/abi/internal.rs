@@ -1,23 +1,26 @@
 use crate::ClientContext;
 use crate::boc::internal::deserialize_cell_from_boc;
-use crate::abi::{Error, Signer, DeploySet};
+use crate::abi::{Abi, Error, Signer, DeploySet};
+use crate::crypto::boxes::signing_box::SigningBoxMessage;
 use crate::crypto::internal::decode_public_key;
 use crate::encoding::hex_decode;
 use crate::error::ClientResult;
 use std::sync::Arc;
 use serde_json::Value;
+use ton_block::Deserializable;
 use ton_sdk::ContractImage;
+use ton_types::BuilderData;
 
 /// Combines `hex` encoded `signature` with `base64` encoded `unsigned_message`.
 /// Returns signed message encoded with `base64`.
 pub(crate) fn add_sign_to_message(
-    abi: &str,
+    abi: &ton_abi::Contract,
     signature: &[u8],
     public_key: Option<&[u8]>,
     unsigned_message: &[u8],
 ) -> ClientResult<Vec<u8>> {
-    let signed = ton_sdk::Contract::add_sign_to_message(
-        abi.to_string(),
+    let signed = ton_sdk::Contract::attach_signature(
+        abi,
         signature,
         public_key,
         unsigned_message,
@@ -29,14 +32,14 @@ pub(crate) fn add_sign_to_message(
 /// Combines `hex` encoded `signature` with `base64` encoded `unsigned_message`.
 /// Returns signed message encoded with `base64`.
 pub(crate) fn add_sign_to_message_body(
-    abi: &str,
+    abi: &ton_abi::Contract,
     signature: &[u8],
     public_key: Option<&[u8]>,
     unsigned_body: &[u8],
 ) -> ClientResult<Vec<u8>> {
     let unsigned = ton_sdk::Contract::deserialize_tree_to_slice(unsigned_body)
         .map_err(|err| Error::attach_signature_failed(err))?;
-    let body = ton_abi::add_sign_to_function_call(abi.to_string(), signature, public_key, unsigned)
+    let body = abi.add_sign_to_encoded_input(signature, public_key, unsigned)
         .map_err(|err| Error::attach_signature_failed(err))?;
     Ok(ton_types::serialize_toc(
         &body
@@ -46,15 +49,29 @@ pub(crate) fn add_sign_to_message_body(
     .map_err(|err| Error::attach_signature_failed(err))?)
 }
 
+/// Extracts destination address and unsigned body from serialized unsigned message.
+pub(crate) fn signing_message_from_message(message: &[u8]) -> ClientResult<SigningBoxMessage> {
+    let message = ton_block::Message::construct_from_bytes(message)
+        .map_err(|err| Error::attach_signature_failed(err))?;
+    let body = message.body().unwrap_or_default();
+    Ok(SigningBoxMessage {
+        dst: message.dst(),
+        unsigned_body: BuilderData::from_slice(&body)
+            .into_cell()
+            .map_err(|err| Error::attach_signature_failed(err))?,
+    })
+}
+
 pub(crate) async fn try_to_sign_message(
     context: Arc<ClientContext>,
-    abi: &str,
+    abi: &ton_abi::Contract,
     message: Vec<u8>,
     data_to_sign: Option<Vec<u8>>,
     signer: &Signer,
 ) -> ClientResult<(Vec<u8>, Option<Vec<u8>>)> {
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = signer.sign(context.clone(), unsigned).await? {
+        let signing_message = signing_message_from_message(&message)?;
+        if let Some(signature) = signer.sign_message(context.clone(), &signing_message, unsigned).await? {
             let pubkey = signer.resolve_public_key(context)
                 .await?
                 .map(|string| hex_decode(&string))
@@ -70,7 +87,7 @@ pub(crate) async fn try_to_sign_message(
 
 pub(crate) async fn create_tvc_image(
     context: &ClientContext,
-    abi: &str,
+    abi: &Abi,
     init_params: Option<&Value>,
     tvc: &String,
 ) -> ClientResult<ContractImage> {
@@ -82,7 +99,7 @@ pub(crate) async fn create_tvc_image(
         .map_err(|err| Error::invalid_tvc_image(err))?;
 
     if let Some(params) = init_params {
-        image.update_data(&params.to_string(), abi).map_err(|err| {
+        image.update_data(&params.to_string(), &abi.json_string(context)?).map_err(|err| {
             Error::invalid_tvc_image(format!("Failed to set initial data: {}", err))
         })?;
     }
@@ -25,6 +25,7 @@ pub(crate) mod init_data;
 
 mod errors;
 mod internal;
+pub(crate) mod registry;
 mod signing;
 mod types;
 
@@ -56,6 +57,7 @@ pub use init_data::{
     ParamsOfEncodeInitialData, ParamsOfDecodeInitialData, ParamsOfUpdateInitialData,
     ResultOfEncodeInitialData, ResultOfDecodeInitialData, ResultOfUpdateInitialData,
 };
+pub use registry::{register_abi, remove_abi, ParamsOfRegisterAbi, RegisteredAbi};
 pub use signing::Signer;
 pub use types::{
     Abi, AbiContract, AbiData, AbiEvent, AbiFunction, AbiHandle, AbiParam, FunctionHeader,
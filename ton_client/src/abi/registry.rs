@@ -0,0 +1,79 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use crate::abi::{Abi, AbiHandle, Error};
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+
+/// ABI stored in the client context. Keeps both the source JSON (required by
+/// functions that still pass ABI to `ton_abi` as a string, e.g. initial data update)
+/// and the parsed contract.
+pub(crate) struct RegisteredAbiEntry {
+    pub json: String,
+    pub contract: Arc<ton_abi::Contract>,
+}
+
+pub(crate) fn get_registered_abi(
+    context: &ClientContext,
+    handle: &AbiHandle,
+) -> ClientResult<Arc<RegisteredAbiEntry>> {
+    context
+        .abis
+        .get(&handle.0)
+        .map(|entry| entry.1.clone())
+        .ok_or_else(|| Error::abi_handle_not_registered(handle.0))
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfRegisterAbi {
+    /// Contract ABI to register.
+    pub abi: Abi,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct RegisteredAbi {
+    /// Handle of the registered ABI.
+    pub handle: AbiHandle,
+}
+
+/// Parses the ABI once and stores it in the client context.
+///
+/// The returned handle can be passed as `Abi::Handle` to any function that accepts
+/// ABI, so the ABI JSON is neither sent nor parsed again on each call.
+/// Registered ABI lives until it is removed with `remove_abi` or the context is destroyed.
+#[api_function]
+pub fn register_abi(
+    context: Arc<ClientContext>,
+    params: ParamsOfRegisterAbi,
+) -> ClientResult<RegisteredAbi> {
+    let json = params.abi.json_string(&context)?;
+    let contract = ton_abi::Contract::load(json.as_bytes())
+        .map(Arc::new)
+        .map_err(|x| Error::invalid_json(x))?;
+
+    let id = context.get_next_id();
+    context.abis.insert(id, Arc::new(RegisteredAbiEntry { json, contract }));
+
+    Ok(RegisteredAbi {
+        handle: AbiHandle(id),
+    })
+}
+
+/// Removes ABI from the client context.
+#[api_function]
+pub fn remove_abi(context: Arc<ClientContext>, params: RegisteredAbi) -> ClientResult<()> {
+    context.abis.remove(&params.handle.0);
+    Ok(())
+}
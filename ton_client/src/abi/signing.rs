@@ -1,5 +1,8 @@
 use crate::ClientContext;
 use crate::crypto::{KeyPair, SigningBoxHandle};
+use crate::crypto::boxes::signing_box::{
+    signing_box_check_abi_signing, signing_box_sign_message, SigningBoxMessage,
+};
 use crate::error::ClientResult;
 use std::sync::Arc;
 
@@ -58,12 +61,31 @@ impl Signer {
         }
     }
 
+    /// Signs data of the ABI message. Signing boxes receive the message itself, so they
+    /// are able to inspect what they sign.
+    pub(crate) async fn sign_message(
+        &self,
+        context: Arc<ClientContext>,
+        message: &SigningBoxMessage,
+        data_to_sign: &[u8],
+    ) -> ClientResult<Option<Vec<u8>>> {
+        match self {
+            Signer::SigningBox { handle } => {
+                signing_box_sign_message(context, handle, message, data_to_sign)
+                    .await
+                    .map(Some)
+            },
+            _ => self.sign(context, data_to_sign).await,
+        }
+    }
+
     pub async fn resolve_public_key(&self, context: Arc<ClientContext>) -> ClientResult<Option<String>> {
         match self {
             Signer::None => Ok(None),
             Signer::Keys { keys } => Ok(Some(keys.public.clone())),
             Signer::External { public_key } => Ok(Some(public_key.clone())),
             Signer::SigningBox { handle } => {
+                signing_box_check_abi_signing(&context, handle)?;
                 crate::crypto::signing_box_get_public_key(
                     context,
                     crate::crypto::RegisteredSigningBox {
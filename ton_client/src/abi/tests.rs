@@ -81,6 +81,7 @@ fn encode_v2() {
         }),
         signer: signing,
         processing_try_index: None,
+        boc_serialization: None,
     };
 
     let unsigned: ResultOfEncodeMessage = client
@@ -158,6 +159,7 @@ fn encode_v2() {
         }),
         signer: signing,
         processing_try_index: None,
+        boc_serialization: None,
     };
     let body_params = |run_params: ParamsOfEncodeMessage| ParamsOfEncodeMessageBody {
         abi: run_params.abi,
@@ -409,7 +411,7 @@ async fn test_resolve_pubkey() -> Result<()> {
         tvc: tvc.clone(),
         ..Default::default()
     };
-    let mut image = create_tvc_image(&context, "", None, &tvc).await?;
+    let mut image = create_tvc_image(&context, &Abi::default(), None, &tvc).await?;
     assert!(resolve_pubkey(&deploy_set, &image, &None)?.is_none());
 
     let external_pub_key =
@@ -523,7 +525,7 @@ async fn test_encode_message_pubkey_internal(
     expected_pubkey: &Option<ed25519_dalek::PublicKey>,
 ) -> Result<()> {
     let context = crate::ClientContext::new(crate::ClientConfig::default()).unwrap();
-    let mut image = create_tvc_image(&context, &abi.json_string()?, None, &tvc).await?;
+    let mut image = create_tvc_image(&context, abi, None, &tvc).await?;
     if let Some(tvc_pubkey) = tvc_pubkey {
         image.set_public_key(tvc_pubkey)?;
     }
@@ -546,6 +548,7 @@ async fn test_encode_message_pubkey_internal(
             Signer::None
         },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -575,10 +578,10 @@ fn gen_pubkey() -> ed25519_dalek::PublicKey {
 async fn test_encode_internal_message() -> Result<()> {
     let client = TestClient::new();
     let (abi, tvc) = TestClient::package(HELLO, None);
-    let contract = Contract::load(abi.json_string().unwrap().as_bytes()).unwrap();
+    let contract = Contract::load(abi.json_string(&client.context()).unwrap().as_bytes()).unwrap();
     let func_id = contract.function("sayHello").unwrap().get_input_id();
     let context = crate::ClientContext::new(crate::ClientConfig::default()).unwrap();
-    let image = create_tvc_image(&context, &abi.json_string()?, None, &tvc).await?;
+    let image = create_tvc_image(&context, &abi, None, &tvc).await?;
     let address =
         String::from("0:1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef");
 
@@ -835,7 +838,10 @@ async fn test_encode_internal_message_deploy(
     let code_from_tvc: ResultOfGetCodeFromTvc = client
         .request_async(
             "boc.get_code_from_tvc",
-            ParamsOfGetCodeFromTvc { tvc: tvc.clone() },
+            ParamsOfGetCodeFromTvc {
+                tvc: tvc.clone(),
+                ..Default::default()
+            },
         )
         .await?;
 
@@ -1148,3 +1154,97 @@ fn test_calc_function_id() {
 
     assert_eq!(result.function_id, 0xF744C7E2);
 }
+
+#[test]
+fn test_abi_handle() {
+    let client = TestClient::new();
+    let abi = TestClient::abi("GiverV2", Some(2));
+
+    let registered: RegisteredAbi = client.request(
+        "abi.register_abi",
+        ParamsOfRegisterAbi { abi: abi.clone() },
+    ).unwrap();
+    let handle = Abi::Handle(registered.handle.clone());
+
+    assert_eq!(
+        handle.json_string(&client.context()).unwrap(),
+        abi.json_string(&client.context()).unwrap(),
+    );
+
+    let result: ResultOfCalcFunctionId = client.request(
+        "abi.calc_function_id",
+        ParamsOfCalcFunctionId {
+            abi: handle.clone(),
+            function_name: "getMessages".to_owned(),
+            ..Default::default()
+        },
+    ).unwrap();
+
+    assert_eq!(result.function_id, 0x7744C7E2);
+
+    let keys = KeyPair {
+        public: "4c7c408ff1ddebb8d6405ee979c716a14fdd6cc08124107a61d3c25597099499".into(),
+        secret: "cc8929d635719612a9478b9cd17675a39cfad52d8959e8a177389b8c0b9122a7".into(),
+    };
+    let encode = |abi: Abi| {
+        client.request::<_, ResultOfEncodeMessage>(
+            "abi.encode_message",
+            ParamsOfEncodeMessage {
+                abi,
+                address: Some(
+                    "0:ece57bcc6c530283becbbd8a3b24d3c5987cdddc3c8b7b33be6e4a6312490415".into(),
+                ),
+                deploy_set: None,
+                call_set: Some(CallSet {
+                    function_name: "sendTransaction".into(),
+                    header: Some(FunctionHeader {
+                        pubkey: None,
+                        time: Some(1599458364291),
+                        expire: Some(1599458404),
+                    }),
+                    input: Some(json!({
+                        "dest": "0:1111111111111111111111111111111111111111111111111111111111111111",
+                        "value": "1000000000",
+                        "bounce": false,
+                    })),
+                }),
+                signer: Signer::Keys { keys: keys.clone() },
+                processing_try_index: None,
+                boc_serialization: None,
+            },
+        )
+        .unwrap()
+    };
+    let by_handle = encode(handle.clone());
+    let by_json = encode(abi.clone());
+    assert_eq!(by_handle.message, by_json.message);
+    assert_eq!(by_handle.message_id, by_json.message_id);
+
+    let decode = |abi: Abi| {
+        client.request::<_, DecodedMessageBody>(
+            "abi.decode_message",
+            ParamsOfDecodeMessage {
+                abi,
+                message: by_json.message.clone(),
+                allow_partial: false,
+            },
+        )
+        .unwrap()
+    };
+    let decoded = decode(handle.clone());
+    assert_eq!(decoded, decode(abi.clone()));
+    assert_eq!(decoded.name, "sendTransaction");
+
+    let _: () = client.request("abi.remove_abi", registered).unwrap();
+
+    let error = client.request::<_, ResultOfCalcFunctionId>(
+        "abi.calc_function_id",
+        ParamsOfCalcFunctionId {
+            abi: handle,
+            function_name: "getMessages".to_owned(),
+            ..Default::default()
+        },
+    ).unwrap_err();
+
+    assert_eq!(error.code, ErrorCode::AbiHandleNotRegistered as u32);
+}
@@ -1,3 +1,4 @@
+use crate::abi::registry::get_registered_abi;
 use crate::abi::{Error, ParamsOfEncodeMessage};
 use crate::error::{ClientError, ClientResult};
 use crate::{processing, ClientContext};
@@ -5,8 +6,14 @@ use std::convert::TryInto;
 use std::sync::Arc;
 use ton_abi::{Token, TokenValue};
 
-#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
-pub struct AbiHandle(u32);
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct AbiHandle(pub u32);
+
+impl From<u32> for AbiHandle {
+    fn from(handle: u32) -> Self {
+        Self(handle)
+    }
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
 #[serde(tag = "type", content = "value")]
@@ -25,21 +32,23 @@ impl Default for Abi {
 }
 
 impl Abi {
-    pub(crate) fn json_string(&self) -> ClientResult<String> {
+    pub(crate) fn json_string(&self, context: &ClientContext) -> ClientResult<String> {
         match self {
             Self::Contract(abi) | Self::Serialized(abi) => {
                 Ok(serde_json::to_string(abi).map_err(|err| Error::invalid_abi(err))?)
             }
             Self::Json(abi) => Ok(abi.clone()),
-            _ => Err(crate::client::Error::not_implemented(
-                "ABI handles are not supported yet",
-            )),
+            Self::Handle(handle) => Ok(get_registered_abi(context, handle)?.json.clone()),
         }
     }
 
-    pub(crate) fn abi(&self) -> ClientResult<ton_abi::Contract> {
-        ton_abi::Contract::load(self.json_string()?.as_bytes())
-            .map_err(|x| Error::invalid_json(x))
+    pub(crate) fn abi(&self, context: &ClientContext) -> ClientResult<Arc<ton_abi::Contract>> {
+        match self {
+            Self::Handle(handle) => Ok(get_registered_abi(context, handle)?.contract.clone()),
+            _ => ton_abi::Contract::load(self.json_string(context)?.as_bytes())
+                .map(Arc::new)
+                .map_err(|x| Error::invalid_json(x)),
+        }
     }
 }
 
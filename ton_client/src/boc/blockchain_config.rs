@@ -11,11 +11,12 @@
 * limitations under the License.
 */
 
-use crate::boc::Error;
+use crate::boc::{BocCacheType, Error};
 use crate::client::ClientContext;
 use crate::error::ClientResult;
-use ton_block::Serializable;
-use super::internal::deserialize_object_from_boc;
+use serde_json::{Map, Value};
+use ton_block::{ConfigParams, Serializable};
+use super::internal::{deserialize_object_from_boc, serialize_object_to_boc};
 
 #[derive(Serialize, Deserialize, Clone, ApiType, Default)]
 pub struct ParamsOfGetBlockchainConfig {
@@ -84,3 +85,102 @@ pub(crate) fn extract_config_from_zerostate(zerostate: ton_block::ShardStateUnsp
 
     Ok(master.config().clone())
 }
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfParseBlockchainConfig {
+    /// Blockchain config BOC encoded as base64 or BOC handle
+    pub config_boc: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfParseBlockchainConfig {
+    /// Config params as a JSON object with `p0`, `p1`, ... fields.
+    ///
+    /// JSON structure is compatible with GraphQL API block `master.config` object.
+    pub config: Value,
+}
+
+/// Parses blockchain config BOC into a JSON.
+#[api_function]
+pub async fn parse_blockchain_config(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfParseBlockchainConfig,
+) -> ClientResult<ResultOfParseBlockchainConfig> {
+    let config = deserialize_object_from_boc::<ConfigParams>(
+        &context, &params.config_boc, "blockchain config"
+    ).await?;
+    Ok(ResultOfParseBlockchainConfig {
+        config: Value::Object(serialize_config(&config.object)?),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfUpdateBlockchainConfig {
+    /// Blockchain config BOC encoded as base64 or BOC handle
+    pub config_boc: String,
+    /// Config params to set as a JSON object with `pN` fields
+    /// in the format returned by `parse_blockchain_config`.
+    pub params: Value,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfUpdateBlockchainConfig {
+    /// Updated blockchain config BOC encoded as base64 or BOC handle
+    pub config_boc: String,
+}
+
+/// Sets or replaces blockchain config params.
+///
+/// Params that are not specified in `params` are kept untouched.
+/// Resulting config can be passed to `ExecutionOptions.blockchain_config`.
+#[api_function]
+pub async fn update_blockchain_config(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfUpdateBlockchainConfig,
+) -> ClientResult<ResultOfUpdateBlockchainConfig> {
+    let mut config = deserialize_object_from_boc::<ConfigParams>(
+        &context, &params.config_boc, "blockchain config"
+    ).await?.object;
+
+    let updates = params.params.as_object().ok_or_else(|| {
+        Error::invalid_blockchain_config("`params` must be an object with `pN` fields")
+    })?;
+    let mut indexes = Vec::new();
+    for key in updates.keys() {
+        let index = key
+            .strip_prefix('p')
+            .and_then(|index| index.parse::<u32>().ok())
+            .ok_or_else(|| Error::invalid_blockchain_config(format!("invalid param name `{}`", key)))?;
+        indexes.push(index);
+    }
+
+    // Updated params are parsed along with the current ones
+    // because some params are validated against the others
+    let mut merged = serialize_config(&config)?;
+    merged.extend(updates.clone());
+    let updated = ton_block_json::parse_config(&merged)
+        .map_err(|err| Error::invalid_blockchain_config(err))?;
+
+    for index in indexes {
+        let param = updated
+            .config(index)
+            .map_err(|err| Error::invalid_blockchain_config(err))?
+            .ok_or_else(|| Error::invalid_blockchain_config(format!("param `p{}` is not supported", index)))?;
+        config
+            .set_config(param)
+            .map_err(|err| Error::invalid_blockchain_config(err))?;
+    }
+
+    Ok(ResultOfUpdateBlockchainConfig {
+        config_boc: serialize_object_to_boc(&context, &config, "blockchain config", params.boc_cache).await?,
+    })
+}
+
+fn serialize_config(config: &ConfigParams) -> ClientResult<Map<String, Value>> {
+    let mut map = Map::new();
+    ton_block_json::serialize_config(&mut map, config, ton_block_json::SerializationMode::QServer)
+        .map_err(|err| Error::serialization_error(err, "blockchain config"))?;
+    Ok(map)
+}
@@ -11,19 +11,23 @@
 * limitations under the License.
 */
 
-use crate::boc::internal::{deserialize_cell_from_boc, serialize_cell_to_base64, DeserializedBoc};
-use crate::client::ClientContext;
+use crate::boc::internal::{
+    deserialize_cell_from_boc, serialize_cell_to_base64_ex, serialize_cell_to_bytes, DeserializedBoc,
+};
+use crate::client::storage::KeyValueStorage;
+use crate::client::{ClientContext, LocalStorage};
 use crate::error::ClientResult;
-use super::Error;
+use super::{BocConfig, BocSerializationOptions, Error};
 
 use lru::LruCache;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Cursor;
 use std::iter::FromIterator;
 use std::sync::Arc;
 #[allow(unused_imports)]
 use std::str::FromStr;
-use tokio::sync::{Mutex, RwLock};
-use ton_types::{Cell, UInt256};
+use tokio::sync::{Mutex, MutexGuard, RwLock};
+use ton_types::{deserialize_tree_of_cells, Cell, UInt256};
 
 
 pub const SHA256_SIZE: usize = 32;
@@ -106,24 +110,195 @@ pub struct CachedBocs {
     cache_size: usize,
 }
 
+const PERSISTENT_STORAGE_NAME: &str = "bocs";
+const PERSISTENT_INDEX_KEY: &str = "index";
+
+#[derive(Serialize, Deserialize)]
+struct PersistentBocInfo {
+    hash: String,
+    size: usize,
+}
+
+/// BOCs stored in the local storage. Index keeps sizes of the stored BOCs and the order
+/// they were stored in. It is saved along with BOCs to restore the cache size after
+/// the context restart.
+///
+/// Several contexts can share the same storage path: before the index is saved, entries
+/// stored by other contexts are re-read from the storage and merged into it, so they are
+/// not lost when the last writer rewrites the index.
+pub struct PersistentBocs {
+    storage: Arc<dyn KeyValueStorage>,
+    sizes: HashMap<UInt256, usize>,
+    order: VecDeque<UInt256>,
+    // entries removed since the last index save, they must not be merged back
+    removed: HashSet<UInt256>,
+    cache_size: usize,
+}
+
+impl PersistentBocs {
+    async fn load(storage: Arc<dyn KeyValueStorage>) -> ClientResult<Self> {
+        let mut result = Self {
+            storage,
+            sizes: HashMap::new(),
+            order: VecDeque::new(),
+            removed: HashSet::new(),
+            cache_size: 0,
+        };
+        result.merge_stored_index().await?;
+        Ok(result)
+    }
+
+    /// Appends entries of the index saved in the storage which are not known to this cache
+    async fn merge_stored_index(&mut self) -> ClientResult<()> {
+        let index: Vec<PersistentBocInfo> = match self.storage.get_str(PERSISTENT_INDEX_KEY).await? {
+            Some(index) => serde_json::from_str(&index).unwrap_or_else(|err| {
+                log::warn!("Persistent BOC cache index is corrupted and will be reset: {}", err);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        for info in index {
+            if let Ok(hash) = UInt256::from_str(&info.hash) {
+                if self.removed.contains(&hash) || self.sizes.contains_key(&hash) {
+                    continue;
+                }
+                self.sizes.insert(hash.clone(), info.size);
+                self.order.push_back(hash);
+                self.cache_size += info.size;
+            }
+        }
+        Ok(())
+    }
+
+    async fn save_index(&mut self) -> ClientResult<()> {
+        self.merge_stored_index().await?;
+        self.removed.clear();
+        let index: Vec<PersistentBocInfo> = self.order
+            .iter()
+            .map(|hash| PersistentBocInfo { hash: hash.as_hex_string(), size: self.sizes[hash] })
+            .collect();
+        let index = serde_json::to_string(&index)
+            .map_err(|err| Error::serialization_error(err, "persistent BOC cache index"))?;
+        self.storage.put_str(PERSISTENT_INDEX_KEY, &index).await
+    }
+
+    async fn remove(&mut self, hash: &UInt256) -> ClientResult<()> {
+        if let Some(size) = self.sizes.remove(hash) {
+            self.order.retain(|entry| entry != hash);
+            self.cache_size -= size;
+        }
+        self.removed.insert(hash.clone());
+        // the BOC may be already removed by another context sharing the storage
+        if let Err(err) = self.storage.remove(&hash.as_hex_string()).await {
+            log::warn!("Persistent BOC cache remove error: {}", err.message);
+        }
+        Ok(())
+    }
+
+    async fn get(&mut self, hash: &UInt256) -> ClientResult<Option<Cell>> {
+        if !self.sizes.contains_key(hash) {
+            return Ok(None);
+        }
+        let cell = self.storage.get_bin(&hash.as_hex_string()).await?
+            .and_then(|bytes| deserialize_tree_of_cells(&mut Cursor::new(&bytes)).ok())
+            // virtualized cells are stored with their content hash
+            .filter(|cell| cell.repr_hash() == *hash);
+        if cell.is_none() {
+            // entries that can not be loaded are pruned so they don't count against the quota
+            self.remove(hash).await?;
+            self.save_index().await?;
+        }
+        Ok(cell)
+    }
+
+    async fn add(&mut self, hash: UInt256, cell: &Cell, max_cache_size: usize) -> ClientResult<()> {
+        if self.sizes.contains_key(&hash) {
+            return Ok(());
+        }
+        let bytes = serialize_cell_to_bytes(cell, "BOC")?;
+        if bytes.len() > max_cache_size {
+            return Ok(());
+        }
+        // BOCs stored by other contexts count against the quota as well
+        self.merge_stored_index().await?;
+        if self.sizes.contains_key(&hash) {
+            return Ok(());
+        }
+        while self.cache_size + bytes.len() > max_cache_size {
+            match self.order.front().cloned() {
+                Some(removed) => self.remove(&removed).await?,
+                None => break,
+            }
+        }
+        self.storage.put_bin(&hash.as_hex_string(), &bytes).await?;
+        self.cache_size += bytes.len();
+        self.sizes.insert(hash.clone(), bytes.len());
+        self.order.push_back(hash);
+        self.save_index().await
+    }
+}
+
 pub struct Bocs {
     pinned: RwLock<HashMap<UInt256, PinnedBoc>>,
     cached: Mutex<CachedBocs>,
     max_cache_size: usize,
+    persistent: Mutex<Option<PersistentBocs>>,
+    max_persistent_cache_size: usize,
+    local_storage_path: Option<String>,
+}
+
+fn kilobytes_to_bytes(size: u32) -> usize {
+    (size as usize)
+        .checked_mul(1024)
+        .unwrap_or(std::usize::MAX)
 }
 
 impl Bocs {
-    pub(crate) fn new(max_cache_size: u32) -> Self {
-        let max_cache_size = (max_cache_size as usize)
-            .checked_mul(1024) // kilobytes in config
-            .unwrap_or(std::usize::MAX);
+    pub(crate) fn new(config: &BocConfig, local_storage_path: Option<String>) -> Self {
         Bocs {
             pinned: RwLock::default(),
             cached:Mutex::new(CachedBocs {
                 bocs: LruCache::unbounded(),
                 cache_size: 0,
             }),
-            max_cache_size,
+            max_cache_size: kilobytes_to_bytes(config.cache_max_size),
+            persistent: Mutex::new(None),
+            max_persistent_cache_size: kilobytes_to_bytes(config.persistent_cache_max_size),
+            local_storage_path,
+        }
+    }
+
+    async fn persistent(&self) -> ClientResult<MutexGuard<'_, Option<PersistentBocs>>> {
+        let mut lock = self.persistent.lock().await;
+        if lock.is_none() {
+            let storage = LocalStorage::new(
+                self.local_storage_path.clone(),
+                PERSISTENT_STORAGE_NAME.to_owned(),
+            ).await?;
+            *lock = Some(PersistentBocs::load(Arc::new(storage)).await?);
+        }
+        Ok(lock)
+    }
+
+    async fn get_persistent(&self, hash: &UInt256) -> ClientResult<Option<Cell>> {
+        if self.max_persistent_cache_size == 0 {
+            return Ok(None);
+        }
+        match self.persistent().await?.as_mut() {
+            Some(persistent) => persistent.get(hash).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn add_persistent(&self, hash: &UInt256, cell: &Cell) -> ClientResult<()> {
+        if self.max_persistent_cache_size == 0 {
+            return Ok(());
+        }
+        match self.persistent().await?.as_mut() {
+            Some(persistent) => {
+                persistent.add(hash.clone(), cell, self.max_persistent_cache_size).await
+            }
+            None => Ok(()),
         }
     }
 
@@ -202,7 +377,19 @@ impl Bocs {
             return Some(cell);
         }
 
-        None
+        match self.get_persistent(&hash).await {
+            Ok(Some(cell)) => {
+                let size = calc_tree_size(&cell);
+                // BOC is returned even if it doesn't fit into the in-memory cache
+                let _ = self.add_cached(hash.clone(), cell.clone(), size).await;
+                Some(cell)
+            }
+            Ok(None) => None,
+            Err(err) => {
+                log::warn!("Persistent BOC cache read error: {}", err.message);
+                None
+            }
+        }
     }
 
     pub(crate) async fn add(
@@ -211,13 +398,18 @@ impl Bocs {
         let hash = cell.repr_hash();
         log::debug!("Bocs::add {:x}", hash);
         match cache_type {
-            BocCacheType::Pinned { pin } => self.add_new_pinned(hash.clone(), pin, cell).await,
+            BocCacheType::Pinned { pin } => {
+                self.add_new_pinned(hash.clone(), pin, cell).await
+            }
             BocCacheType::Unpinned => {
                 if let Some(_) = self.get_cached(&hash).await {
                     return Ok(hash);
                 }
                 let size = size.unwrap_or_else(|| calc_tree_size(&cell));
-                self.add_cached(hash.clone(), cell, size).await?;
+                self.add_cached(hash.clone(), cell.clone(), size).await?;
+                if let Err(err) = self.add_persistent(&hash, &cell).await {
+                    log::warn!("Persistent BOC cache write error: {}", err.message);
+                }
             }
         }
         Ok(hash)
@@ -273,6 +465,8 @@ pub async fn cache_set(
 pub struct ParamsOfBocCacheGet {
     /// Reference to the cached BOC
     pub boc_ref: String,
+    /// BOC serialization options
+    pub boc_serialization: Option<BocSerializationOptions>,
 }
 
 #[derive(Serialize, Deserialize, Clone, ApiType, Default)]
@@ -292,7 +486,7 @@ pub async fn cache_get(
     let boc = context.bocs
         .get(&hash)
         .await
-        .map(|cell| serialize_cell_to_base64(&cell, "BOC"))
+        .map(|cell| serialize_cell_to_base64_ex(&cell, "BOC", params.boc_serialization.as_ref()))
         .transpose()?;
     
     Ok( ResultOfBocCacheGet { boc })
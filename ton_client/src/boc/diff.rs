@@ -0,0 +1,283 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::boc::internal::{bits_to_hex_string, deserialize_cell_from_boc};
+use crate::boc::Error;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use std::collections::{HashMap, HashSet};
+use ton_types::{Cell, UInt256};
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfDiff {
+    /// First BOC encoded as base64 or BOC handle
+    pub boc1: String,
+    /// Second BOC encoded as base64 or BOC handle
+    pub boc2: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum CellDiff {
+    /// Cell data differs.
+    DataChanged {
+        /// Path of the cell: indexes of references starting from the root cell
+        path: Vec<u32>,
+        /// Cell data in the first BOC in bitstring notation
+        data1: String,
+        /// Cell data in the second BOC in bitstring notation
+        data2: String,
+        /// Cell data length in bits in the first BOC
+        bit_length1: u32,
+        /// Cell data length in bits in the second BOC
+        bit_length2: u32,
+        /// Index of the first different bit
+        first_different_bit: u32,
+    },
+    /// Cell type differs, e.g. exotic cell is replaced with an ordinary one.
+    CellTypeChanged {
+        /// Path of the cell
+        path: Vec<u32>,
+        /// Cell type in the first BOC
+        cell_type1: String,
+        /// Cell type in the second BOC
+        cell_type2: String,
+    },
+    /// Cell in the second BOC has a reference missing in the first BOC.
+    ReferenceAdded {
+        /// Path of the added referenced cell
+        path: Vec<u32>,
+        /// Hash of the added cell encoded with hex
+        hash: String,
+    },
+    /// Cell in the second BOC lacks a reference present in the first BOC.
+    ReferenceRemoved {
+        /// Path of the removed referenced cell
+        path: Vec<u32>,
+        /// Hash of the removed cell encoded with hex
+        hash: String,
+    },
+    /// Referenced cell is replaced with the subtree found elsewhere in the first BOC.
+    /// Such subtrees are reported in `shared_subtrees` and are not compared further.
+    ReferenceReplaced {
+        /// Path of the referenced cell
+        path: Vec<u32>,
+        /// Hash of the cell in the first BOC encoded with hex
+        hash1: String,
+        /// Hash of the cell in the second BOC encoded with hex
+        hash2: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Debug, PartialEq)]
+pub struct SharedSubtree {
+    /// Subtree root hash encoded with hex
+    pub hash: String,
+    /// Path of the subtree root in the first BOC
+    pub path1: Vec<u32>,
+    /// Path of the subtree root in the second BOC
+    pub path2: Vec<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfDiff {
+    /// `true` if BOCs have the same root hash
+    pub equal: bool,
+    /// Differences found in cells located on the same paths
+    pub differences: Vec<CellDiff>,
+    /// Subtrees of the second BOC found on a different path in the first BOC
+    pub shared_subtrees: Vec<SharedSubtree>,
+}
+
+/// Compares two BOCs cell by cell.
+///
+/// Cells located on the same path in both BOCs are compared recursively
+/// until their hashes match. For each pair of different cells the data difference
+/// and added or removed references are reported. If a cell of the second BOC
+/// is found by hash on a different path of the first BOC, it is reported
+/// as a shared subtree instead of being compared.
+///
+/// Each pair of cells is compared once: if the same pair is reachable by several paths,
+/// the differences are reported for the first path only.
+#[api_function]
+pub async fn diff(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfDiff,
+) -> ClientResult<ResultOfDiff> {
+    let (_, cell1) = deserialize_cell_from_boc(&context, &params.boc1, "boc1").await?;
+    let (_, cell2) = deserialize_cell_from_boc(&context, &params.boc2, "boc2").await?;
+
+    let mut differ = CellDiffer::new(&cell1)?;
+    differ.compare(&mut Vec::new(), &cell1, &cell2)?;
+
+    Ok(ResultOfDiff {
+        equal: cell1.repr_hash() == cell2.repr_hash(),
+        differences: differ.differences,
+        shared_subtrees: differ.shared_subtrees,
+    })
+}
+
+struct CellDiffer {
+    paths1: HashMap<UInt256, Vec<u32>>,
+    compared: HashSet<(UInt256, UInt256)>,
+    searched: HashSet<UInt256>,
+    differences: Vec<CellDiff>,
+    shared_subtrees: Vec<SharedSubtree>,
+}
+
+impl CellDiffer {
+    fn new(root1: &Cell) -> ClientResult<Self> {
+        let mut paths1 = HashMap::new();
+        index_cells(&mut paths1, &mut Vec::new(), root1)?;
+        Ok(Self {
+            paths1,
+            compared: HashSet::new(),
+            searched: HashSet::new(),
+            differences: Vec::new(),
+            shared_subtrees: Vec::new(),
+        })
+    }
+
+    fn compare(&mut self, path: &mut Vec<u32>, cell1: &Cell, cell2: &Cell) -> ClientResult<()> {
+        if cell1.repr_hash() == cell2.repr_hash()
+            || !self.compared.insert((cell1.repr_hash(), cell2.repr_hash()))
+        {
+            return Ok(());
+        }
+
+        if cell1.cell_type() != cell2.cell_type() {
+            self.differences.push(CellDiff::CellTypeChanged {
+                path: path.clone(),
+                cell_type1: format!("{:?}", cell1.cell_type()),
+                cell_type2: format!("{:?}", cell2.cell_type()),
+            });
+        }
+
+        let (bit_length1, bit_length2) = (cell1.bit_length(), cell2.bit_length());
+        if let Some(first_different_bit) =
+            first_different_bit(cell1.data(), bit_length1, cell2.data(), bit_length2)
+        {
+            self.differences.push(CellDiff::DataChanged {
+                path: path.clone(),
+                data1: bits_to_hex_string(cell1.data(), bit_length1),
+                data2: bits_to_hex_string(cell2.data(), bit_length2),
+                bit_length1: bit_length1 as u32,
+                bit_length2: bit_length2 as u32,
+                first_different_bit: first_different_bit as u32,
+            });
+        }
+
+        let refs_count = std::cmp::max(cell1.references_count(), cell2.references_count());
+        for index in 0..refs_count {
+            path.push(index as u32);
+            match (reference(cell1, index)?, reference(cell2, index)?) {
+                (Some(child1), Some(child2)) => {
+                    if child1.repr_hash() != child2.repr_hash() && self.is_shared(path, &child2) {
+                        self.differences.push(CellDiff::ReferenceReplaced {
+                            path: path.clone(),
+                            hash1: child1.repr_hash().as_hex_string(),
+                            hash2: child2.repr_hash().as_hex_string(),
+                        });
+                    } else {
+                        self.compare(path, &child1, &child2)?;
+                    }
+                }
+                (None, Some(child2)) => {
+                    self.differences.push(CellDiff::ReferenceAdded {
+                        path: path.clone(),
+                        hash: child2.repr_hash().as_hex_string(),
+                    });
+                    self.find_shared(path, &child2)?;
+                }
+                (Some(child1), None) => {
+                    self.differences.push(CellDiff::ReferenceRemoved {
+                        path: path.clone(),
+                        hash: child1.repr_hash().as_hex_string(),
+                    });
+                }
+                (None, None) => {}
+            }
+            path.pop();
+        }
+        Ok(())
+    }
+
+    /// Reports the cell as shared if it is found on a different path in the first BOC
+    fn is_shared(&mut self, path2: &Vec<u32>, cell2: &Cell) -> bool {
+        match self.paths1.get(&cell2.repr_hash()) {
+            Some(path1) if path1 != path2 => {
+                self.shared_subtrees.push(SharedSubtree {
+                    hash: cell2.repr_hash().as_hex_string(),
+                    path1: path1.clone(),
+                    path2: path2.clone(),
+                });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Searches the added subtree for the cells shared with the first BOC
+    fn find_shared(&mut self, path2: &mut Vec<u32>, cell2: &Cell) -> ClientResult<()> {
+        if !self.searched.insert(cell2.repr_hash()) || self.is_shared(path2, cell2) {
+            return Ok(());
+        }
+        for index in 0..cell2.references_count() {
+            path2.push(index as u32);
+            if let Some(child) = reference(cell2, index)? {
+                self.find_shared(path2, &child)?;
+            }
+            path2.pop();
+        }
+        Ok(())
+    }
+}
+
+fn index_cells(
+    paths: &mut HashMap<UInt256, Vec<u32>>,
+    path: &mut Vec<u32>,
+    cell: &Cell,
+) -> ClientResult<()> {
+    if paths.contains_key(&cell.repr_hash()) {
+        return Ok(());
+    }
+    paths.insert(cell.repr_hash(), path.clone());
+    for index in 0..cell.references_count() {
+        path.push(index as u32);
+        if let Some(child) = reference(cell, index)? {
+            index_cells(paths, path, &child)?;
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+fn reference(cell: &Cell, index: usize) -> ClientResult<Option<Cell>> {
+    if index >= cell.references_count() {
+        return Ok(None);
+    }
+    cell.reference(index)
+        .map(Some)
+        .map_err(|err| Error::invalid_boc(err))
+}
+
+fn get_bit(data: &[u8], index: usize) -> bool {
+    data[index / 8] & (0x80 >> (index % 8)) != 0
+}
+
+fn first_different_bit(data1: &[u8], len1: usize, data2: &[u8], len2: usize) -> Option<usize> {
+    let common = std::cmp::min(len1, len2);
+    (0..common)
+        .find(|index| get_bit(data1, *index) != get_bit(data2, *index))
+        .or(if len1 != len2 { Some(common) } else { None })
+}
@@ -1,18 +1,23 @@
+use std::collections::HashSet;
 use std::slice::Iter;
 
 use crate::{error::ClientResult, ClientContext};
 use serde_json::Value;
-use ton_block::Serializable;
-use ton_types::{BuilderData, Cell, IBitstring};
+use futures::future::BoxFuture;
+use ton_block::{Serializable, StateInit, StateInitLib};
+use ton_types::{BuilderData, HashmapE, HashmapType, IBitstring};
 
-use super::{internal::serialize_cell_to_boc, Error};
+use super::{internal::serialize_cell_to_boc_ex, Error};
 use crate::boc::internal::deserialize_cell_from_boc;
-use crate::boc::BocCacheType;
+use crate::boc::{BocCacheType, BocSerializationOptions};
 use num_bigint::{BigInt, BigUint, Sign};
 use num_traits::Num;
 use std::ops::ShlAssign;
 use crate::encoding::account_decode;
 
+/// Dictionary key must fit into a cell.
+const MAX_DICT_KEY_SIZE: u32 = 1023;
+
 /// Cell builder operation.
 #[derive(Serialize, Deserialize, Clone, ApiType)]
 #[serde(tag = "type")]
@@ -61,7 +66,49 @@ pub enum BuilderOp {
     Address {
         /// Address in a common `workchain:account` or base64 format.
         address: String,
-    }
+    },
+    /// Append coins amount as `VarUInteger 16` (`Grams`).
+    Coins {
+        /// Amount in nanotokens:
+        /// - `Number` containing integer number. e.g. `123`.
+        /// - Decimal string. e.g. `"123"`.
+        /// - `0x` prefixed hexadecimal string. e.g `0x123`.
+        value: Value,
+    },
+    /// Append `Maybe ^Cell`: bit `1` followed by the ref to the cell
+    /// or bit `0` if neither `builder` nor `boc` is specified.
+    Maybe {
+        /// Nested cell builder.
+        builder: Option<Vec<BuilderOp>>,
+        /// Nested cell BOC encoded with `base64` or BOC cache key.
+        boc: Option<String>,
+    },
+    /// Append `HashmapE` dictionary.
+    Dict {
+        /// Bit size of the keys, from 1 to 1023.
+        key_size: u32,
+        /// Dictionary entries. Keys must be unique.
+        entries: Vec<BuilderDictEntry>,
+    },
+    /// Append `StateInit` structure.
+    StateInit {
+        /// Contract code BOC encoded as base64 or BOC handle
+        code: Option<String>,
+        /// Contract data BOC encoded as base64 or BOC handle
+        data: Option<String>,
+        /// Root cell of the contract libraries dictionary `HashmapE 256 SimpleLib`
+        /// encoded as base64 or BOC handle. Not a library code BOC.
+        library: Option<String>,
+    },
+}
+
+/// Dictionary entry for the `BuilderOp::Dict` operation.
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct BuilderDictEntry {
+    /// Entry key. Integer of `key_size` bits in the same format as `BuilderOp::Integer` value.
+    pub key: Value,
+    /// Builder operations for the entry value.
+    pub value: Vec<BuilderOp>,
 }
 
 impl Default for BuilderOp {
@@ -80,6 +127,8 @@ pub struct ParamsOfEncodeBoc {
     /// Cache type to put the result.
     /// The BOC itself returned if no cache type provided.
     pub boc_cache: Option<BocCacheType>,
+    /// BOC serialization options. Ignored if `boc_cache` is specified.
+    pub boc_serialization: Option<BocSerializationOptions>,
 }
 
 #[derive(Serialize, Deserialize, Clone, ApiType, Default)]
@@ -97,27 +146,51 @@ pub async fn encode_boc(
     context: std::sync::Arc<ClientContext>,
     params: ParamsOfEncodeBoc,
 ) -> ClientResult<ResultOfEncodeBoc> {
-    let mut stack = Vec::<Builder>::new();
-    let mut builder = Builder::new(&params.builder);
-    loop {
-        match builder.build(&context).await? {
-            BuildResult::Nested { nested, prev } => {
-                stack.push(prev);
-                builder = nested;
-            }
-            BuildResult::Complete(cell) => {
-                if let Some(prev) = stack.pop() {
-                    builder = prev;
-                    builder.result.append_reference_cell(cell);
-                } else {
-                    return Ok(ResultOfEncodeBoc {
-                        boc: serialize_cell_to_boc(&context, cell, "encoded cell", params.boc_cache)
-                            .await?,
-                    });
+    let cell = encode_builder(&context, &params.builder)
+        .await?
+        .into_cell()
+        .map_err(|err| Error::serialization_error(err, "encode_boc"))?;
+    Ok(ResultOfEncodeBoc {
+        boc: serialize_cell_to_boc_ex(
+            &context,
+            cell,
+            "encoded cell",
+            params.boc_cache,
+            params.boc_serialization.as_ref(),
+        )
+        .await?,
+    })
+}
+
+/// Builds cell data using builder operations.
+/// Boxed because dictionary entries are built recursively.
+fn encode_builder<'a>(
+    context: &'a std::sync::Arc<ClientContext>,
+    operations: &'a Vec<BuilderOp>,
+) -> BoxFuture<'a, ClientResult<BuilderData>> {
+    Box::pin(async move {
+        let mut stack = Vec::<Builder>::new();
+        let mut builder = Builder::new(operations);
+        loop {
+            match builder.build(context).await? {
+                BuildResult::Nested { nested, prev } => {
+                    stack.push(prev);
+                    builder = nested;
+                }
+                BuildResult::Complete(data) => {
+                    if let Some(prev) = stack.pop() {
+                        builder = prev;
+                        let cell = data
+                            .into_cell()
+                            .map_err(|err| Error::serialization_error(err, "encode_boc"))?;
+                        builder.result.append_reference_cell(cell);
+                    } else {
+                        return Ok(data);
+                    }
                 }
             }
         }
-    }
+    })
 }
 
 struct Builder<'a> {
@@ -130,7 +203,7 @@ enum BuildResult<'a> {
         nested: Builder<'a>,
         prev: Builder<'a>,
     },
-    Complete(Cell),
+    Complete(BuilderData),
 }
 
 impl<'a> Builder<'a> {
@@ -173,15 +246,119 @@ impl<'a> Builder<'a> {
                         .write_to(&mut self.result)
                         .map_err(|err| Error::invalid_boc(err))?;
                 }
+                BuilderOp::Coins { value } => {
+                    append_coins(&mut self.result, value)?;
+                }
+                BuilderOp::Maybe { builder, boc } => match (builder, boc) {
+                    (Some(_), Some(_)) => {
+                        return Err(Error::serialization_error(
+                            "only one of `builder` or `boc` can be specified",
+                            "Maybe",
+                        ));
+                    }
+                    (Some(builder), None) => {
+                        append_bit(&mut self.result, true, "Maybe")?;
+                        return Ok(BuildResult::Nested {
+                            nested: Self::new(builder),
+                            prev: self,
+                        });
+                    }
+                    (None, Some(boc)) => {
+                        let cell = deserialize_cell_from_boc(context, boc, "Maybe").await?.1;
+                        append_bit(&mut self.result, true, "Maybe")?;
+                        self.result.append_reference_cell(cell);
+                    }
+                    (None, None) => {
+                        append_bit(&mut self.result, false, "Maybe")?;
+                    }
+                },
+                BuilderOp::Dict { key_size, entries } => {
+                    if *key_size == 0 || *key_size > MAX_DICT_KEY_SIZE {
+                        return Err(Error::serialization_error(
+                            format!("key size must be from 1 to {}, got {}", MAX_DICT_KEY_SIZE, key_size),
+                            "Dict",
+                        ));
+                    }
+                    let mut dict = HashmapE::with_bit_len(*key_size as usize);
+                    let mut keys = HashSet::new();
+                    for entry in entries {
+                        let mut key = BuilderData::new();
+                        append_integer(&mut key, *key_size as usize, &entry.key)?;
+                        let key = key
+                            .into_cell()
+                            .map_err(|err| Error::serialization_error(err, "Dict key"))?;
+                        if !keys.insert(key.repr_hash()) {
+                            return Err(Error::serialization_error(
+                                format!("duplicate key {}", entry.key),
+                                "Dict",
+                            ));
+                        }
+                        let value = encode_builder(context, &entry.value).await?;
+                        dict.set_builder(key.into(), &value)
+                            .map_err(|err| Error::serialization_error(err, "Dict"))?;
+                    }
+                    dict.write_hashmap_data(&mut self.result)
+                        .map_err(|err| Error::serialization_error(err, "Dict"))?;
+                }
+                BuilderOp::StateInit { code, data, library } => {
+                    let mut state_init = StateInit::default();
+                    if let Some(code) = code {
+                        state_init.code =
+                            Some(deserialize_cell_from_boc(context, code, "StateInit code").await?.1);
+                    }
+                    if let Some(data) = data {
+                        state_init.data =
+                            Some(deserialize_cell_from_boc(context, data, "StateInit data").await?.1);
+                    }
+                    if let Some(library) = library {
+                        state_init.library = StateInitLib::with_hashmap(Some(
+                            deserialize_cell_from_boc(context, library, "StateInit library").await?.1,
+                        ));
+                    }
+                    state_init
+                        .write_to(&mut self.result)
+                        .map_err(|err| Error::serialization_error(err, "StateInit"))?;
+                }
             }
         }
-        Ok(BuildResult::Complete(self.result.into_cell().map_err(
-            |err| Error::serialization_error(err, "encode_boc"),
-        )?))
+        Ok(BuildResult::Complete(self.result))
+    }
+}
+
+fn append_bit(builder: &mut BuilderData, bit: bool, name: &str) -> ClientResult<()> {
+    builder
+        .append_bit_bool(bit)
+        .map_err(|err| Error::serialization_error(err, name))?;
+    Ok(())
+}
+
+/// Appends `VarUInteger 16` value.
+fn append_coins(builder: &mut BuilderData, value: &Value) -> ClientResult<()> {
+    let number = if let Some(value) = value.as_u64() {
+        BigUint::from(value)
+    } else if let Some(string) = value.as_str() {
+        let string = string.trim();
+        let (string, radix) = match string.strip_prefix("0x").or_else(|| string.strip_prefix("0X")) {
+            Some(hex) => (hex, 16),
+            None => (string, 10),
+        };
+        BigUint::from_str_radix(string, radix)
+            .map_err(|err| Error::serialization_error(err, "Coins"))?
+    } else {
+        return Err(Error::serialization_error(
+            "Coins value must be a non negative number or a string representation",
+            "builder operations",
+        ));
+    };
+    let len = (number.bits() as usize + 7) / 8;
+    if len >= 16 {
+        return Err(Error::serialization_error("value is too big", "Coins"));
     }
+    append_number(builder, false, BigUint::from(len), 4, "Coins")?;
+    append_number(builder, false, number, len * 8, "Coins")
 }
 
-fn append_integer(builder: &mut BuilderData, size: usize, value: &Value) -> ClientResult<()> {
+pub(crate) fn append_integer(builder: &mut BuilderData, size: usize, value: &Value) -> ClientResult<()> {
     if let Some(value) = value.as_i64() {
         append_number(
             builder,
@@ -205,7 +382,7 @@ fn append_integer(builder: &mut BuilderData, size: usize, value: &Value) -> Clie
 /// If count of the significant bits in `number` is less than `size`
 /// then the buffer will be padded with bit `0` for positive
 /// or bit `1` for `negative`.
-fn append_number(
+pub(crate) fn append_number(
     builder: &mut BuilderData,
     negative: bool,
     number: BigUint,
@@ -271,7 +448,7 @@ fn parse_string(
 }
 
 /// Append bitstring canonical (extended with `n` prefix) representation.
-fn append_bitstring(builder: &mut BuilderData, string: &str) -> ClientResult<()> {
+pub(crate) fn append_bitstring(builder: &mut BuilderData, string: &str) -> ClientResult<()> {
     let mut num_str = string.trim();
 
     // Try parse direct binary form
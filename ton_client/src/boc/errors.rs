@@ -13,6 +13,7 @@
 
 use crate::error::ClientError;
 use std::fmt::Display;
+use ton_types::UInt256;
 
 #[derive(ApiType)]
 pub enum ErrorCode {
@@ -23,6 +24,14 @@ pub enum ErrorCode {
     InsufficientCacheSize = 205,
     BocRefNotFound = 206,
     InvalidBocRef = 207,
+    InvalidCellQuery = 208,
+    InvalidTlbSchema = 209,
+    TlbDataMismatch = 210,
+    InvalidCellText = 211,
+    InvalidMerkleProof = 212,
+    CellNotFound = 213,
+    InvalidBlockchainConfig = 214,
+    LibraryNotFound = 215,
 }
 pub struct Error;
 
@@ -83,4 +92,64 @@ impl Error {
         error.data["boc_ref"] = boc_ref.into();
         error
     }
+
+    pub fn invalid_cell_query<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::InvalidCellQuery,
+            format!("Invalid cell query: {}", err),
+        )
+    }
+
+    pub fn invalid_tlb_schema<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::InvalidTlbSchema,
+            format!("Invalid TL-B schema: {}", err),
+        )
+    }
+
+    pub fn tlb_data_mismatch<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::TlbDataMismatch,
+            format!("Data does not match TL-B schema: {}", err),
+        )
+    }
+
+    pub fn invalid_cell_text<E: Display>(err: E, line: usize) -> ClientError {
+        let mut error = error(
+            ErrorCode::InvalidCellText,
+            format!("Invalid cell text at line {}: {}", line, err),
+        );
+        error.data["line"] = line.into();
+        error
+    }
+
+    pub fn invalid_merkle_proof<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::InvalidMerkleProof,
+            format!("Invalid Merkle proof: {}", err),
+        )
+    }
+
+    pub fn cell_not_found<E: Display>(cell: E) -> ClientError {
+        error(
+            ErrorCode::CellNotFound,
+            format!("Cell not found: {}", cell),
+        )
+    }
+
+    pub fn invalid_blockchain_config<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::InvalidBlockchainConfig,
+            format!("Invalid blockchain config: {}", err),
+        )
+    }
+
+    pub fn library_not_found(hash: &UInt256) -> ClientError {
+        let mut error = error(
+            ErrorCode::LibraryNotFound,
+            format!("Library cell {:x} not found. Add it using `boc.add_libraries`", hash),
+        );
+        error.data["library_hash"] = hash.as_hex_string().into();
+        error
+    }
 }
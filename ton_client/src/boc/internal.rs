@@ -12,13 +12,14 @@
 */
 
 use crate::ClientContext;
-use crate::boc::{BocCacheType, Error};
+use crate::boc::{BocCacheType, BocSerializationOptions, Error};
 use crate::error::ClientResult;
 use std::io::Cursor;
 #[allow(unused_imports)]
 use std::str::FromStr;
 use ton_block::{Deserializable, Serializable};
-use ton_types::{UInt256, deserialize_tree_of_cells};
+use num_bigint::{BigInt, BigUint};
+use ton_types::{SliceData, UInt256, deserialize_tree_of_cells};
 
 pub(crate) fn get_boc_hash(boc: &[u8]) -> ClientResult<String> {
     let cells = deserialize_tree_of_cells(&mut Cursor::new(boc))
@@ -117,6 +118,127 @@ pub(crate) fn serialize_cell_to_base64(cell: &ton_types::Cell, name: &str) -> Cl
     Ok(base64::encode(&serialize_cell_to_bytes(cell, name)?))
 }
 
+const BOC_GENERIC_TAG: [u8; 4] = [0xb5, 0xee, 0x9c, 0x72];
+const BOC_HAS_INDEX: u8 = 0x80;
+const BOC_HAS_CRC32C: u8 = 0x40;
+const BOC_HAS_CACHE_BITS: u8 = 0x20;
+const CRC32C: crc::Crc<u32> = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI);
+
+/// Serializes cell using the serialization options.
+/// Without options the result is the same as `serialize_cell_to_bytes` returns.
+pub(crate) fn serialize_cell_to_bytes_ex(
+    cell: &ton_types::Cell,
+    name: &str,
+    options: Option<&BocSerializationOptions>,
+) -> ClientResult<Vec<u8>> {
+    let options = match options {
+        Some(options) => options,
+        None => return serialize_cell_to_bytes(cell, name),
+    };
+    let cache_bits = options.cache_bits.unwrap_or(false);
+    let index = options.index.unwrap_or(false) || cache_bits;
+    let offset_size = options
+        .offset_size
+        .map(|size| match size {
+            1..=8 => Ok(size as usize),
+            _ => Err(Error::serialization_error(
+                format!("offset size must be from 1 to 8 bytes, {} provided", size),
+                name,
+            )),
+        })
+        .transpose()?;
+
+    let mut bytes = Vec::new();
+    ton_types::BagOfCells::with_root(cell)
+        .write_to_ex(&mut bytes, index, false, None, if cache_bits { None } else { offset_size })
+        .map_err(|err| Error::serialization_error(err, name))?;
+    if cache_bits {
+        bytes = add_cache_bits(&bytes, offset_size)
+            .map_err(|err| Error::serialization_error(err, name))?;
+    }
+    if options.crc.unwrap_or(false) {
+        bytes[4] |= BOC_HAS_CRC32C;
+        let crc = CRC32C.checksum(&bytes);
+        bytes.extend_from_slice(&crc.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+pub(crate) fn serialize_cell_to_base64_ex(
+    cell: &ton_types::Cell,
+    name: &str,
+    options: Option<&BocSerializationOptions>,
+) -> ClientResult<String> {
+    Ok(base64::encode(&serialize_cell_to_bytes_ex(cell, name, options)?))
+}
+
+/// Sets the cache bits flag of the BOC with index.
+///
+/// Each index entry is extended with the cache bit (cleared for all cells), so the offsets
+/// are doubled and can require larger offset size.
+fn add_cache_bits(boc: &[u8], offset_size: Option<usize>) -> Result<Vec<u8>, String> {
+    fn read(boc: &[u8], pos: &mut usize, size: usize) -> Result<u64, String> {
+        let bytes = boc
+            .get(*pos..*pos + size)
+            .ok_or_else(|| "unexpected end of BOC".to_string())?;
+        *pos += size;
+        Ok(bytes.iter().fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+    fn write(boc: &mut Vec<u8>, value: u64, size: usize) {
+        boc.extend_from_slice(&value.to_be_bytes()[8 - size..]);
+    }
+
+    if boc.len() < 6 || boc[..4] != BOC_GENERIC_TAG || boc[4] & BOC_HAS_INDEX == 0 {
+        return Err("BOC with index expected".to_string());
+    }
+    let flags = boc[4];
+    let ref_size = (flags & 0x07) as usize;
+    let old_offset_size = boc[5] as usize;
+    let mut pos = 6;
+    let cells = read(boc, &mut pos, ref_size)?;
+    let roots = read(boc, &mut pos, ref_size)?;
+    let absent = read(boc, &mut pos, ref_size)?;
+    let total_size = read(boc, &mut pos, old_offset_size)?;
+    let roots_start = pos;
+    pos += roots as usize * ref_size;
+    let roots_end = pos;
+    let mut offsets = Vec::with_capacity(cells as usize);
+    for _ in 0..cells {
+        offsets.push(read(boc, &mut pos, old_offset_size)?);
+    }
+    let data = boc
+        .get(pos..pos + total_size as usize)
+        .ok_or_else(|| "unexpected end of BOC".to_string())?;
+
+    let max_value = total_size << 1;
+    let min_offset_size = std::cmp::max(1, (64 - max_value.leading_zeros() as usize + 7) / 8);
+    let offset_size = match offset_size {
+        Some(size) if size < min_offset_size => {
+            return Err(format!(
+                "offset size {} is too small for BOC with cache bits, at least {} required",
+                size, min_offset_size
+            ))
+        }
+        Some(size) => size,
+        None => min_offset_size,
+    };
+
+    let mut result = Vec::with_capacity(boc.len() + offsets.len() * offset_size);
+    result.extend_from_slice(&BOC_GENERIC_TAG);
+    result.push(flags | BOC_HAS_CACHE_BITS);
+    result.push(offset_size as u8);
+    write(&mut result, cells, ref_size);
+    write(&mut result, roots, ref_size);
+    write(&mut result, absent, ref_size);
+    write(&mut result, total_size, offset_size);
+    result.extend_from_slice(&boc[roots_start..roots_end]);
+    for offset in offsets {
+        write(&mut result, offset << 1, offset_size);
+    }
+    result.extend_from_slice(data);
+    Ok(result)
+}
+
 pub(crate) fn serialize_object_to_base64<S: Serializable>(
     object: &S,
     name: &str,
@@ -125,6 +247,83 @@ pub(crate) fn serialize_object_to_base64<S: Serializable>(
     Ok(serialize_cell_to_base64(&cell, name)?)
 }
 
+/// Reads unsigned integer of `size` bits.
+pub(crate) fn read_biguint(slice: &mut SliceData, size: usize) -> ton_types::Result<BigUint> {
+    let bytes = slice.get_next_bits(size)?;
+    Ok(BigUint::from_bytes_be(&bytes) >> ((8 - size % 8) % 8))
+}
+
+/// Reads two's complement signed integer of `size` bits.
+pub(crate) fn read_bigint(slice: &mut SliceData, size: usize) -> ton_types::Result<BigInt> {
+    let unsigned = BigInt::from(read_biguint(slice, size)?);
+    if size > 0 && unsigned >= BigInt::from(1) << (size - 1) {
+        Ok(unsigned - (BigInt::from(1) << size))
+    } else {
+        Ok(unsigned)
+    }
+}
+
+/// Dictionary keys up to 64 bits are represented as decimal numbers,
+/// longer keys (hashes, account ids) as zero padded hex strings.
+pub(crate) fn format_dict_key(key: &BigUint, key_size: usize) -> String {
+    if key_size <= 64 {
+        key.to_string()
+    } else {
+        format!("{:0>width$}", key.to_str_radix(16), width = (key_size + 3) / 4)
+    }
+}
+
+/// Parses dictionary key formatted with `format_dict_key`.
+pub(crate) fn parse_dict_key(key: &str, key_size: usize) -> Option<BigUint> {
+    let radix = if key_size <= 64 { 10 } else { 16 };
+    BigUint::parse_bytes(key.as_bytes(), radix)
+}
+
+/// Formats `bits` high bits of `data` using the hexadecimal bitstring notation
+/// (see `TON VM specification` 1.0). If the bit length is not a multiple of 4,
+/// the completion tag is appended and the string is ended with `_`.
+pub(crate) fn bits_to_hex_string(data: &[u8], bits: usize) -> String {
+    let mut bytes = data[..(bits + 7) / 8].to_vec();
+    if bits % 4 != 0 {
+        let last = bits / 8;
+        bytes[last] &= !(0xFFu8 >> (bits % 8));
+        bytes[last] |= 0x80 >> (bits % 8);
+    }
+    let mut string = hex::encode(&bytes).to_uppercase();
+    string.truncate((bits + 3) / 4);
+    if bits % 4 != 0 {
+        string.push('_');
+    }
+    string
+}
+
+/// Parses the hexadecimal bitstring notation produced by `bits_to_hex_string`.
+/// Returns data padded to the whole bytes and its length in bits.
+pub(crate) fn hex_string_to_bits(string: &str) -> Option<(Vec<u8>, usize)> {
+    let (digits, tagged) = match string.strip_suffix('_') {
+        Some(digits) => (digits, true),
+        None => (string, false),
+    };
+    let mut padded = digits.to_string();
+    if padded.len() % 2 != 0 {
+        padded.push('0');
+    }
+    let mut data = hex::decode(&padded).ok()?;
+    let mut bits = digits.len() * 4;
+    if tagged {
+        while bits > 0 && data[(bits - 1) / 8] & (0x80 >> ((bits - 1) % 8)) == 0 {
+            bits -= 1;
+        }
+        // remove the completion tag itself
+        bits = bits.checked_sub(1)?;
+    }
+    data.truncate((bits + 7) / 8);
+    if bits % 8 != 0 {
+        data[bits / 8] &= !(0xFFu8 >> (bits % 8));
+    }
+    Some((data, bits))
+}
+
 pub(crate) async fn deserialize_cell_from_boc(
     context: &ClientContext, boc: &str, name: &str
 ) -> ClientResult<(DeserializedBoc, ton_types::Cell)> {
@@ -173,19 +372,41 @@ pub(crate) fn deserialize_object_from_boc_bin<S: Deserializable>(
 
 pub(crate) async fn serialize_cell_to_boc(
     context: &ClientContext, cell: ton_types::Cell, name: &str, boc_cache: Option<BocCacheType>,
+) -> ClientResult<String> {
+    serialize_cell_to_boc_ex(context, cell, name, boc_cache, None).await
+}
+
+/// Puts cell into the BOC cache or serializes it using the serialization options.
+/// Options are ignored if cache type is provided.
+pub(crate) async fn serialize_cell_to_boc_ex(
+    context: &ClientContext,
+    cell: ton_types::Cell,
+    name: &str,
+    boc_cache: Option<BocCacheType>,
+    options: Option<&BocSerializationOptions>,
 ) -> ClientResult<String> {
     if let Some(cache_type) = boc_cache {
         context.bocs.add(cache_type, cell, None)
             .await
             .map(|hash| format!("*{:x}", hash))
     } else {
-        serialize_cell_to_base64(&cell, name)
+        serialize_cell_to_base64_ex(&cell, name, options)
     }
 }
 
 pub(crate) async fn serialize_object_to_boc<S: Serializable>(
     context: &ClientContext, object: &S,name: &str, boc_cache: Option<BocCacheType>,
+) -> ClientResult<String> {
+    serialize_object_to_boc_ex(context, object, name, boc_cache, None).await
+}
+
+pub(crate) async fn serialize_object_to_boc_ex<S: Serializable>(
+    context: &ClientContext,
+    object: &S,
+    name: &str,
+    boc_cache: Option<BocCacheType>,
+    options: Option<&BocSerializationOptions>,
 ) -> ClientResult<String> {
     let cell = serialize_object_to_cell(object, name)?;
-    serialize_cell_to_boc(context, cell, name, boc_cache).await
+    serialize_cell_to_boc_ex(context, cell, name, boc_cache, options).await
 }
@@ -0,0 +1,210 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::boc::internal::{deserialize_cell_from_boc, deserialize_object_from_boc};
+use crate::boc::Error;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use crate::net::{query_collection, ParamsOfQueryCollection};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use ton_block::{Account, HashmapAugType, LibDescr, ShardStateUnsplit, SimpleLib, StateInit};
+use ton_types::{BuilderData, Cell, CellType, HashmapE, HashmapType, IBitstring, UInt256};
+
+/// Library cells available for the local code inspection and execution.
+#[derive(Default)]
+pub(crate) struct Libraries {
+    cells: RwLock<HashMap<UInt256, Cell>>,
+}
+
+impl Libraries {
+    fn add(&self, cell: Cell) -> UInt256 {
+        let hash = cell.repr_hash();
+        self.cells.write().unwrap().insert(hash.clone(), cell);
+        hash
+    }
+
+    fn remove(&self, hash: &UInt256) {
+        self.cells.write().unwrap().remove(hash);
+    }
+
+    fn clear(&self) {
+        self.cells.write().unwrap().clear();
+    }
+
+    pub fn snapshot(&self) -> HashMap<UInt256, Cell> {
+        self.cells.read().unwrap().clone()
+    }
+
+    /// Replaces the library reference cell with the library cell.
+    pub fn resolve(&self, cell: Cell) -> ClientResult<Cell> {
+        resolve_library(&self.cells.read().unwrap(), cell)
+    }
+}
+
+fn library_hash(cell: &Cell) -> Option<UInt256> {
+    // library reference cell data: 8-bit cell type followed by 256-bit library hash
+    if cell.cell_type() == CellType::LibraryReference && cell.data().len() >= 33 {
+        Some(UInt256::from_slice(&cell.data()[1..33]))
+    } else {
+        None
+    }
+}
+
+/// Replaces the library reference cell with the library cell.
+/// Other cells are returned as is.
+pub(crate) fn resolve_library(libraries: &HashMap<UInt256, Cell>, cell: Cell) -> ClientResult<Cell> {
+    match library_hash(&cell) {
+        Some(hash) => libraries
+            .get(&hash)
+            .cloned()
+            .ok_or_else(|| Error::library_not_found(&hash)),
+        None => Ok(cell),
+    }
+}
+
+/// Builds libraries dictionary in the `SimpleLib` format accepted by TVM.
+pub(crate) fn libraries_to_hashmap(libraries: &HashMap<UInt256, Cell>) -> ClientResult<HashmapE> {
+    let mut dict = HashmapE::with_bit_len(256);
+    for (hash, cell) in libraries {
+        let mut value = BuilderData::new();
+        value
+            .append_bit_one()
+            .and_then(|value| value.checked_append_reference(cell.clone()))
+            .map_err(|err| Error::serialization_error(err, "library"))?;
+        dict.set_builder(hash.clone().into(), &value)
+            .map_err(|err| Error::serialization_error(err, "libraries"))?;
+    }
+    Ok(dict)
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfAddLibraries {
+    /// Library cell BOCs encoded as base64 or BOC handles
+    pub libraries: Option<Vec<String>>,
+    /// Account BOC encoded as base64 or BOC handle. Libraries of the account state are added
+    pub account: Option<String>,
+    /// Contract TVC image BOC encoded as base64 or BOC handle. Libraries of the image are added
+    pub tvc: Option<String>,
+    /// Masterchain state BOC encoded as base64 or BOC handle.
+    /// Public libraries of the masterchain are added
+    pub masterchain_state: Option<String>,
+    /// Account address. Account is fetched from the network and libraries of its state are added
+    pub address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfAddLibraries {
+    /// Hashes of the added libraries encoded with hex
+    pub hashes: Vec<String>,
+}
+
+/// Adds library cells to the client library store.
+///
+/// Library reference cells (`EXOTIC` cells of the library type) found in the contract
+/// code are replaced with the stored libraries by `get_code_salt`, `get_compiler_version`,
+/// `get_code_from_tvc` (if `resolve_libraries` is set) and `tvm` module functions.
+#[api_function]
+pub async fn add_libraries(
+    context: Arc<ClientContext>,
+    params: ParamsOfAddLibraries,
+) -> ClientResult<ResultOfAddLibraries> {
+    let mut cells = Vec::new();
+    for boc in params.libraries.unwrap_or_default() {
+        cells.push(deserialize_cell_from_boc(&context, &boc, "library").await?.1);
+    }
+    let mut accounts = Vec::new();
+    if let Some(account) = params.account {
+        accounts.push(deserialize_object_from_boc::<Account>(&context, &account, "account").await?.object);
+    }
+    if let Some(address) = params.address {
+        let result = query_collection(
+            context.clone(),
+            ParamsOfQueryCollection {
+                collection: "accounts".to_owned(),
+                filter: Some(json!({ "id": { "eq": address } })),
+                result: "boc".to_owned(),
+                limit: Some(1),
+                order: None,
+            },
+        )
+        .await?
+        .result;
+        let boc = result
+            .get(0)
+            .and_then(|account| account["boc"].as_str())
+            .ok_or_else(|| Error::invalid_boc(format!("account {} not found", address)))?;
+        accounts.push(deserialize_object_from_boc::<Account>(&context, boc, "account").await?.object);
+    }
+    let mut libraries = Vec::new();
+    for account in accounts {
+        if let Some(state_init) = account.state_init() {
+            libraries.push(state_init.library.clone());
+        }
+    }
+    if let Some(tvc) = params.tvc {
+        libraries.push(deserialize_object_from_boc::<StateInit>(&context, &tvc, "TVC").await?.object.library);
+    }
+    for library in libraries {
+        library
+            .iterate_with_keys(|_: UInt256, lib: SimpleLib| {
+                cells.push(lib.root().clone());
+                Ok(true)
+            })
+            .map_err(|err| Error::invalid_boc(err))?;
+    }
+    if let Some(state) = params.masterchain_state {
+        deserialize_object_from_boc::<ShardStateUnsplit>(&context, &state, "masterchain state")
+            .await?
+            .object
+            .libraries()
+            .iterate_with_keys(|_: UInt256, lib: LibDescr| {
+                cells.push(lib.lib().clone());
+                Ok(true)
+            })
+            .map_err(|err| Error::invalid_boc(err))?;
+    }
+
+    Ok(ResultOfAddLibraries {
+        hashes: cells
+            .into_iter()
+            .map(|cell| context.libraries.add(cell).as_hex_string())
+            .collect(),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfRemoveLibraries {
+    /// Hashes of the libraries to remove encoded with hex. All libraries are removed if not specified
+    pub hashes: Option<Vec<String>>,
+}
+
+/// Removes libraries from the client library store.
+#[api_function]
+pub async fn remove_libraries(
+    context: Arc<ClientContext>,
+    params: ParamsOfRemoveLibraries,
+) -> ClientResult<()> {
+    match params.hashes {
+        Some(hashes) => {
+            for hash in hashes {
+                let hash = UInt256::from_str(&hash)
+                    .map_err(|err| Error::invalid_boc(format!("invalid library hash `{}`: {}", hash, err)))?;
+                context.libraries.remove(&hash);
+            }
+        }
+        None => context.libraries.clear(),
+    }
+    Ok(())
+}
@@ -0,0 +1,209 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::boc::internal::{
+    deserialize_cell_from_boc, deserialize_object_from_cell, serialize_cell_to_boc,
+    serialize_object_to_cell,
+};
+use crate::boc::{BocCacheType, Error};
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use ton_block::MerkleProof;
+use ton_types::{Cell, UInt256};
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfCreateMerkleProof {
+    /// Root BOC encoded as base64 or BOC handle
+    pub boc: String,
+    /// Paths of the cells to keep in the proof: indexes of references starting from the root cell
+    pub paths: Option<Vec<Vec<u32>>>,
+    /// Hashes of the cells to keep in the proof encoded with hex
+    pub hashes: Option<Vec<String>>,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfCreateMerkleProof {
+    /// MerkleProof cell BOC or BOC cache key.
+    pub proof: String,
+}
+
+/// Creates Merkle proof for the cells of the BOC.
+///
+/// Kept cells are included into the proof with their whole subtrees. Cells on the paths
+/// from the root to the kept cells are included with other references replaced
+/// by pruned branches. All other cells are pruned.
+#[api_function]
+pub async fn create_merkle_proof(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfCreateMerkleProof,
+) -> ClientResult<ResultOfCreateMerkleProof> {
+    let (_, root) = deserialize_cell_from_boc(&context, &params.boc, "root").await?;
+
+    let mut kept = HashSet::new();
+    let mut included = HashSet::new();
+    for path in params.paths.unwrap_or_default() {
+        let mut cell = root.clone();
+        included.insert(cell.repr_hash());
+        for index in &path {
+            if *index as usize >= cell.references_count() {
+                return Err(Error::cell_not_found(format!("path {:?}", path)));
+            }
+            cell = cell.reference(*index as usize).map_err(|err| Error::invalid_boc(err))?;
+            included.insert(cell.repr_hash());
+        }
+        kept.insert(cell.repr_hash());
+    }
+    let hashes = params.hashes.unwrap_or_default();
+    if !hashes.is_empty() {
+        let mut targets = HashSet::new();
+        for hash in &hashes {
+            targets.insert(UInt256::from_str(hash).map_err(|err| {
+                Error::cell_not_found(format!("invalid hash `{}`: {}", hash, err))
+            })?);
+        }
+        let mut visited = HashMap::new();
+        collect_ancestors(&root, &targets, &mut visited)?;
+        for hash in &targets {
+            if !visited.contains_key(hash) {
+                return Err(Error::cell_not_found(format!("hash {:x}", hash)));
+            }
+            kept.insert(hash.clone());
+        }
+        included.extend(
+            visited
+                .into_iter()
+                .filter_map(|(hash, on_path)| if on_path { Some(hash) } else { None }),
+        );
+    }
+
+    let mut subtrees = HashSet::new();
+    for hash in &kept {
+        if let Some(cell) = find_cell(&root, hash, &mut HashSet::new())? {
+            include_subtree(&cell, &mut included, &mut subtrees)?;
+        }
+    }
+
+    let proof = MerkleProof::create(&root, |hash| included.contains(hash))
+        .map_err(|err| Error::serialization_error(err, "Merkle proof"))?;
+    let proof = serialize_object_to_cell(&proof, "Merkle proof")?;
+    Ok(ResultOfCreateMerkleProof {
+        proof: serialize_cell_to_boc(&context, proof, "Merkle proof", params.boc_cache).await?,
+    })
+}
+
+/// Marks each visited cell with `true` if it is one of the targets or has one in its subtree.
+fn collect_ancestors(
+    cell: &Cell,
+    targets: &HashSet<UInt256>,
+    visited: &mut HashMap<UInt256, bool>,
+) -> ClientResult<bool> {
+    if let Some(on_path) = visited.get(&cell.repr_hash()) {
+        return Ok(*on_path);
+    }
+    let mut on_path = targets.contains(&cell.repr_hash());
+    for index in 0..cell.references_count() {
+        let child = cell.reference(index).map_err(|err| Error::invalid_boc(err))?;
+        on_path |= collect_ancestors(&child, targets, visited)?;
+    }
+    visited.insert(cell.repr_hash(), on_path);
+    Ok(on_path)
+}
+
+fn find_cell(cell: &Cell, hash: &UInt256, visited: &mut HashSet<UInt256>) -> ClientResult<Option<Cell>> {
+    if cell.repr_hash() == *hash {
+        return Ok(Some(cell.clone()));
+    }
+    if !visited.insert(cell.repr_hash()) {
+        return Ok(None);
+    }
+    for index in 0..cell.references_count() {
+        let child = cell.reference(index).map_err(|err| Error::invalid_boc(err))?;
+        if let Some(found) = find_cell(&child, hash, visited)? {
+            return Ok(Some(found));
+        }
+    }
+    Ok(None)
+}
+
+fn include_subtree(
+    cell: &Cell,
+    included: &mut HashSet<UInt256>,
+    visited: &mut HashSet<UInt256>,
+) -> ClientResult<()> {
+    if !visited.insert(cell.repr_hash()) {
+        return Ok(());
+    }
+    included.insert(cell.repr_hash());
+    for index in 0..cell.references_count() {
+        let child = cell.reference(index).map_err(|err| Error::invalid_boc(err))?;
+        include_subtree(&child, included, visited)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfVerifyMerkleProof {
+    /// MerkleProof cell BOC encoded as base64 or BOC handle
+    pub proof: String,
+    /// Expected hash of the proven cell encoded with hex
+    pub root_hash: String,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfVerifyMerkleProof {
+    /// Proven cell BOC or BOC cache key.
+    ///
+    /// If `boc_cache` is specified, the virtualized cell is cached, so the cache key
+    /// is equal to the expected root hash and the cell can be passed to other functions
+    /// as is. Otherwise the BOC contains the proof content with pruned branches.
+    pub boc: String,
+}
+
+/// Verifies Merkle proof against the expected root hash.
+///
+/// Checks that the proof is a valid MerkleProof cell and that its virtualized
+/// content has the expected hash.
+#[api_function]
+pub async fn verify_merkle_proof(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfVerifyMerkleProof,
+) -> ClientResult<ResultOfVerifyMerkleProof> {
+    let root_hash = UInt256::from_str(&params.root_hash)
+        .map_err(|err| Error::invalid_merkle_proof(format!("invalid root hash: {}", err)))?;
+    let (_, cell) = deserialize_cell_from_boc(&context, &params.proof, "Merkle proof").await?;
+    let proof: MerkleProof = deserialize_object_from_cell(cell, "Merkle proof")
+        .map_err(|err| Error::invalid_merkle_proof(err.message))?;
+    if proof.hash != root_hash {
+        return Err(Error::invalid_merkle_proof(format!(
+            "proof hash {:x} doesn't match expected hash {:x}",
+            proof.hash, root_hash,
+        )));
+    }
+    let virtualized = proof.proof.clone().virtualize(1);
+    if virtualized.repr_hash() != root_hash {
+        return Err(Error::invalid_merkle_proof(format!(
+            "virtualized cell hash {:x} doesn't match expected hash {:x}",
+            virtualized.repr_hash(), root_hash,
+        )));
+    }
+    let cell = if params.boc_cache.is_some() { virtualized } else { proof.proof };
+    Ok(ResultOfVerifyMerkleProof {
+        boc: serialize_cell_to_boc(&context, cell, "proven cell", params.boc_cache).await?,
+    })
+}
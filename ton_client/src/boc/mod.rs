@@ -15,11 +15,18 @@ use serde::{Deserialize, Deserializer};
 
 pub(crate) mod blockchain_config;
 pub(crate) mod cache;
+pub(crate) mod diff;
 pub(crate) mod encode;
 mod errors;
 pub(crate) mod common;
 pub(crate) mod internal;
+pub(crate) mod libraries;
+pub(crate) mod merkle;
 pub(crate) mod parse;
+pub(crate) mod reader;
+pub(crate) mod shardstate_iterator;
+pub(crate) mod text;
+pub(crate) mod tlb;
 pub(crate) mod tvc;
 
 #[cfg(test)]
@@ -30,22 +37,47 @@ pub use encode_external_in_message::{
     encode_external_in_message, ParamsOfEncodeExternalInMessage, ResultOfEncodeExternalInMessage,
 };
 pub use blockchain_config::{
-    get_blockchain_config, ParamsOfGetBlockchainConfig, ResultOfGetBlockchainConfig,
+    get_blockchain_config, parse_blockchain_config, update_blockchain_config,
+    ParamsOfGetBlockchainConfig, ParamsOfParseBlockchainConfig, ParamsOfUpdateBlockchainConfig,
+    ResultOfGetBlockchainConfig, ResultOfParseBlockchainConfig, ResultOfUpdateBlockchainConfig,
 };
 pub use cache::{
     cache_get, cache_set, cache_unpin, BocCacheType, ParamsOfBocCacheGet, ParamsOfBocCacheSet,
     ParamsOfBocCacheUnpin, ResultOfBocCacheGet, ResultOfBocCacheSet,
 };
-pub use encode::{encode_boc, BuilderOp, ParamsOfEncodeBoc, ResultOfEncodeBoc};
+pub use diff::{diff, CellDiff, ParamsOfDiff, ResultOfDiff, SharedSubtree};
+pub use encode::{encode_boc, BuilderDictEntry, BuilderOp, ParamsOfEncodeBoc, ResultOfEncodeBoc};
 pub use errors::{Error, ErrorCode};
 pub use common::{
     get_boc_depth, get_boc_hash,
     ParamsOfGetBocDepth, ResultOfGetBocDepth, ParamsOfGetBocHash, ResultOfGetBocHash,
 };
+pub use libraries::{
+    add_libraries, remove_libraries, ParamsOfAddLibraries, ParamsOfRemoveLibraries,
+    ResultOfAddLibraries,
+};
+pub use merkle::{
+    create_merkle_proof, verify_merkle_proof, ParamsOfCreateMerkleProof, ParamsOfVerifyMerkleProof,
+    ResultOfCreateMerkleProof, ResultOfVerifyMerkleProof,
+};
 pub use parse::{
     parse_account, parse_block, parse_message, parse_shardstate, parse_transaction, required_boc,
     source_boc, ParamsOfParse, ParamsOfParseShardstate, ResultOfParse,
 };
+pub use reader::{query_cell, ParamsOfQueryCell, ResultOfQueryCell};
+pub use shardstate_iterator::{
+    create_shardstate_iterator, remove_shardstate_iterator, shardstate_iterator_next,
+    ParamsOfCreateShardstateIterator, ParamsOfShardstateIteratorNext,
+    RegisteredShardstateIterator, ResultOfShardstateIteratorNext,
+};
+pub use text::{
+    cell_from_text, cell_to_text, ParamsOfCellFromText, ParamsOfCellToText, ResultOfCellFromText,
+    ResultOfCellToText,
+};
+pub use tlb::{
+    encode_tlb, parse_tlb, ParamsOfEncodeTlb, ParamsOfParseTlb, ResultOfEncodeTlb,
+    ResultOfParseTlb,
+};
 pub use tvc::{
     decode_tvc, encode_tvc, get_code_from_tvc, get_code_salt, get_compiler_version, get_compiler_version_from_cell, set_code_salt,
     ParamsOfDecodeTvc, ParamsOfEncodeTvc, ParamsOfGetCodeFromTvc, ParamsOfGetCodeSalt,
@@ -61,6 +93,12 @@ fn deserialize_cache_max_size<'de, D: Deserializer<'de>>(deserializer: D) -> Res
     Ok(Option::deserialize(deserializer)?.unwrap_or(default_cache_max_size()))
 }
 
+fn deserialize_persistent_cache_max_size<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u32, D::Error> {
+    Ok(Option::deserialize(deserializer)?.unwrap_or_default())
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone, ApiType)]
 pub struct BocConfig {
     /// Maximum BOC cache size in kilobytes. Default is 10 MB
@@ -69,12 +107,41 @@ pub struct BocConfig {
         deserialize_with = "deserialize_cache_max_size"
     )]
     pub cache_max_size: u32,
+    /// Maximum persistent BOC cache size in kilobytes. Default is 0 that disables
+    /// the persistent cache.
+    ///
+    /// Persistent cache keeps unpinned cached BOCs in the client local storage
+    /// (see `local_storage_path`) so they survive the client context restart. When the quota
+    /// is exceeded, the oldest BOCs are removed from the persistent cache.
+    #[serde(
+        default,
+        deserialize_with = "deserialize_persistent_cache_max_size"
+    )]
+    pub persistent_cache_max_size: u32,
 }
 
 impl Default for BocConfig {
     fn default() -> Self {
         Self {
             cache_max_size: default_cache_max_size(),
+            persistent_cache_max_size: 0,
         }
     }
 }
+
+/// BOC serialization flags.
+///
+/// Without options BOCs are serialized without index and checksum
+/// using the minimal reference and offset sizes.
+#[derive(Deserialize, Serialize, Debug, Clone, ApiType, Default, PartialEq)]
+pub struct BocSerializationOptions {
+    /// Append CRC32C checksum. Default is `false`.
+    pub crc: Option<bool>,
+    /// Include the cells index. Default is `false`.
+    pub index: Option<bool>,
+    /// Include the cache bits into the cells index. Implies `index`. Default is `false`.
+    pub cache_bits: Option<bool>,
+    /// Size of the cell offsets in bytes, from 1 to 8.
+    /// The minimal size fitting all offsets is used by default.
+    pub offset_size: Option<u8>,
+}
@@ -0,0 +1,70 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+pub(crate) mod parser;
+pub(crate) mod query;
+
+use crate::boc::internal::deserialize_cell_from_boc;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use parser::CellQuery;
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfQueryCell {
+    /// Cell BOC encoded as base64 or BOC handle
+    pub boc: String,
+    /// Query describing the cell layout.
+    pub query: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfQueryCell {
+    /// Values read from the cell
+    pub value: Value,
+}
+
+/// Reads values from a cell using the cell query.
+///
+/// Query is a whitespace separated sequence of fields `[-][name:]type`
+/// that are read from the cell one after another.
+/// Field without name is stored in the result with its index in the sequence.
+/// Field prefixed with `-` is read but not stored in the result.
+///
+/// Supported types:
+/// - `uN`, `iN` – unsigned and signed integer of `N` bits (1..256). Returned as decimal string.
+/// - `grams`, `coins` – `VarUInteger 16` amount. Returned as decimal string.
+/// - `bitsN` – `N` bits of raw data. Returned in bitstring notation, e.g. `1AB`, `2D9_`.
+/// - `address` – `MsgAddress`. Returned as `workchain:account` string or `null` for `addr_none`.
+/// - `cell` – next cell reference. Returned as BOC encoded with `base64`.
+/// - `ref(...)` – next cell reference read with the nested query.
+/// - `maybe(...)` – `Maybe` bit followed by the nested query. Returned as `null` if bit is `0`.
+/// - `either(...)(...)` – `Either` bit followed by the left or the right nested query.
+///   Returned as `{ "left": ... }` or `{ "right": ... }`.
+/// - `dict`, `dictN` – `HashmapE` with `N` bits keys (256 by default).
+///   Returns the count of entries. If followed by `(...)` returns an object
+///   with entry values read with the nested query. Keys up to 64 bits are
+///   represented as decimal numbers, longer keys as hex strings.
+///
+/// Example: `seqno:u32 -u32 owner:address wallets:dict267(ref(balance:coins))`
+#[api_function]
+pub async fn query_cell(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfQueryCell,
+) -> ClientResult<ResultOfQueryCell> {
+    let query = CellQuery::parse(params.query)?;
+    let (_, cell) = deserialize_cell_from_boc(&context, &params.boc, "cell").await?;
+    Ok(ResultOfQueryCell {
+        value: query::query_cell(&query, &cell)?,
+    })
+}
@@ -11,6 +11,7 @@
 * limitations under the License.
 */
 
+use crate::boc::Error;
 use crate::error::{ClientResult, ClientError};
 
 #[derive(Debug)]
@@ -18,8 +19,16 @@ pub(crate) enum CellValueReader {
     IntWithSize(usize),
     UIntWithSize(usize),
     Grams,
-    Dict(Vec<CellFieldReader>),
-    // Cell(Vec<CellFieldReader>),
+    Bits(usize),
+    Address,
+    Cell,
+    Ref(Vec<CellFieldReader>),
+    Maybe(Vec<CellFieldReader>),
+    Either(Vec<CellFieldReader>, Vec<CellFieldReader>),
+    Dict {
+        key_size: usize,
+        value: Option<Vec<CellFieldReader>>,
+    },
 }
 
 
@@ -30,6 +39,9 @@ pub(crate) struct CellFieldReader {
     pub name: String,
 }
 
+const MAX_DATA_BITS: usize = 1023;
+const DEFAULT_DICT_KEY_SIZE: usize = 256;
+
 #[derive(Debug)]
 pub(crate) struct CellQuery {
     pub(crate) commands: Vec<CellFieldReader>
@@ -94,12 +106,12 @@ impl Parser {
     }
 
     fn tokenize_error(rest: &str) -> ClientError {
-        ClientError::cell_invalid_query(format!("invalid character (-> {})", rest))
+        Error::invalid_cell_query(format!("invalid character (-> {})", rest))
     }
 
     fn parse_error(&self, msg: &str) -> ClientError {
         // TODO: error message must point to error position related to self.tokens[self.pos]
-        ClientError::cell_invalid_query(format!("{}", msg))
+        Error::invalid_cell_query(format!("{}", msg))
     }
 
     fn tokenize(source: String) -> ClientResult<Self> {
@@ -110,6 +122,7 @@ impl Parser {
             next = chars.next();
             if let Some(token) = match current {
                 space if space <= ' ' => None,
+                '-' => Some(Token::Minus),
                 ':' => Some(Token::Colon),
                 '(' => Some(Token::Open),
                 ')' => Some(Token::Close),
@@ -160,19 +173,38 @@ impl Parser {
         Ok(commands)
     }
 
-    fn parse_optional_parenthesis_enclosed_commands(&mut self) -> ClientResult<Vec<CellFieldReader>> {
+    fn parse_optional_parenthesis_enclosed_commands(&mut self) -> ClientResult<Option<Vec<CellFieldReader>>> {
         if self.pass(Token::is_open) != None {
             let commands = self.parse_commands()?;
             if self.pass(Token::is_close) != None {
-                Ok(commands)
+                Ok(Some(commands))
             } else {
                 Err(self.parse_error(") expected"))
             }
         } else {
-            Ok(Vec::new())
+            Ok(None)
         }
     }
 
+    fn parse_parenthesis_enclosed_commands(&mut self, type_name: &str) -> ClientResult<Vec<CellFieldReader>> {
+        self.parse_optional_parenthesis_enclosed_commands()?
+            .ok_or_else(|| self.parse_error(&format!("( expected after [{}]", type_name)))
+    }
+
+    /// Splits type name into the name itself and the optional trailing size,
+    /// e.g. `u32` -> (`u`, 32), `dict` -> (`dict`, None).
+    fn split_type_size(&self, type_name: &str) -> ClientResult<(String, Option<usize>)> {
+        let pos = type_name.find(|c: char| Self::is_digit(c)).unwrap_or(type_name.len());
+        let (name, size) = type_name.split_at(pos);
+        let size = if size.is_empty() {
+            None
+        } else {
+            Some(size.parse::<usize>()
+                .map_err(|_| self.parse_error(&format!("invalid size in type [{}]", type_name)))?)
+        };
+        Ok((name.to_string(), size))
+    }
+
     fn parse_command(&mut self) -> ClientResult<Option<CellFieldReader>> {
         let skip = self.pass(Token::is_minus) != None;
         if let Some(identifier) = self.pass(Token::identifier) {
@@ -198,23 +230,29 @@ impl Parser {
     }
 
     fn parse_value_reader(&mut self, type_name: &str) -> ClientResult<CellValueReader> {
-        Ok(match type_name {
-            "u1" => CellValueReader::UIntWithSize(1),
-            "u8" => CellValueReader::UIntWithSize(8),
-            "u16" => CellValueReader::UIntWithSize(16),
-            "u32" => CellValueReader::UIntWithSize(32),
-            "u64" => CellValueReader::UIntWithSize(64),
-            "u128" => CellValueReader::UIntWithSize(128),
-            "u256" => CellValueReader::UIntWithSize(256),
-            "i1" => CellValueReader::IntWithSize(1),
-            "i8" => CellValueReader::IntWithSize(8),
-            "i16" => CellValueReader::IntWithSize(16),
-            "i32" => CellValueReader::IntWithSize(32),
-            "i64" => CellValueReader::IntWithSize(64),
-            "i128" => CellValueReader::IntWithSize(128),
-            "i256" => CellValueReader::IntWithSize(256),
-            "grams" => CellValueReader::Grams,
-            "dict" => CellValueReader::Dict(self.parse_optional_parenthesis_enclosed_commands()?),
+        let (name, size) = self.split_type_size(type_name)?;
+        Ok(match (name.as_str(), size) {
+            ("u", Some(size)) if (1..=256).contains(&size) => CellValueReader::UIntWithSize(size),
+            ("i", Some(size)) if (1..=256).contains(&size) => CellValueReader::IntWithSize(size),
+            ("bits", Some(size)) if size <= MAX_DATA_BITS => CellValueReader::Bits(size),
+            ("grams", None) | ("coins", None) => CellValueReader::Grams,
+            ("address", None) => CellValueReader::Address,
+            ("cell", None) => CellValueReader::Cell,
+            ("ref", None) => CellValueReader::Ref(self.parse_parenthesis_enclosed_commands(type_name)?),
+            ("maybe", None) => CellValueReader::Maybe(self.parse_parenthesis_enclosed_commands(type_name)?),
+            ("either", None) => {
+                let left = self.parse_parenthesis_enclosed_commands(type_name)?;
+                let right = self.parse_parenthesis_enclosed_commands(type_name)?;
+                CellValueReader::Either(left, right)
+            }
+            ("dict", key_size)
+                if (1..=MAX_DATA_BITS).contains(&key_size.unwrap_or(DEFAULT_DICT_KEY_SIZE)) =>
+            {
+                CellValueReader::Dict {
+                    key_size: key_size.unwrap_or(DEFAULT_DICT_KEY_SIZE),
+                    value: self.parse_optional_parenthesis_enclosed_commands()?,
+                }
+            }
             _ => return Err(self.parse_error(&format!("unknown type [{}]", type_name)))
         })
     }
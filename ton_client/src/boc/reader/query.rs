@@ -12,39 +12,98 @@
 */
 
 use serde_json::Value;
-use crate::error::{ClientResult, ClientError};
+use crate::boc::Error;
+use crate::boc::internal::{
+    bits_to_hex_string, format_dict_key, read_bigint, read_biguint, serialize_cell_to_base64,
+};
+use crate::error::ClientResult;
 use super::parser::{CellQuery, CellFieldReader, CellValueReader};
-use ton_types::{Cell, SliceData, HashmapE, HashmapType};
+use num_bigint::{BigInt, BigUint};
+use ton_block::{Deserializable, MsgAddress};
 use ton_block::types::Grams;
+use ton_types::{Cell, SliceData, HashmapE, HashmapType};
+
+fn read_uint(slice: &mut SliceData, size: usize) -> ClientResult<BigUint> {
+    read_biguint(slice, size).map_err(|err| Error::invalid_cell_query(err))
+}
+
+fn read_int(slice: &mut SliceData, size: usize) -> ClientResult<BigInt> {
+    read_bigint(slice, size).map_err(|err| Error::invalid_cell_query(err))
+}
+
+fn read_ref(slice: &mut SliceData) -> ClientResult<Cell> {
+    slice.checked_drain_reference()
+        .map_err(|err| Error::invalid_cell_query(err))
+}
+
+fn read_bit(slice: &mut SliceData) -> ClientResult<bool> {
+    slice.get_next_bit()
+        .map_err(|err| Error::invalid_cell_query(err))
+}
 
 fn read_value(slice: &mut SliceData, reader: &CellValueReader) -> ClientResult<Value> {
     Ok(match reader {
         CellValueReader::IntWithSize(size) => {
-            let n = slice.get_next_int(*size)
-                .map_err(|err| ClientError::cell_invalid_query(err))?;
-            Value::String(format!("{}", n))
+            Value::String(read_int(slice, *size)?.to_string())
         }
         CellValueReader::UIntWithSize(size) => {
-            let n = slice.get_next_int(*size)
-                .map_err(|err| ClientError::cell_invalid_query(err))?;
-            Value::String(format!("{}", n))
+            Value::String(read_uint(slice, *size)?.to_string())
         }
         CellValueReader::Grams => {
-            let n = Grams::read_from_cell(slice)
-                .map_err(|err| ClientError::cell_invalid_query(err))?;
+            let n = Grams::construct_from(slice)
+                .map_err(|err| Error::invalid_cell_query(err))?;
             Value::String(format!("{}", n))
         }
-        CellValueReader::Dict(_fields) => {
-            let mut dict = HashmapE::with_bit_len(256);
+        CellValueReader::Bits(size) => {
+            let bits = slice.get_next_bits(*size)
+                .map_err(|err| Error::invalid_cell_query(err))?;
+            Value::String(bits_to_hex_string(&bits, *size))
+        }
+        CellValueReader::Address => {
+            match MsgAddress::construct_from(slice).map_err(|err| Error::invalid_cell_query(err))? {
+                MsgAddress::AddrNone => Value::Null,
+                address => Value::String(address.to_string()),
+            }
+        }
+        CellValueReader::Cell => {
+            Value::String(serialize_cell_to_base64(&read_ref(slice)?, "cell")?)
+        }
+        CellValueReader::Ref(fields) => {
+            read(&mut SliceData::from(read_ref(slice)?), fields)?
+        }
+        CellValueReader::Maybe(fields) => {
+            if read_bit(slice)? {
+                read(slice, fields)?
+            } else {
+                Value::Null
+            }
+        }
+        CellValueReader::Either(left, right) => {
+            if read_bit(slice)? {
+                json!({ "right": read(slice, right)? })
+            } else {
+                json!({ "left": read(slice, left)? })
+            }
+        }
+        CellValueReader::Dict { key_size, value } => {
+            let mut dict = HashmapE::with_bit_len(*key_size);
             dict.read_hashmap_data(slice)
-                .map_err(|err| ClientError::cell_invalid_query(err))?;
-            let mut count = 0;
-            let result = dict.iterate(&mut |_key, _value| {
-                count += 1;
+                .map_err(|err| Error::invalid_cell_query(err))?;
+            let mut items = Vec::new();
+            dict.iterate_slices(|key, value| {
+                items.push((key, value));
                 Ok(true)
-            });
-            result.map_err(|err| ClientError::cell_invalid_query(err))?;
-            Value::from(count)
+            }).map_err(|err| Error::invalid_cell_query(err))?;
+            if let Some(fields) = value {
+                let mut entries = serde_json::Map::new();
+                for (mut key, mut value) in items {
+                    let key = read_uint(&mut key, *key_size)?;
+                    entries.insert(format_dict_key(&key, *key_size), read(&mut value, fields)?);
+                }
+                Value::Object(entries)
+            } else {
+                Value::from(items.len())
+            }
         }
     })
 }
@@ -52,8 +111,12 @@ fn read_value(slice: &mut SliceData, reader: &CellValueReader) -> ClientResult<V
 fn read(slice: &mut SliceData, commands: &Vec<CellFieldReader>) -> ClientResult<Value> {
     let mut values = serde_json::Map::new();
     for (index, command) in commands.iter().enumerate() {
+        let value = read_value(slice, &command.value)?;
+        if command.skip {
+            continue;
+        }
         let name = if command.name.is_empty() { format!("{}", index) } else { command.name.clone() };
-        values.insert(name, read_value(slice, &command.value)?);
+        values.insert(name, value);
     }
     Ok(Value::Object(values))
 }
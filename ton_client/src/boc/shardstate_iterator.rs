@@ -0,0 +1,278 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::boc::internal::{
+    deserialize_object_from_boc, deserialize_object_from_cell, serialize_cell_to_bytes,
+};
+use crate::boc::Error;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use ton_block::{
+    Account, DepthBalanceInfo, Deserializable, Serializable, ShardAccount, ShardStateUnsplit,
+};
+use ton_types::{fail, Cell, SliceData, UInt256};
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const ACCOUNT_ID_BITS: usize = 256;
+
+/// Subtree of the accounts dictionary that is not visited yet.
+struct PendingNode {
+    cell: Cell,
+    /// Count of the key bits left to read in the subtree.
+    key_bits: usize,
+}
+
+/// Reads `HmLabel` of the dictionary node and returns the label length.
+fn read_label(slice: &mut SliceData, max_len: usize) -> ton_types::Result<usize> {
+    // `#<= max_len` is stored in the minimal number of bits enough to store `max_len`
+    let len_bits = (0usize.leading_zeros() - max_len.leading_zeros()) as usize;
+    let len = if !slice.get_next_bit()? {
+        // hml_short$0 len:(Unary ~n) s:(n * Bit)
+        let mut len = 0;
+        while slice.get_next_bit()? {
+            len += 1;
+        }
+        slice.get_next_slice(len)?;
+        len
+    } else if !slice.get_next_bit()? {
+        // hml_long$10 n:(#<= m) s:(n * Bit)
+        let len = slice.get_next_int(len_bits)? as usize;
+        slice.get_next_slice(len)?;
+        len
+    } else {
+        // hml_same$11 v:Bit n:(#<= m)
+        slice.get_next_bit()?;
+        slice.get_next_int(len_bits)? as usize
+    };
+    if len > max_len {
+        fail!("dictionary label is longer than the key: {} > {}", len, max_len);
+    }
+    Ok(len)
+}
+
+/// Cursor over the shardstate accounts dictionary.
+///
+/// Dictionary is walked in the order of account ids keeping only the stack of
+/// not visited subtrees, so accounts are loaded and serialized page by page.
+pub(crate) struct ShardstateIterator {
+    pending: Vec<PendingNode>,
+    code_hash: Option<UInt256>,
+    min_balance: Option<u128>,
+    max_balance: Option<u128>,
+}
+
+impl ShardstateIterator {
+    fn next_account(&mut self) -> ton_types::Result<Option<ShardAccount>> {
+        while let Some(node) = self.pending.pop() {
+            let mut slice = SliceData::from(node.cell);
+            let key_bits = node.key_bits - read_label(&mut slice, node.key_bits)?;
+            if key_bits == 0 {
+                // ahmn_leaf#_ extra:Y value:X
+                DepthBalanceInfo::construct_from(&mut slice)?;
+                return Ok(Some(ShardAccount::construct_from(&mut slice)?));
+            }
+            // ahmn_fork#_ left:^(HashmapAug (n - 1) X Y) right:^(HashmapAug (n - 1) X Y) extra:Y
+            self.pending.push(PendingNode { cell: slice.reference(1)?, key_bits: key_bits - 1 });
+            self.pending.push(PendingNode { cell: slice.reference(0)?, key_bits: key_bits - 1 });
+        }
+        Ok(None)
+    }
+
+    fn matches(&self, account: &Account) -> bool {
+        if let Some(code_hash) = &self.code_hash {
+            if account.get_code().map(|code| code.repr_hash()).as_ref() != Some(code_hash) {
+                return false;
+            }
+        }
+        if self.min_balance.is_some() || self.max_balance.is_some() {
+            let balance = account
+                .balance()
+                .and_then(|balance| u128::from_str(&balance.grams.to_string()).ok())
+                .unwrap_or_default();
+            if self.min_balance.map(|min| balance < min).unwrap_or(false)
+                || self.max_balance.map(|max| balance > max).unwrap_or(false)
+            {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn next(&mut self, limit: usize) -> ClientResult<Vec<Value>> {
+        let mut items = Vec::new();
+        while items.len() < limit {
+            let shard_account = match self.next_account().map_err(|err| Error::invalid_boc(err))? {
+                Some(shard_account) => shard_account,
+                None => break,
+            };
+            let cell = shard_account.account_cell();
+            let account: Account = deserialize_object_from_cell(cell.clone(), "account")?;
+            if !self.matches(&account) {
+                continue;
+            }
+            let set = ton_block_json::AccountSerializationSet {
+                boc: serialize_cell_to_bytes(&cell, "account")?,
+                proof: None,
+                account,
+                ..Default::default()
+            };
+            let parsed = ton_block_json::db_serialize_account_ex(
+                "id",
+                &set,
+                ton_block_json::SerializationMode::QServer,
+            )
+            .map_err(|err| Error::serialization_error(err, "account"))?;
+            items.push(parsed.into());
+        }
+        Ok(items)
+    }
+}
+
+fn invalid_filter(name: &str, value: &str, err: impl std::fmt::Display) -> crate::error::ClientError {
+    crate::client::Error::invalid_params(
+        &json!({ name: value }).to_string(),
+        format!("invalid `{}` value `{}`: {}", name, value, err),
+    )
+}
+
+fn parse_balance(balance: &Option<String>, name: &str) -> ClientResult<Option<u128>> {
+    balance
+        .as_ref()
+        .map(|balance| u128::from_str(balance).map_err(|err| invalid_filter(name, balance, err)))
+        .transpose()
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfCreateShardstateIterator {
+    /// Shardstate BOC encoded as base64 or BOC handle
+    pub boc: String,
+    /// Return only accounts with the code hash (encoded with hex)
+    pub code_hash: Option<String>,
+    /// Return only accounts with balance greater or equal to the value (decimal nanotokens)
+    pub min_balance: Option<String>,
+    /// Return only accounts with balance less or equal to the value (decimal nanotokens)
+    pub max_balance: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct RegisteredShardstateIterator {
+    /// Iterator handle.
+    ///
+    /// Must be removed using `remove_shardstate_iterator`
+    /// when it is no more needed for the application.
+    pub handle: u32,
+}
+
+/// Creates iterator over accounts of the shardstate.
+///
+/// Accounts are returned by `shardstate_iterator_next` in the order of account ids.
+/// Each account is a JSON compatible with GraphQL API account object
+/// (the same as returned by `parse_account`). Filters are applied to each account,
+/// so the function doesn't parse the whole shardstate into JSON.
+#[api_function]
+pub async fn create_shardstate_iterator(
+    context: Arc<ClientContext>,
+    params: ParamsOfCreateShardstateIterator,
+) -> ClientResult<RegisteredShardstateIterator> {
+    let code_hash = params
+        .code_hash
+        .as_ref()
+        .map(|hash| UInt256::from_str(hash).map_err(|err| invalid_filter("code_hash", hash, err)))
+        .transpose()?;
+    let min_balance = parse_balance(&params.min_balance, "min_balance")?;
+    let max_balance = parse_balance(&params.max_balance, "max_balance")?;
+
+    let state = deserialize_object_from_boc::<ShardStateUnsplit>(&context, &params.boc, "shardstate")
+        .await?
+        .object;
+    // ahme_empty$0 extra:Y | ahme_root$1 root:^(HashmapAug n X Y) extra:Y
+    let mut accounts = state
+        .read_accounts()
+        .and_then(|accounts| accounts.serialize())
+        .map(SliceData::from)
+        .map_err(|err| Error::invalid_boc(err))?;
+    let mut pending = Vec::new();
+    if accounts.get_next_bit().map_err(|err| Error::invalid_boc(err))? {
+        pending.push(PendingNode {
+            cell: accounts.reference(0).map_err(|err| Error::invalid_boc(err))?,
+            key_bits: ACCOUNT_ID_BITS,
+        });
+    }
+
+    let handle = context.get_next_id();
+    context.shardstate_iterators.insert(
+        handle,
+        Arc::new(Mutex::new(ShardstateIterator {
+            pending,
+            code_hash,
+            min_balance,
+            max_balance,
+        })),
+    );
+    Ok(RegisteredShardstateIterator { handle })
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfShardstateIteratorNext {
+    /// Iterator handle
+    pub iterator: u32,
+    /// Maximum count of the returned accounts. Default is 50.
+    pub limit: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfShardstateIteratorNext {
+    /// Next accounts matching the filters.
+    pub items: Vec<Value>,
+    /// Indicates that there are more accounts to check.
+    ///
+    /// Note that the next page can be empty if no remaining accounts match the filters.
+    pub has_more: bool,
+}
+
+/// Returns the next page of the shardstate accounts.
+#[api_function]
+pub async fn shardstate_iterator_next(
+    context: Arc<ClientContext>,
+    params: ParamsOfShardstateIteratorNext,
+) -> ClientResult<ResultOfShardstateIteratorNext> {
+    let iterator = context
+        .shardstate_iterators
+        .get(&params.iterator)
+        .map(|entry| entry.1.clone())
+        .ok_or_else(|| crate::client::Error::invalid_handle(params.iterator, "shardstate iterator"))?;
+    let mut iterator = iterator.lock().await;
+    let limit = std::cmp::max(params.limit.unwrap_or(DEFAULT_PAGE_SIZE), 1);
+    let items = iterator.next(limit as usize)?;
+    Ok(ResultOfShardstateIteratorNext {
+        items,
+        has_more: !iterator.pending.is_empty(),
+    })
+}
+
+/// Removes shardstate iterator and frees the shardstate.
+#[api_function]
+pub async fn remove_shardstate_iterator(
+    context: Arc<ClientContext>,
+    params: RegisteredShardstateIterator,
+) -> ClientResult<()> {
+    context
+        .shardstate_iterators
+        .remove(&params.handle)
+        .ok_or_else(|| crate::client::Error::invalid_handle(params.handle, "shardstate iterator"))?;
+    Ok(())
+}
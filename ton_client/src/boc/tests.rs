@@ -24,7 +24,7 @@ use pretty_assertions::assert_eq;
 use serde_json::Value;
 use std::str::FromStr;
 use ton_block::{MsgAddrStd, MsgAddressInt, Serializable};
-use ton_types::{AccountId, BuilderData, IBitstring};
+use ton_types::{AccountId, BuilderData, HashmapE, HashmapType, IBitstring};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_encode_boc() {
@@ -150,6 +150,7 @@ async fn test_encode_boc() {
                 ]),
             ],
             boc_cache: None,
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -175,12 +176,171 @@ async fn test_encode_boc() {
                 },
             ],
             boc_cache: None,
+            boc_serialization: None,
         })
         .await
         .unwrap();
     assert_eq!(boc, response.boc);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_encode_boc_extended_ops() {
+    fn cell_u8(value: u8) -> ton_types::Cell {
+        let mut builder = BuilderData::new();
+        builder.append_u8(value).unwrap();
+        builder.into_cell().unwrap()
+    }
+    fn write_u8(value: u8) -> BuilderOp {
+        BuilderOp::Integer {
+            size: 8,
+            value: Value::from(value),
+        }
+    }
+
+    let client = TestClient::new();
+    let inner = cell_u8(7);
+    let inner_boc = serialize_cell_to_base64(&inner, "inner").unwrap();
+
+    let mut dict = HashmapE::with_bit_len(32);
+    let mut value1 = BuilderData::new();
+    value1.append_u64(10).unwrap();
+    let mut value2 = BuilderData::new();
+    value2.append_reference_cell(cell_u8(1));
+    for (key, value) in [(1u32, &value1), (2, &value2)].iter() {
+        let mut key_builder = BuilderData::new();
+        key_builder.append_u32(*key).unwrap();
+        dict.set_builder(key_builder.into_cell().unwrap().into(), value)
+            .unwrap();
+    }
+
+    let mut builder = BuilderData::new();
+    builder
+        .append_bits(4, 4)
+        .unwrap()
+        .append_u32(1_000_000_000)
+        .unwrap()
+        .append_bit_zero()
+        .unwrap()
+        .append_bit_one()
+        .unwrap()
+        .append_bit_one()
+        .unwrap();
+    builder.append_reference_cell(cell_u8(5));
+    builder.append_reference_cell(inner.clone());
+    dict.write_hashmap_data(&mut builder).unwrap();
+    let expected = serialize_cell_to_base64(&builder.into_cell().unwrap(), "cell").unwrap();
+
+    let result: ResultOfEncodeBoc = client
+        .request_async(
+            "boc.encode_boc",
+            ParamsOfEncodeBoc {
+                builder: vec![
+                    BuilderOp::Coins {
+                        value: Value::from("1000000000"),
+                    },
+                    BuilderOp::Maybe {
+                        builder: None,
+                        boc: None,
+                    },
+                    BuilderOp::Maybe {
+                        builder: Some(vec![write_u8(5)]),
+                        boc: None,
+                    },
+                    BuilderOp::Maybe {
+                        builder: None,
+                        boc: Some(inner_boc.clone()),
+                    },
+                    BuilderOp::Dict {
+                        key_size: 32,
+                        entries: vec![
+                            BuilderDictEntry {
+                                key: Value::from(1),
+                                value: vec![BuilderOp::Integer {
+                                    size: 64,
+                                    value: Value::from(10),
+                                }],
+                            },
+                            BuilderDictEntry {
+                                key: Value::from("0x2"),
+                                value: vec![BuilderOp::Cell {
+                                    builder: vec![write_u8(1)],
+                                }],
+                            },
+                        ],
+                    },
+                ],
+                boc_cache: None,
+                boc_serialization: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.boc, expected);
+
+    let state_init = ton_block::StateInit {
+        code: Some(inner.clone()),
+        data: Some(cell_u8(5)),
+        ..Default::default()
+    };
+    let expected = serialize_cell_to_base64(&state_init.serialize().unwrap(), "cell").unwrap();
+    let result: ResultOfEncodeBoc = client
+        .request_async(
+            "boc.encode_boc",
+            ParamsOfEncodeBoc {
+                builder: vec![BuilderOp::StateInit {
+                    code: Some(inner_boc.clone()),
+                    data: Some(serialize_cell_to_base64(&cell_u8(5), "data").unwrap()),
+                    library: None,
+                }],
+                boc_cache: None,
+                boc_serialization: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.boc, expected);
+
+    let error = client
+        .request_async::<_, ResultOfEncodeBoc>(
+            "boc.encode_boc",
+            ParamsOfEncodeBoc {
+                builder: vec![BuilderOp::Maybe {
+                    builder: Some(vec![]),
+                    boc: Some(inner_boc),
+                }],
+                boc_cache: None,
+                boc_serialization: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SerializationError as u32);
+
+    let dict_entry = |key: Value| BuilderDictEntry {
+        key,
+        value: vec![write_u8(1)],
+    };
+    let invalid_dicts = vec![
+        (0, vec![dict_entry(Value::from(0))]),
+        (1024, vec![dict_entry(Value::from(1))]),
+        (32, vec![dict_entry(Value::from(2)), dict_entry(Value::from("0x2"))]),
+    ];
+    for (key_size, entries) in invalid_dicts {
+        let error = client
+            .request_async::<_, ResultOfEncodeBoc>(
+                "boc.encode_boc",
+                ParamsOfEncodeBoc {
+                    builder: vec![BuilderOp::Dict { key_size, entries }],
+                    boc_cache: None,
+                    boc_serialization: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::SerializationError as u32);
+    }
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_pinned_cache() {
     let client = TestClient::new();
@@ -213,6 +373,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -251,6 +412,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -259,6 +421,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref2.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -285,6 +448,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -293,6 +457,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref4.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -318,6 +483,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -333,6 +499,7 @@ async fn test_pinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -368,6 +535,7 @@ async fn test_unpinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -386,6 +554,7 @@ async fn test_unpinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref1.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -394,12 +563,99 @@ async fn test_unpinned_cache() {
     let boc = cache_get
         .call(ParamsOfBocCacheGet {
             boc_ref: ref2.clone(),
+            boc_serialization: None,
         })
         .await
         .unwrap();
     assert_eq!(boc.boc, Some(boc2.clone()));
 }
 
+#[cfg(not(feature = "wasm-base"))]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_persistent_cache() {
+    let boc1 = TestClient::tvc(crate::tests::TEST_DEBOT, None);
+    let boc2 = TestClient::tvc(crate::tests::SUBSCRIBE, None);
+
+    let boc_max_size = std::cmp::max(
+        base64::decode(&boc1).unwrap().len(),
+        base64::decode(&boc2).unwrap().len(),
+    );
+    let path = std::env::temp_dir().join(format!("tonclient-{}", rand::random::<u32>()));
+    let config = json!({
+        "boc": {
+            "persistent_cache_max_size": boc_max_size / 1024 + 1
+        },
+        "local_storage_path": path.to_string_lossy(),
+    });
+
+    async fn cache_set(client: &TestClient, boc: &str) -> String {
+        let result: ResultOfBocCacheSet = client
+            .request_async(
+                "boc.cache_set",
+                ParamsOfBocCacheSet {
+                    boc: boc.to_string(),
+                    cache_type: BocCacheType::Unpinned,
+                },
+            )
+            .await
+            .unwrap();
+        result.boc_ref
+    }
+
+    async fn cache_get(client: &TestClient, boc_ref: &str) -> Option<String> {
+        let result: ResultOfBocCacheGet = client
+            .request_async(
+                "boc.cache_get",
+                ParamsOfBocCacheGet {
+                    boc_ref: boc_ref.to_string(),
+                    boc_serialization: None,
+                },
+            )
+            .await
+            .unwrap();
+        result.boc
+    }
+
+    let ref1 = cache_set(&TestClient::new_with_config(config.clone()), &boc1).await;
+
+    // BOC is restored from the persistent cache in the new context
+    let client = TestClient::new_with_config(config.clone());
+    assert_eq!(cache_get(&client, &ref1).await, Some(boc1.clone()));
+
+    // add second BOC to remove first BOC by insufficient persistent cache size
+    let ref2 = cache_set(&client, &boc2).await;
+    drop(client);
+
+    let client = TestClient::new_with_config(config);
+    assert_eq!(cache_get(&client, &ref1).await, None);
+    assert_eq!(cache_get(&client, &ref2).await, Some(boc2.clone()));
+
+    let _ignore_errors = std::fs::remove_dir_all(path);
+
+    // contexts sharing the storage path keep BOCs stored by each other
+    let path = std::env::temp_dir().join(format!("tonclient-{}", rand::random::<u32>()));
+    let config = json!({
+        "boc": {
+            "persistent_cache_max_size": 2 * (boc_max_size / 1024 + 1)
+        },
+        "local_storage_path": path.to_string_lossy(),
+    });
+    let client1 = TestClient::new_with_config(config.clone());
+    let client2 = TestClient::new_with_config(config.clone());
+    assert_eq!(cache_get(&client1, &ref1).await, None);
+    assert_eq!(cache_get(&client2, &ref2).await, None);
+    cache_set(&client1, &boc1).await;
+    cache_set(&client2, &boc2).await;
+    drop(client1);
+    drop(client2);
+
+    let client = TestClient::new_with_config(config);
+    assert_eq!(cache_get(&client, &ref1).await, Some(boc1));
+    assert_eq!(cache_get(&client, &ref2).await, Some(boc2));
+
+    let _ignore_errors = std::fs::remove_dir_all(path);
+}
+
 #[test]
 fn get_boc_hash() {
     let client = TestClient::new();
@@ -440,7 +696,8 @@ fn get_code_from_tvc() {
     let result: super::ResultOfGetCodeFromTvc = client.request(
         "boc.get_code_from_tvc",
         super::ParamsOfGetCodeFromTvc {
-            tvc: String::from("te6ccgECHAEABDkAAgE0BgEBAcACAgPPIAUDAQHeBAAD0CAAQdgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAIm/wD0pCAiwAGS9KDhiu1TWDD0oQkHAQr0pCD0oQgAAAIBIAwKAej/fyHTAAGOJoECANcYIPkBAXDtRND0BYBA9A7yitcL/wHtRyJvde1XAwH5EPKo3u1E0CDXScIBjhb0BNM/0wDtRwFvcQFvdgFvcwFvcu1Xjhj0Be1HAW9ycG9zcG92yIAgz0DJ0G9x7Vfi0z8B7UdvEyG5IAsAYJ8wIPgjgQPoqIIIG3dAoLneme1HIW9TIO1XMJSANPLw4jDTHwH4I7zyudMfAfFAAQIBIBgNAgEgEQ4BCbqLVfP4DwH67UdvYW6OO+1E0CDXScIBjhb0BNM/0wDtRwFvcQFvdgFvcwFvcu1Xjhj0Be1HAW9ycG9zcG92yIAgz0DJ0G9x7Vfi3u1HbxaS8jOX7Udxb1btV+IA+ADR+CO1H+1HIG8RMAHIyx/J0G9R7VftR28SyPQA7UdvE88LP+1HbxYQABzPCwDtR28RzxbJ7VRwagIBahUSAQm0ABrWwBMB/O1Hb2FujjvtRNAg10nCAY4W9ATTP9MA7UcBb3EBb3YBb3MBb3LtV44Y9AXtRwFvcnBvc3BvdsiAIM9AydBvce1X4t7tR29lIG6SMHDecO1HbxKAQPQO8orXC/+68uBk+AD6QNEgyMn7BIED6HCBAIDIcc8LASLPCgBxz0D4KBQAjs8WJM8WI/oCcc9AcPoCcPoCgEDPQPgjzwsfcs9AIMki+wBfBTDtR28SyPQA7UdvE88LP+1HbxbPCwDtR28RzxbJ7VRwatswAQm0ZfaLwBYB+O1Hb2FujjvtRNAg10nCAY4W9ATTP9MA7UcBb3EBb3YBb3MBb3LtV44Y9AXtRwFvcnBvc3BvdsiAIM9AydBvce1X4t7R7UdvEdcLH8iCEFDL7ReCEIAAAACxzwsfIc8LH8hzzwsB+CjPFnLPQPglzws/gCHPQCDPNSLPMbwXAHiWcc9AIc8XlXHPQSHN4iDJcfsAWyHA/44e7UdvEsj0AO1HbxPPCz/tR28WzwsA7UdvEc8Wye1U3nFq2zACASAbGQEJu3MS5FgaAPjtR29hbo477UTQINdJwgGOFvQE0z/TAO1HAW9xAW92AW9zAW9y7VeOGPQF7UcBb3Jwb3Nwb3bIgCDPQMnQb3HtV+Le+ADR+CO1H+1HIG8RMAHIyx/J0G9R7VftR28SyPQA7UdvE88LP+1HbxbPCwDtR28RzxbJ7VRwatswAMrdcCHXSSDBII4rIMAAjhwj0HPXIdcLACDAAZbbMF8H2zCW2zBfB9sw4wTZltswXwbbMOME2eAi0x80IHS7II4VMCCCEP////+6IJkwIIIQ/////rrf35bbMF8H2zDgIyHxQAFfBw==")
+            tvc: String::from("te6ccgECHAEABDkAAgE0BgEBAcACAgPPIAUDAQHeBAAD0CAAQdgAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAIm/wD0pCAiwAGS9KDhiu1TWDD0oQkHAQr0pCD0oQgAAAIBIAwKAej/fyHTAAGOJoECANcYIPkBAXDtRND0BYBA9A7yitcL/wHtRyJvde1XAwH5EPKo3u1E0CDXScIBjhb0BNM/0wDtRwFvcQFvdgFvcwFvcu1Xjhj0Be1HAW9ycG9zcG92yIAgz0DJ0G9x7Vfi0z8B7UdvEyG5IAsAYJ8wIPgjgQPoqIIIG3dAoLneme1HIW9TIO1XMJSANPLw4jDTHwH4I7zyudMfAfFAAQIBIBgNAgEgEQ4BCbqLVfP4DwH67UdvYW6OO+1E0CDXScIBjhb0BNM/0wDtRwFvcQFvdgFvcwFvcu1Xjhj0Be1HAW9ycG9zcG92yIAgz0DJ0G9x7Vfi3u1HbxaS8jOX7Udxb1btV+IA+ADR+CO1H+1HIG8RMAHIyx/J0G9R7VftR28SyPQA7UdvE88LP+1HbxYQABzPCwDtR28RzxbJ7VRwagIBahUSAQm0ABrWwBMB/O1Hb2FujjvtRNAg10nCAY4W9ATTP9MA7UcBb3EBb3YBb3MBb3LtV44Y9AXtRwFvcnBvc3BvdsiAIM9AydBvce1X4t7tR29lIG6SMHDecO1HbxKAQPQO8orXC/+68uBk+AD6QNEgyMn7BIED6HCBAIDIcc8LASLPCgBxz0D4KBQAjs8WJM8WI/oCcc9AcPoCcPoCgEDPQPgjzwsfcs9AIMki+wBfBTDtR28SyPQA7UdvE88LP+1HbxbPCwDtR28RzxbJ7VRwatswAQm0ZfaLwBYB+O1Hb2FujjvtRNAg10nCAY4W9ATTP9MA7UcBb3EBb3YBb3MBb3LtV44Y9AXtRwFvcnBvc3BvdsiAIM9AydBvce1X4t7R7UdvEdcLH8iCEFDL7ReCEIAAAACxzwsfIc8LH8hzzwsB+CjPFnLPQPglzws/gCHPQCDPNSLPMbwXAHiWcc9AIc8XlXHPQSHN4iDJcfsAWyHA/44e7UdvEsj0AO1HbxPPCz/tR28WzwsA7UdvEc8Wye1U3nFq2zACASAbGQEJu3MS5FgaAPjtR29hbo477UTQINdJwgGOFvQE0z/TAO1HAW9xAW92AW9zAW9y7VeOGPQF7UcBb3Jwb3Nwb3bIgCDPQMnQb3HtV+Le+ADR+CO1H+1HIG8RMAHIyx/J0G9R7VftR28SyPQA7UdvE88LP+1HbxbPCwDtR28RzxbJ7VRwatswAMrdcCHXSSDBII4rIMAAjhwj0HPXIdcLACDAAZbbMF8H2zCW2zBfB9sw4wTZltswXwbbMOME2eAi0x80IHS7II4VMCCCEP////+6IJkwIIIQ/////rrf35bbMF8H2zDgIyHxQAFfBw=="),
+            ..Default::default()
         }
     ).unwrap();
 
@@ -554,6 +811,116 @@ fn parse_shardstate() {
     assert_eq!(result.parsed["seq_no"], 0);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_shardstate_iterator() {
+    let client = TestClient::new();
+    let boc = base64::encode(&include_bytes!("test_data/zerostate.boc"));
+
+    async fn read_all(
+        client: &TestClient,
+        params: ParamsOfCreateShardstateIterator,
+    ) -> Vec<Value> {
+        let iterator: RegisteredShardstateIterator = client
+            .request_async("boc.create_shardstate_iterator", params)
+            .await
+            .unwrap();
+        let mut accounts = Vec::new();
+        loop {
+            let result: ResultOfShardstateIteratorNext = client
+                .request_async(
+                    "boc.shardstate_iterator_next",
+                    ParamsOfShardstateIteratorNext {
+                        iterator: iterator.handle,
+                        limit: Some(2),
+                    },
+                )
+                .await
+                .unwrap();
+            assert!(result.items.len() <= 2);
+            accounts.extend(result.items);
+            if !result.has_more {
+                break;
+            }
+        }
+        let _: () = client
+            .request_async("boc.remove_shardstate_iterator", iterator.clone())
+            .await
+            .unwrap();
+        let error = client
+            .request_async::<_, ResultOfShardstateIteratorNext>(
+                "boc.shardstate_iterator_next",
+                ParamsOfShardstateIteratorNext {
+                    iterator: iterator.handle,
+                    limit: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, crate::client::ErrorCode::InvalidHandle as u32);
+        accounts
+    }
+
+    let accounts = read_all(
+        &client,
+        ParamsOfCreateShardstateIterator {
+            boc: boc.clone(),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(accounts.len() > 2);
+    for account in &accounts {
+        assert!(account["id"].is_string());
+        assert!(account["boc"].is_string());
+    }
+    let ids: Vec<&str> = accounts.iter().map(|account| account["id"].as_str().unwrap()).collect();
+    let mut sorted_ids = ids.clone();
+    sorted_ids.sort();
+    sorted_ids.dedup();
+    assert_eq!(ids, sorted_ids);
+
+    let code_hash = accounts
+        .iter()
+        .find_map(|account| account["code_hash"].as_str())
+        .unwrap()
+        .to_string();
+    let filtered = read_all(
+        &client,
+        ParamsOfCreateShardstateIterator {
+            boc: boc.clone(),
+            code_hash: Some(code_hash.clone()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(!filtered.is_empty());
+    assert!(filtered.iter().all(|account| account["code_hash"] == code_hash));
+
+    let filtered = read_all(
+        &client,
+        ParamsOfCreateShardstateIterator {
+            boc,
+            min_balance: Some(u128::MAX.to_string()),
+            ..Default::default()
+        },
+    )
+    .await;
+    assert!(filtered.is_empty());
+
+    let error = client
+        .request_async::<_, RegisteredShardstateIterator>(
+            "boc.create_shardstate_iterator",
+            ParamsOfCreateShardstateIterator {
+                boc: String::new(),
+                max_balance: Some("-1".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::client::ErrorCode::InvalidParams as u32);
+}
+
 #[test]
 fn get_blockchain_config() {
     let client = TestClient::new();
@@ -587,6 +954,63 @@ fn get_blockchain_config() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_parse_and_update_blockchain_config() {
+    let client = TestClient::new();
+    let block_config = base64::encode(&include_bytes!("test_data/block_config.boc"));
+    let zerostate_config = base64::encode(&include_bytes!("test_data/zerostate_config.boc"));
+
+    async fn parse(client: &TestClient, config_boc: String) -> Value {
+        let result: ResultOfParseBlockchainConfig = client
+            .request_async(
+                "boc.parse_blockchain_config",
+                ParamsOfParseBlockchainConfig { config_boc },
+            )
+            .await
+            .unwrap();
+        result.config
+    }
+
+    let parsed_block_config = parse(&client, block_config.clone()).await;
+    let parsed_zerostate_config = parse(&client, zerostate_config.clone()).await;
+    assert!(parsed_block_config["p0"].is_string());
+    assert!(parsed_block_config["p20"].is_object());
+    assert!(parsed_block_config["p34"].is_object());
+
+    let result: ResultOfUpdateBlockchainConfig = client
+        .request_async(
+            "boc.update_blockchain_config",
+            ParamsOfUpdateBlockchainConfig {
+                config_boc: zerostate_config.clone(),
+                params: json!({
+                    "p20": parsed_block_config["p20"],
+                    "p21": parsed_block_config["p21"],
+                }),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+    let updated = parse(&client, result.config_boc).await;
+    assert_eq!(updated["p20"], parsed_block_config["p20"]);
+    assert_eq!(updated["p21"], parsed_block_config["p21"]);
+    assert_eq!(updated["p0"], parsed_zerostate_config["p0"]);
+    assert_eq!(updated["p34"], parsed_zerostate_config["p34"]);
+
+    let error = client
+        .request_async::<_, ResultOfUpdateBlockchainConfig>(
+            "boc.update_blockchain_config",
+            ParamsOfUpdateBlockchainConfig {
+                config_boc: zerostate_config,
+                params: json!({ "gas": {} }),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidBlockchainConfig as u32);
+}
+
 fn read_salted_boc(name: &str) -> String {
     base64::encode(&std::fs::read("src/boc/test_data/salt/".to_owned() + name).unwrap())
 }
@@ -627,6 +1051,7 @@ fn check_salt(
                 "boc.cache_get",
                 ParamsOfBocCacheGet {
                     boc_ref: result.code.clone(),
+                    boc_serialization: None,
                 },
             )
             .unwrap();
@@ -756,6 +1181,7 @@ fn check_encode_tvc(client: &TestClient, tvc: String, decoded: ResultOfDecodeTvc
                 tick: result.tick,
                 tock: result.tock,
                 boc_cache: None,
+                boc_serialization: None,
             },
         )
         .unwrap();
@@ -860,6 +1286,7 @@ fn encode_external_in_message() {
         }),
         signer: signing,
         processing_try_index: None,
+        boc_serialization: None,
     };
 
     let abi_encoded: ResultOfEncodeMessage = client
@@ -899,3 +1326,771 @@ fn encode_external_in_message() {
 
     assert_eq!(boc_encoded.message, abi_encoded.message);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_cell() {
+    let client = TestClient::new();
+    let owner = "-1:efd5a14409a8a129686114fc092525fddd508f1ea56d1b649a3a695d3a5b188c";
+    let owner_address = MsgAddressInt::from_str(owner)
+        .unwrap()
+        .write_to_new_cell()
+        .unwrap();
+
+    let mut dict = HashmapE::with_bit_len(32);
+    for (key, value) in [(1u32, 10u64), (2, 20)].iter() {
+        let mut key_builder = BuilderData::new();
+        key_builder.append_u32(*key).unwrap();
+        let mut value_builder = BuilderData::new();
+        value_builder.append_u64(*value).unwrap();
+        dict.set_builder(key_builder.into_cell().unwrap().into(), &value_builder)
+            .unwrap();
+    }
+
+    let mut inner = BuilderData::new();
+    inner.append_u64(1000).unwrap();
+    let inner = inner.into_cell().unwrap();
+
+    let mut builder = BuilderData::new();
+    builder
+        .append_u32(123)
+        .unwrap()
+        .append_i8(-2)
+        .unwrap()
+        .append_bits(0b00101101100, 11)
+        .unwrap()
+        .append_builder(&owner_address)
+        .unwrap()
+        .append_bits(4, 4)
+        .unwrap()
+        .append_u32(1_000_000_000)
+        .unwrap()
+        .append_bit_one()
+        .unwrap()
+        .append_u16(7)
+        .unwrap()
+        .append_bit_zero()
+        .unwrap()
+        .append_bit_one()
+        .unwrap()
+        .append_bits(5, 4)
+        .unwrap();
+    dict.write_hashmap_data(&mut builder).unwrap();
+    builder.append_reference_cell(inner.clone());
+    let boc = serialize_cell_to_base64(&builder.into_cell().unwrap(), "cell").unwrap();
+
+    let result: ResultOfQueryCell = client
+        .request_async(
+            "boc.query_cell",
+            ParamsOfQueryCell {
+                boc: boc.clone(),
+                query: "seqno:u32 delta:i8 tag:bits11 owner:address balance:grams \
+                    opt:maybe(v:u16) none:maybe(v:u16) e:either(a:u8)(b:u4) \
+                    items:dict32(v:u64) r:ref(n:u64)"
+                    .to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        result.value,
+        json!({
+            "seqno": "123",
+            "delta": "-2",
+            "tag": "2D9_",
+            "owner": owner,
+            "balance": "1000000000",
+            "opt": { "v": "7" },
+            "none": null,
+            "e": { "right": { "b": "5" } },
+            "items": {
+                "1": { "v": "10" },
+                "2": { "v": "20" },
+            },
+            "r": { "n": "1000" },
+        })
+    );
+
+    let result: ResultOfQueryCell = client
+        .request_async(
+            "boc.query_cell",
+            ParamsOfQueryCell {
+                boc: boc.clone(),
+                query: "-u32 -i8 -bits11 -address -coins -maybe(u16) -maybe(u16) \
+                    -either(u8)(u4) dict32 cell"
+                    .to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.value["8"], json!(2));
+    assert_eq!(
+        result.value["9"],
+        json!(serialize_cell_to_base64(&inner, "cell").unwrap())
+    );
+
+    let error = client
+        .request_async::<_, ResultOfQueryCell>(
+            "boc.query_cell",
+            ParamsOfQueryCell {
+                boc,
+                query: "seqno:u33x".to_string(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidCellQuery as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_tlb() {
+    let client = TestClient::new();
+    let schema = r#"
+        // Storage of a test contract
+        item$_ value:uint32 = Item;
+        storage#1a seqno:uint32 active:Bool len:(## 4) data:(bits len)
+            balance:Grams owner:MsgAddress extra:(Maybe ^[ a:int8 ])
+            items:(HashmapE 32 Item) choice:(Either uint8 ^Item) code:^Cell = Storage;
+    "#;
+
+    let mut code = BuilderData::new();
+    code.append_u32(0xFF00F4A4).unwrap();
+    let code = serialize_cell_to_base64(&code.into_cell().unwrap(), "code").unwrap();
+
+    let value = json!({
+        "@type": "storage",
+        "seqno": "7",
+        "active": true,
+        "len": "12",
+        "data": "123",
+        "balance": "1000000000",
+        "owner": "0:1111111111111111111111111111111111111111111111111111111111111111",
+        "extra": { "a": "-5" },
+        "items": {
+            "1": { "@type": "item", "value": "10" },
+            "2": { "@type": "item", "value": "20" },
+        },
+        "choice": { "right": { "@type": "item", "value": "3" } },
+        "code": code,
+    });
+
+    let encoded: ResultOfEncodeTlb = client
+        .request_async(
+            "boc.encode_tlb",
+            ParamsOfEncodeTlb {
+                value: value.clone(),
+                schema: schema.to_string(),
+                root_type: "Storage".to_string(),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+
+    let queried: ResultOfQueryCell = client
+        .request_async(
+            "boc.query_cell",
+            ParamsOfQueryCell {
+                boc: encoded.boc.clone(),
+                query: "tag:u8 seqno:u32".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(queried.value, json!({ "tag": "26", "seqno": "7" }));
+
+    let parsed: ResultOfParseTlb = client
+        .request_async(
+            "boc.parse_tlb",
+            ParamsOfParseTlb {
+                boc: encoded.boc.clone(),
+                schema: schema.to_string(),
+                root_type: "Storage".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(parsed.parsed, value);
+
+    let error = client
+        .request_async::<_, ResultOfParseTlb>(
+            "boc.parse_tlb",
+            ParamsOfParseTlb {
+                boc: encoded.boc,
+                schema: "item$_ value:uint32 = Item;".to_string(),
+                root_type: "Item".to_string(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::TlbDataMismatch as u32);
+
+    let error = client
+        .request_async::<_, ResultOfParseTlb>(
+            "boc.parse_tlb",
+            ParamsOfParseTlb {
+                boc: code.clone(),
+                schema: "a#1 b:Unknown = A;".to_string(),
+                root_type: "A".to_string(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidTlbSchema as u32);
+
+    // Recursive type consuming no data
+    let error = client
+        .request_async::<_, ResultOfParseTlb>(
+            "boc.parse_tlb",
+            ParamsOfParseTlb {
+                boc: code,
+                schema: "a$_ x:A = A;".to_string(),
+                root_type: "A".to_string(),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidTlbSchema as u32);
+
+    // Values out of the range of the field type
+    let schema = "a$_ u:uint8 i:int8 d:(HashmapE 8 uint8) = A;";
+    let out_of_range = [
+        ("300", "0", "1"),
+        ("-1", "0", "1"),
+        ("0", "128", "1"),
+        ("0", "-129", "1"),
+        ("0", "0", "256"),
+    ];
+    for (u, i, key) in out_of_range.iter() {
+        let error = client
+            .request_async::<_, ResultOfEncodeTlb>(
+                "boc.encode_tlb",
+                ParamsOfEncodeTlb {
+                    value: json!({ "u": u, "i": i, "d": { key.to_string(): "1" } }),
+                    schema: schema.to_string(),
+                    root_type: "A".to_string(),
+                    boc_cache: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::TlbDataMismatch as u32);
+    }
+    let encoded: ResultOfEncodeTlb = client
+        .request_async(
+            "boc.encode_tlb",
+            ParamsOfEncodeTlb {
+                value: json!({ "u": "255", "i": "-128", "d": { "255": "127" } }),
+                schema: schema.to_string(),
+                root_type: "A".to_string(),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+    let parsed: ResultOfParseTlb = client
+        .request_async(
+            "boc.parse_tlb",
+            ParamsOfParseTlb {
+                boc: encoded.boc,
+                schema: schema.to_string(),
+                root_type: "A".to_string(),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        parsed.parsed,
+        json!({ "@type": "a", "u": "255", "i": "-128", "d": { "255": "127" } })
+    );
+
+    // Linked list deeper than the nesting limit of the types within a cell.
+    // Encoded directly, the JSON parser of the client limits the nesting of values
+    let schema = tlb::schema::Schema::parse("node$_ v:uint8 next:(Maybe ^Node) = Node;").unwrap();
+    let node_type = tlb::schema::TypeExpr::Named("Node".to_string());
+    let mut list = json!({ "@type": "node", "v": "0", "next": null });
+    for i in 1..260 {
+        list = json!({ "@type": "node", "v": (i % 256).to_string(), "next": list });
+    }
+    let cell = tlb::codec::Encoder::new(&schema).encode_cell(&node_type, &list).unwrap();
+    let parsed = tlb::codec::Decoder::new(&schema).decode_cell(&node_type, cell).unwrap();
+    assert_eq!(parsed, list);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_diff() {
+    fn cell_u32(value: u32) -> ton_types::Cell {
+        let mut builder = BuilderData::new();
+        builder.append_u32(value).unwrap();
+        builder.into_cell().unwrap()
+    }
+    fn root(data: u16, refs: &[&ton_types::Cell]) -> String {
+        let mut builder = BuilderData::new();
+        builder.append_u16(data).unwrap();
+        for cell in refs {
+            builder.append_reference_cell((*cell).clone());
+        }
+        serialize_cell_to_base64(&builder.into_cell().unwrap(), "root").unwrap()
+    }
+
+    let client = TestClient::new();
+    let (a, b, c, a2) = (cell_u32(1), cell_u32(2), cell_u32(3), cell_u32(9));
+    let boc1 = root(0x1234, &[&a, &b]);
+    let boc2 = root(0x1235, &[&a2, &a, &c]);
+
+    let result: ResultOfDiff = client
+        .request_async(
+            "boc.diff",
+            ParamsOfDiff {
+                boc1: boc1.clone(),
+                boc2: boc1.clone(),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(result.equal);
+    assert!(result.differences.is_empty());
+    assert!(result.shared_subtrees.is_empty());
+
+    let result: ResultOfDiff = client
+        .request_async("boc.diff", ParamsOfDiff { boc1, boc2 })
+        .await
+        .unwrap();
+    assert!(!result.equal);
+    assert_eq!(
+        result.differences,
+        vec![
+            CellDiff::DataChanged {
+                path: vec![],
+                data1: "1234".into(),
+                data2: "1235".into(),
+                bit_length1: 16,
+                bit_length2: 16,
+                first_different_bit: 15,
+            },
+            CellDiff::DataChanged {
+                path: vec![0],
+                data1: "00000001".into(),
+                data2: "00000009".into(),
+                bit_length1: 32,
+                bit_length2: 32,
+                first_different_bit: 28,
+            },
+            CellDiff::ReferenceReplaced {
+                path: vec![1],
+                hash1: b.repr_hash().as_hex_string(),
+                hash2: a.repr_hash().as_hex_string(),
+            },
+            CellDiff::ReferenceAdded {
+                path: vec![2],
+                hash: c.repr_hash().as_hex_string(),
+            },
+        ]
+    );
+    assert_eq!(
+        result.shared_subtrees,
+        vec![SharedSubtree {
+            hash: a.repr_hash().as_hex_string(),
+            path1: vec![0],
+            path2: vec![1],
+        }]
+    );
+
+    // Cells shared by many parents are compared once
+    fn chain(leaf: u32) -> String {
+        let mut cell = cell_u32(leaf);
+        for _ in 0..64 {
+            let mut builder = BuilderData::new();
+            builder.append_reference_cell(cell.clone());
+            builder.append_reference_cell(cell);
+            cell = builder.into_cell().unwrap();
+        }
+        serialize_cell_to_base64(&cell, "chain").unwrap()
+    }
+    let result: ResultOfDiff = client
+        .request_async(
+            "boc.diff",
+            ParamsOfDiff {
+                boc1: chain(1),
+                boc2: chain(2),
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!result.equal);
+    assert_eq!(result.differences.len(), 1);
+
+    // Exotic and ordinary cells with the same data
+    let mut data = BuilderData::with_raw(vec![ton_types::CellType::LibraryReference.into()], 8)
+        .unwrap();
+    data.append_raw(&[0x11; 32], 256).unwrap();
+    let ordinary = serialize_cell_to_base64(&data.clone().into_cell().unwrap(), "").unwrap();
+    data.set_type(ton_types::CellType::LibraryReference);
+    let library = serialize_cell_to_base64(&data.into_cell().unwrap(), "").unwrap();
+    let result: ResultOfDiff = client
+        .request_async(
+            "boc.diff",
+            ParamsOfDiff {
+                boc1: ordinary,
+                boc2: library,
+            },
+        )
+        .await
+        .unwrap();
+    assert!(!result.equal);
+    assert_eq!(
+        result.differences,
+        vec![CellDiff::CellTypeChanged {
+            path: vec![],
+            cell_type1: "Ordinary".into(),
+            cell_type2: "LibraryReference".into(),
+        }]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_cell_text() {
+    let client = TestClient::new();
+
+    let mut inner = BuilderData::new();
+    inner.append_u16(0xFFFF).unwrap();
+    inner.append_reference_cell(BuilderData::new().into_cell().unwrap());
+    let mut bit = BuilderData::new();
+    bit.append_bit_one().unwrap();
+    let mut root = BuilderData::new();
+    root.append_u32(123).unwrap();
+    root.append_reference_cell(bit.into_cell().unwrap());
+    root.append_reference_cell(inner.into_cell().unwrap());
+    let root = root.into_cell().unwrap();
+    let boc = serialize_cell_to_base64(&root, "root").unwrap();
+
+    let text = "x{0000007B}\n x{C_}\n x{FFFF}\n  x{}\n";
+    let result: ResultOfCellToText = client
+        .request_async("boc.cell_to_text", ParamsOfCellToText { boc: boc.clone() })
+        .await
+        .unwrap();
+    assert_eq!(result.text, text);
+
+    let result: ResultOfCellFromText = client
+        .request_async(
+            "boc.cell_from_text",
+            ParamsOfCellFromText {
+                text: text.to_string(),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.boc, boc);
+
+    let result: ResultOfCellFromText = client
+        .request_async(
+            "boc.cell_from_text",
+            ParamsOfCellFromText {
+                text: "x{4_}\n x{0004_}\n".to_string(),
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+    let result: ResultOfCellToText = client
+        .request_async("boc.cell_to_text", ParamsOfCellToText { boc: result.boc })
+        .await
+        .unwrap();
+    assert_eq!(result.text, "x{4_}\n x{0004_}\n");
+
+    for text in &["x{12}\n   x{34}\n", "x{12}\nx{34}\n", "x{1G}\n", "12\n", ""] {
+        let error = client
+            .request_async::<_, ResultOfCellFromText>(
+                "boc.cell_from_text",
+                ParamsOfCellFromText {
+                    text: text.to_string(),
+                    boc_cache: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidCellText as u32);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_merkle_proof() {
+    let client = TestClient::new();
+
+    let mut leaf = BuilderData::new();
+    leaf.append_u8(7).unwrap();
+    let mut a = BuilderData::new();
+    a.append_u32(1).unwrap();
+    a.append_reference_cell(leaf.into_cell().unwrap());
+    let mut b = BuilderData::new();
+    b.append_u32(2).unwrap();
+    let b = b.into_cell().unwrap();
+    let mut root = BuilderData::new();
+    root.append_u16(0xABCD).unwrap();
+    root.append_reference_cell(a.into_cell().unwrap());
+    root.append_reference_cell(b.clone());
+    let root = root.into_cell().unwrap();
+    let root_hash = root.repr_hash().as_hex_string();
+    let boc = serialize_cell_to_base64(&root, "root").unwrap();
+
+    for (paths, hashes, pruned) in vec![
+        (Some(vec![vec![0]]), None, 1),
+        (None, Some(vec![b.repr_hash().as_hex_string()]), 0),
+    ] {
+        let proof: ResultOfCreateMerkleProof = client
+            .request_async(
+                "boc.create_merkle_proof",
+                ParamsOfCreateMerkleProof {
+                    boc: boc.clone(),
+                    paths,
+                    hashes,
+                    boc_cache: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let proven: ResultOfVerifyMerkleProof = client
+            .request_async(
+                "boc.verify_merkle_proof",
+                ParamsOfVerifyMerkleProof {
+                    proof: proof.proof.clone(),
+                    root_hash: root_hash.clone(),
+                    boc_cache: None,
+                },
+            )
+            .await
+            .unwrap();
+        let (_, content) = internal::deserialize_cell_from_base64(&proven.boc, "proof").unwrap();
+        assert_eq!(
+            content.reference(pruned).unwrap().cell_type(),
+            ton_types::CellType::PrunedBranch
+        );
+
+        let error = client
+            .request_async::<_, ResultOfVerifyMerkleProof>(
+                "boc.verify_merkle_proof",
+                ParamsOfVerifyMerkleProof {
+                    proof: proof.proof.clone(),
+                    root_hash: b.repr_hash().as_hex_string(),
+                    boc_cache: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidMerkleProof as u32);
+
+        // virtualized cell is cached with the hash of the original cell
+        let proven: ResultOfVerifyMerkleProof = client
+            .request_async(
+                "boc.verify_merkle_proof",
+                ParamsOfVerifyMerkleProof {
+                    proof: proof.proof,
+                    root_hash: root_hash.clone(),
+                    boc_cache: Some(BocCacheType::Pinned { pin: "proof".to_string() }),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(proven.boc, format!("*{}", root_hash));
+        let queried: ResultOfQueryCell = client
+            .request_async(
+                "boc.query_cell",
+                ParamsOfQueryCell {
+                    boc: proven.boc,
+                    query: "value:u16".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(queried.value, json!({ "value": "43981" }));
+        let _: () = client
+            .request_async(
+                "boc.cache_unpin",
+                ParamsOfBocCacheUnpin {
+                    pin: "proof".to_string(),
+                    boc_ref: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    let error = client
+        .request_async::<_, ResultOfCreateMerkleProof>(
+            "boc.create_merkle_proof",
+            ParamsOfCreateMerkleProof {
+                boc,
+                paths: Some(vec![vec![1, 0]]),
+                hashes: None,
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::CellNotFound as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_libraries() {
+    let client = TestClient::new();
+
+    let tvc = TestClient::tvc("t24_initdata", Some(2));
+    let code = client
+        .request_async::<_, ResultOfDecodeTvc>(
+            "boc.decode_tvc",
+            ParamsOfDecodeTvc {
+                tvc,
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap()
+        .code
+        .unwrap();
+    let code_cell =
+        ton_types::deserialize_tree_of_cells(&mut std::io::Cursor::new(base64::decode(&code).unwrap()))
+            .unwrap();
+
+    let mut library_ref = BuilderData::with_raw(vec![ton_types::CellType::LibraryReference.into()], 8)
+        .unwrap();
+    library_ref
+        .append_raw(code_cell.repr_hash().as_slice(), 256)
+        .unwrap();
+    library_ref.set_type(ton_types::CellType::LibraryReference);
+    let library_ref = serialize_cell_to_base64(&library_ref.into_cell().unwrap(), "").unwrap();
+
+    let get_version = || async {
+        client
+            .request_async::<_, ResultOfGetCompilerVersion>(
+                "boc.get_compiler_version",
+                ParamsOfGetCompilerVersion { code: library_ref.clone() },
+            )
+            .await
+    };
+
+    let error = get_version().await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::LibraryNotFound as u32);
+    assert_eq!(error.data["library_hash"], code_cell.repr_hash().as_hex_string());
+
+    let result: ResultOfAddLibraries = client
+        .request_async(
+            "boc.add_libraries",
+            ParamsOfAddLibraries {
+                libraries: Some(vec![code]),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.hashes, vec![code_cell.repr_hash().as_hex_string()]);
+
+    let result = get_version().await.unwrap();
+    assert_eq!(result.version.as_deref(), Some("sol 0.51.0"));
+
+    let _: () = client
+        .request_async(
+            "boc.remove_libraries",
+            ParamsOfRemoveLibraries { hashes: None },
+        )
+        .await
+        .unwrap();
+    let error = get_version().await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::LibraryNotFound as u32);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_boc_serialization_options() {
+    let client = TestClient::new();
+    let encode_boc = client.wrap_async(
+        encode_boc,
+        BocModule::api(),
+        super::encode::encode_boc_api(),
+    );
+    let builder = vec![
+        BuilderOp::Integer { size: 32, value: Value::from(123) },
+        BuilderOp::Cell {
+            builder: vec![BuilderOp::BitString { value: "FFFF".to_string() }],
+        },
+    ];
+    let encode = |boc_serialization: Option<BocSerializationOptions>| {
+        encode_boc.call(ParamsOfEncodeBoc {
+            builder: builder.clone(),
+            boc_cache: None,
+            boc_serialization,
+        })
+    };
+    let boc_hash = |boc: String| {
+        client.request::<_, ResultOfGetBocHash>("boc.get_boc_hash", ParamsOfGetBocHash { boc })
+            .unwrap()
+            .hash
+    };
+
+    let plain = encode(None).await.unwrap().boc;
+    assert_eq!(encode(Some(Default::default())).await.unwrap().boc, plain);
+    let hash = boc_hash(plain.clone());
+
+    let boc = encode(Some(BocSerializationOptions {
+        crc: Some(true),
+        index: Some(true),
+        ..Default::default()
+    }))
+    .await
+    .unwrap()
+    .boc;
+    let bytes = base64::decode(&boc).unwrap();
+    assert_eq!(bytes[4] & 0xE0, 0xC0);
+    let crc = crc::Crc::<u32>::new(&crc::CRC_32_ISCSI).checksum(&bytes[..bytes.len() - 4]);
+    assert_eq!(bytes[bytes.len() - 4..].to_vec(), crc.to_le_bytes().to_vec());
+    assert_eq!(boc_hash(boc), hash);
+
+    let boc = encode(Some(BocSerializationOptions {
+        cache_bits: Some(true),
+        offset_size: Some(4),
+        ..Default::default()
+    }))
+    .await
+    .unwrap()
+    .boc;
+    let bytes = base64::decode(&boc).unwrap();
+    assert_eq!(bytes[4] & 0xE0, 0xA0);
+    assert_eq!(bytes[5], 4);
+    assert_eq!(boc_hash(boc), hash);
+
+    let error = encode(Some(BocSerializationOptions {
+        offset_size: Some(9),
+        ..Default::default()
+    }))
+    .await
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SerializationError as u32);
+
+    let boc_ref = client
+        .request_async::<_, ResultOfBocCacheSet>(
+            "boc.cache_set",
+            ParamsOfBocCacheSet {
+                boc: plain,
+                cache_type: BocCacheType::Unpinned,
+            },
+        )
+        .await
+        .unwrap()
+        .boc_ref;
+    let boc = client
+        .request_async::<_, ResultOfBocCacheGet>(
+            "boc.cache_get",
+            ParamsOfBocCacheGet {
+                boc_ref,
+                boc_serialization: Some(BocSerializationOptions {
+                    crc: Some(true),
+                    ..Default::default()
+                }),
+            },
+        )
+        .await
+        .unwrap()
+        .boc
+        .unwrap();
+    assert_eq!(base64::decode(&boc).unwrap()[4] & 0xE0, 0x40);
+    assert_eq!(boc_hash(boc), hash);
+}
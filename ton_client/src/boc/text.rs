@@ -0,0 +1,168 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::boc::internal::{
+    bits_to_hex_string, deserialize_cell_from_boc, hex_string_to_bits, serialize_cell_to_boc,
+};
+use crate::boc::{BocCacheType, Error};
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use ton_types::{BuilderData, Cell};
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfCellToText {
+    /// BOC encoded as base64 or BOC handle
+    pub boc: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfCellToText {
+    /// Cell tree in Fift notation
+    pub text: String,
+}
+
+/// Prints cell tree in Fift notation.
+///
+/// Each cell is printed on a separate line as `x{...}` with its data in
+/// the bitstring notation. Referenced cells follow their parent cell
+/// and are indented by one more space.
+///
+/// Example:
+/// ```text
+/// x{0000007B}
+///  x{C_}
+///  x{FFFF}
+///   x{}
+/// ```
+#[api_function]
+pub async fn cell_to_text(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfCellToText,
+) -> ClientResult<ResultOfCellToText> {
+    let (_, cell) = deserialize_cell_from_boc(&context, &params.boc, "").await?;
+    let mut text = String::new();
+    write_cell(&mut text, &cell, 0)?;
+    Ok(ResultOfCellToText { text })
+}
+
+fn write_cell(text: &mut String, cell: &Cell, indent: usize) -> ClientResult<()> {
+    text.push_str(&" ".repeat(indent));
+    text.push_str("x{");
+    text.push_str(&bits_to_hex_string(cell.data(), cell.bit_length()));
+    text.push_str("}\n");
+    for index in 0..cell.references_count() {
+        let child = cell.reference(index).map_err(|err| Error::invalid_boc(err))?;
+        write_cell(text, &child, indent + 1)?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfCellFromText {
+    /// Cell tree in Fift notation
+    pub text: String,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfCellFromText {
+    /// Encoded BOC or BOC cache key.
+    pub boc: String,
+}
+
+/// Parses cell tree in Fift notation into BOC.
+///
+/// Accepts the text produced by `cell_to_text`. Each line contains one cell,
+/// the nesting level is determined by the count of leading spaces.
+/// Empty lines are ignored. All cells are created as ordinary cells.
+#[api_function]
+pub async fn cell_from_text(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfCellFromText,
+) -> ClientResult<ResultOfCellFromText> {
+    let cell = parse_text(&params.text)?;
+    Ok(ResultOfCellFromText {
+        boc: serialize_cell_to_boc(&context, cell, "cell", params.boc_cache).await?,
+    })
+}
+
+fn parse_text(text: &str) -> ClientResult<Cell> {
+    // Builders of the cells on the path from the root to the last parsed cell
+    let mut stack: Vec<BuilderData> = Vec::new();
+    let mut root = None;
+    let mut base_indent = None;
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let content = line.trim_start_matches(' ');
+        if content.trim().is_empty() {
+            continue;
+        }
+        let indent = line.len() - content.len();
+        let base_indent = *base_indent.get_or_insert(indent);
+        let depth = indent
+            .checked_sub(base_indent)
+            .ok_or_else(|| Error::invalid_cell_text("unexpected indentation", line_number))?;
+        if depth > stack.len() {
+            return Err(Error::invalid_cell_text("unexpected indentation", line_number));
+        }
+        if depth == 0 && (root.is_some() || !stack.is_empty()) {
+            return Err(Error::invalid_cell_text("multiple root cells", line_number));
+        }
+        while stack.len() > depth {
+            close_cell(&mut stack, &mut root, line_number)?;
+        }
+        stack.push(parse_cell(content.trim_end(), line_number)?);
+    }
+    while !stack.is_empty() {
+        close_cell(&mut stack, &mut root, text.lines().count())?;
+    }
+    root.ok_or_else(|| Error::invalid_cell_text("no cells found", 1))
+}
+
+fn close_cell(
+    stack: &mut Vec<BuilderData>,
+    root: &mut Option<Cell>,
+    line_number: usize,
+) -> ClientResult<()> {
+    let cell = stack
+        .pop()
+        .unwrap()
+        .into_cell()
+        .map_err(|err| Error::invalid_cell_text(err, line_number))?;
+    match stack.last_mut() {
+        Some(parent) => {
+            parent
+                .checked_append_reference(cell)
+                .map_err(|err| Error::invalid_cell_text(err, line_number))?;
+        }
+        None => *root = Some(cell),
+    }
+    Ok(())
+}
+
+fn parse_cell(content: &str, line_number: usize) -> ClientResult<BuilderData> {
+    let bits = content
+        .strip_prefix("x{")
+        .or_else(|| content.strip_prefix("X{"))
+        .and_then(|content| content.strip_suffix('}'))
+        .ok_or_else(|| Error::invalid_cell_text("cell must be written as `x{...}`", line_number))?;
+    let (data, bit_length) = hex_string_to_bits(bits).ok_or_else(|| {
+        Error::invalid_cell_text(format!("invalid bitstring `{}`", bits), line_number)
+    })?;
+    let mut builder = BuilderData::new();
+    builder
+        .append_raw(&data, bit_length)
+        .map_err(|err| Error::invalid_cell_text(err, line_number))?;
+    Ok(builder)
+}
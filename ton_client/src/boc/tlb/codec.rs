@@ -0,0 +1,517 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::Zero;
+use serde_json::{Map, Value};
+use ton_block::{Deserializable, MsgAddress, Serializable};
+use ton_types::{BuilderData, Cell, HashmapE, HashmapType, IBitstring, SliceData};
+
+use super::schema::{Constructor, Field, Schema, Size, TypeExpr};
+use crate::boc::encode::{append_bitstring, append_number};
+use crate::boc::internal::{
+    bits_to_hex_string, deserialize_cell_from_base64, format_dict_key, parse_dict_key,
+    read_bigint, read_biguint, serialize_cell_to_base64,
+};
+use crate::boc::Error;
+use crate::encoding::account_decode;
+use crate::error::{ClientError, ClientResult};
+
+/// JSON field holding the constructor name of the decoded value.
+pub(crate) const CONSTRUCTOR_FIELD: &str = "@type";
+
+/// Maximum nesting depth of the schema types within a cell. Recursive types that consume
+/// no data (e.g. `a$_ x:A = A;`) would recurse infinitely without the limit.
+pub(crate) const MAX_NESTING_DEPTH: usize = 256;
+
+/// Maximum depth of the referenced cells.
+pub(crate) const MAX_CELL_DEPTH: usize = 1024;
+
+fn mismatch(message: impl std::fmt::Display) -> ClientError {
+    Error::tlb_data_mismatch(message)
+}
+
+/// Values of the numeric fields already processed in the current constructor.
+/// Used to resolve sizes like `(bits len)`.
+type Numbers = HashMap<String, usize>;
+
+fn resolve_size(size: &Size, numbers: &Numbers) -> ClientResult<usize> {
+    match size {
+        Size::Const(size) => Ok(*size),
+        Size::Field(name) => numbers
+            .get(name)
+            .cloned()
+            .ok_or_else(|| mismatch(format!("size field `{}` is not read yet", name))),
+    }
+}
+
+fn var_len_bits(max_len: usize) -> usize {
+    (usize::BITS - (max_len.max(1) - 1).leading_zeros()) as usize
+}
+
+fn remember_number(field: &Field, value: &Value, numbers: &mut Numbers) {
+    if let (Some(name), TypeExpr::UInt(_)) = (&field.name, &field.type_expr) {
+        let number = match value {
+            Value::String(string) => string.parse::<usize>().ok(),
+            Value::Number(number) => number.as_u64().map(|n| n as usize),
+            _ => None,
+        };
+        if let Some(number) = number {
+            numbers.insert(name.clone(), number);
+        }
+    }
+}
+
+/// Parses an integer given as a JSON number or a decimal or `0x` prefixed hex string.
+fn parse_integer_value(value: &Value) -> ClientResult<BigInt> {
+    if let Some(number) = value.as_i64() {
+        return Ok(BigInt::from(number));
+    }
+    if let Some(number) = value.as_u64() {
+        return Ok(BigInt::from(number));
+    }
+    let string = value
+        .as_str()
+        .ok_or_else(|| mismatch("integer must be a number or a string"))?;
+    let mut digits = string.trim();
+    let negative = if let Some(rest) = digits.strip_prefix('-') {
+        digits = rest;
+        true
+    } else {
+        digits = digits.strip_prefix('+').unwrap_or(digits);
+        false
+    };
+    let number = match digits.strip_prefix("0x").or_else(|| digits.strip_prefix("0X")) {
+        Some(hex) => BigUint::parse_bytes(hex.as_bytes(), 16),
+        None => BigUint::parse_bytes(digits.as_bytes(), 10),
+    }
+    .ok_or_else(|| mismatch(format!("invalid integer `{}`", string)))?;
+    Ok(BigInt::from_biguint(if negative { Sign::Minus } else { Sign::Plus }, number))
+}
+
+/// Checks that `number` fits `size` bits: `0 <= number < 2^size` for unsigned
+/// and `-2^(size-1) <= number < 2^(size-1)` for signed integers.
+fn check_integer_range(number: &BigInt, size: usize, signed: bool) -> ClientResult<()> {
+    let fits = if !signed {
+        number.sign() != Sign::Minus && number.bits() as usize <= size
+    } else if size == 0 {
+        number.is_zero()
+    } else {
+        let bound = BigInt::from(1) << (size - 1);
+        *number >= -bound.clone() && *number < bound
+    };
+    if fits {
+        Ok(())
+    } else {
+        Err(mismatch(format!(
+            "value `{}` is out of range of {}{}",
+            number,
+            if signed { "int" } else { "uint" },
+            size
+        )))
+    }
+}
+
+fn field_key(field: &Field, index: usize) -> String {
+    field.name.clone().unwrap_or_else(|| index.to_string())
+}
+
+/// Runs `f` one nesting level deeper, failing if the depth limit is reached.
+fn nested<T>(
+    depth: &std::cell::Cell<usize>,
+    type_name: &str,
+    f: impl FnOnce() -> ClientResult<T>,
+) -> ClientResult<T> {
+    let current = depth.get();
+    if current >= MAX_NESTING_DEPTH {
+        return Err(Error::invalid_tlb_schema(format!(
+            "type `{}` is nested deeper than {} levels, the type may be infinitely recursive",
+            type_name, MAX_NESTING_DEPTH
+        )));
+    }
+    depth.set(current + 1);
+    let result = f();
+    depth.set(current);
+    result
+}
+
+/// Runs `f` for the referenced cell. Nesting depth is counted anew within the cell.
+fn referenced<T>(
+    depth: &std::cell::Cell<usize>,
+    cell_depth: &std::cell::Cell<usize>,
+    f: impl FnOnce() -> ClientResult<T>,
+) -> ClientResult<T> {
+    let current = cell_depth.get();
+    if current >= MAX_CELL_DEPTH {
+        return Err(mismatch(format!(
+            "cells are nested deeper than {} levels",
+            MAX_CELL_DEPTH
+        )));
+    }
+    let inline_depth = depth.replace(0);
+    cell_depth.set(current + 1);
+    let result = f();
+    cell_depth.set(current);
+    depth.set(inline_depth);
+    result
+}
+
+pub(crate) struct Decoder<'a> {
+    schema: &'a Schema,
+    depth: std::cell::Cell<usize>,
+    cell_depth: std::cell::Cell<usize>,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            depth: std::cell::Cell::new(0),
+            cell_depth: std::cell::Cell::new(0),
+        }
+    }
+
+    pub fn decode_cell(&self, type_expr: &TypeExpr, cell: Cell) -> ClientResult<Value> {
+        let mut slice = SliceData::from(cell);
+        let value = match type_expr {
+            TypeExpr::Anonymous(fields) => {
+                Value::Object(self.decode_fields(fields, &mut slice, Map::new())?)
+            }
+            _ => self.decode(type_expr, &mut slice, &Numbers::new())?,
+        };
+        if slice.remaining_bits() != 0 || slice.remaining_references() != 0 {
+            return Err(mismatch(format!(
+                "cell is not fully read: {} bits and {} references remain",
+                slice.remaining_bits(),
+                slice.remaining_references(),
+            )));
+        }
+        Ok(value)
+    }
+
+    fn decode_named(&self, type_name: &str, slice: &mut SliceData) -> ClientResult<Value> {
+        nested(&self.depth, type_name, || {
+            let constructor = self.find_constructor(type_name, slice)?;
+            slice
+                .get_next_bits(constructor.tag.bits)
+                .map_err(|err| mismatch(err))?;
+            let mut object = Map::new();
+            if !constructor.name.is_empty() {
+                object.insert(CONSTRUCTOR_FIELD.to_string(), Value::from(constructor.name.as_str()));
+            }
+            Ok(Value::Object(self.decode_fields(&constructor.fields, slice, object)?))
+        })
+    }
+
+    fn find_constructor(&self, type_name: &str, slice: &SliceData) -> ClientResult<&'a Constructor> {
+        for constructor in self.schema.constructors(type_name)? {
+            let tag = &constructor.tag;
+            if tag.bits == 0 {
+                return Ok(constructor);
+            }
+            if slice.remaining_bits() >= tag.bits {
+                let prefix = slice.clone().get_next_int(tag.bits).map_err(|err| mismatch(err))?;
+                if prefix == tag.value {
+                    return Ok(constructor);
+                }
+            }
+        }
+        Err(mismatch(format!("no constructor of `{}` matches the data", type_name)))
+    }
+
+    fn decode_fields(
+        &self,
+        fields: &Vec<Field>,
+        slice: &mut SliceData,
+        mut object: Map<String, Value>,
+    ) -> ClientResult<Map<String, Value>> {
+        let mut numbers = Numbers::new();
+        for (index, field) in fields.iter().enumerate() {
+            let value = self.decode(&field.type_expr, slice, &numbers)?;
+            remember_number(field, &value, &mut numbers);
+            object.insert(field_key(field, index), value);
+        }
+        Ok(object)
+    }
+
+    fn decode(&self, type_expr: &TypeExpr, slice: &mut SliceData, numbers: &Numbers) -> ClientResult<Value> {
+        Ok(match type_expr {
+            TypeExpr::UInt(size) => {
+                let size = resolve_size(size, numbers)?;
+                Value::String(read_biguint(slice, size).map_err(|err| mismatch(err))?.to_string())
+            }
+            TypeExpr::Int(size) => {
+                let size = resolve_size(size, numbers)?;
+                Value::String(read_bigint(slice, size).map_err(|err| mismatch(err))?.to_string())
+            }
+            TypeExpr::Bits(size) => {
+                let size = resolve_size(size, numbers)?;
+                let bits = slice.get_next_bits(size).map_err(|err| mismatch(err))?;
+                Value::String(bits_to_hex_string(&bits, size))
+            }
+            TypeExpr::Bool => Value::Bool(slice.get_next_bit().map_err(|err| mismatch(err))?),
+            TypeExpr::VarUInt(max_len) | TypeExpr::VarInt(max_len) => {
+                let len = slice
+                    .get_next_int(var_len_bits(*max_len))
+                    .map_err(|err| mismatch(err))? as usize;
+                let value = if let TypeExpr::VarInt(_) = type_expr {
+                    read_bigint(slice, len * 8).map_err(|err| mismatch(err))?.to_string()
+                } else {
+                    read_biguint(slice, len * 8).map_err(|err| mismatch(err))?.to_string()
+                };
+                Value::String(value)
+            }
+            TypeExpr::Address => {
+                match MsgAddress::construct_from(slice).map_err(|err| mismatch(err))? {
+                    MsgAddress::AddrNone => Value::Null,
+                    address => Value::String(address.to_string()),
+                }
+            }
+            TypeExpr::Ref(inner) => {
+                let cell = slice.checked_drain_reference().map_err(|err| mismatch(err))?;
+                if let TypeExpr::Cell = inner.as_ref() {
+                    Value::String(serialize_cell_to_base64(&cell, "cell")?)
+                } else {
+                    referenced(&self.depth, &self.cell_depth, || self.decode_cell(inner, cell))?
+                }
+            }
+            TypeExpr::Maybe(inner) => {
+                if slice.get_next_bit().map_err(|err| mismatch(err))? {
+                    self.decode(inner, slice, numbers)?
+                } else {
+                    Value::Null
+                }
+            }
+            TypeExpr::Either(left, right) => {
+                if slice.get_next_bit().map_err(|err| mismatch(err))? {
+                    json!({ "right": self.decode(right, slice, numbers)? })
+                } else {
+                    json!({ "left": self.decode(left, slice, numbers)? })
+                }
+            }
+            TypeExpr::HashmapE(key_size, value_type) => {
+                let mut dict = HashmapE::with_bit_len(*key_size);
+                dict.read_hashmap_data(slice).map_err(|err| mismatch(err))?;
+                let mut items = Vec::new();
+                dict.iterate_slices(|key, value| {
+                    items.push((key, value));
+                    Ok(true)
+                }).map_err(|err| mismatch(err))?;
+                let mut entries = Map::new();
+                for (mut key, mut value) in items {
+                    let key = read_biguint(&mut key, *key_size).map_err(|err| mismatch(err))?;
+                    entries.insert(
+                        format_dict_key(&key, *key_size),
+                        self.decode(value_type, &mut value, &Numbers::new())?,
+                    );
+                }
+                Value::Object(entries)
+            }
+            TypeExpr::Named(type_name) => self.decode_named(type_name, slice)?,
+            TypeExpr::Cell | TypeExpr::Anonymous(_) => {
+                return Err(mismatch("cell can be read only as a reference"));
+            }
+        })
+    }
+}
+
+pub(crate) struct Encoder<'a> {
+    schema: &'a Schema,
+    depth: std::cell::Cell<usize>,
+    cell_depth: std::cell::Cell<usize>,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(schema: &'a Schema) -> Self {
+        Self {
+            schema,
+            depth: std::cell::Cell::new(0),
+            cell_depth: std::cell::Cell::new(0),
+        }
+    }
+
+    pub fn encode_cell(&self, type_expr: &TypeExpr, value: &Value) -> ClientResult<Cell> {
+        if let TypeExpr::Cell = type_expr {
+            let boc = value.as_str().ok_or_else(|| mismatch("cell must be a base64 BOC"))?;
+            return Ok(deserialize_cell_from_base64(boc, "cell")?.1);
+        }
+        let mut builder = BuilderData::new();
+        match type_expr {
+            TypeExpr::Anonymous(fields) => self.encode_fields(fields, value, &mut builder)?,
+            _ => self.encode(type_expr, value, &mut builder, &Numbers::new())?,
+        }
+        builder.into_cell().map_err(|err| mismatch(err))
+    }
+
+    fn encode_named(&self, type_name: &str, value: &Value, builder: &mut BuilderData) -> ClientResult<()> {
+        nested(&self.depth, type_name, || self.encode_constructor(type_name, value, builder))
+    }
+
+    fn encode_constructor(&self, type_name: &str, value: &Value, builder: &mut BuilderData) -> ClientResult<()> {
+        let constructors = self.schema.constructors(type_name)?;
+        let constructor = match value.get(CONSTRUCTOR_FIELD).and_then(|name| name.as_str()) {
+            Some(name) => constructors
+                .iter()
+                .find(|constructor| constructor.name == name)
+                .ok_or_else(|| mismatch(format!("`{}` is not a constructor of `{}`", name, type_name)))?,
+            None if constructors.len() == 1 => &constructors[0],
+            None => {
+                return Err(mismatch(format!(
+                    "`{}` field is required to select a constructor of `{}`",
+                    CONSTRUCTOR_FIELD, type_name
+                )))
+            }
+        };
+        if constructor.tag.bits > 0 {
+            append_number(builder, false, BigUint::from(constructor.tag.value), constructor.tag.bits, type_name)?;
+        }
+        self.encode_fields(&constructor.fields, value, builder)
+    }
+
+    fn encode_fields(&self, fields: &Vec<Field>, value: &Value, builder: &mut BuilderData) -> ClientResult<()> {
+        let mut numbers = Numbers::new();
+        for (index, field) in fields.iter().enumerate() {
+            let key = field_key(field, index);
+            let field_value = value
+                .get(&key)
+                .ok_or_else(|| mismatch(format!("field `{}` is missing", key)))?;
+            self.encode(&field.type_expr, field_value, builder, &numbers)?;
+            remember_number(field, field_value, &mut numbers);
+        }
+        Ok(())
+    }
+
+    fn encode(
+        &self,
+        type_expr: &TypeExpr,
+        value: &Value,
+        builder: &mut BuilderData,
+        numbers: &Numbers,
+    ) -> ClientResult<()> {
+        match type_expr {
+            TypeExpr::UInt(size) | TypeExpr::Int(size) => {
+                let size = resolve_size(size, numbers)?;
+                let number = parse_integer_value(value)?;
+                check_integer_range(&number, size, matches!(type_expr, TypeExpr::Int(_)))?;
+                append_number(
+                    builder,
+                    number.sign() == Sign::Minus,
+                    number.magnitude().clone(),
+                    size,
+                    "integer",
+                )?;
+            }
+            TypeExpr::Bits(size) => {
+                let size = resolve_size(size, numbers)?;
+                let string = value.as_str().ok_or_else(|| mismatch("bits must be a string"))?;
+                let mut bits = BuilderData::new();
+                append_bitstring(&mut bits, string)?;
+                if bits.length_in_bits() != size {
+                    return Err(mismatch(format!(
+                        "expected {} bits, but `{}` contains {} bits", size, string, bits.length_in_bits()
+                    )));
+                }
+                builder.append_builder(&bits).map_err(|err| mismatch(err))?;
+            }
+            TypeExpr::Bool => {
+                let bit = value.as_bool().ok_or_else(|| mismatch("expected boolean value"))?;
+                if bit {
+                    builder.append_bit_one()
+                } else {
+                    builder.append_bit_zero()
+                }.map_err(|err| mismatch(err))?;
+            }
+            TypeExpr::VarUInt(max_len) | TypeExpr::VarInt(max_len) => {
+                let string = match value {
+                    Value::String(string) => string.clone(),
+                    Value::Number(number) => number.to_string(),
+                    _ => return Err(mismatch("expected integer value")),
+                };
+                let number = BigInt::parse_bytes(string.as_bytes(), 10)
+                    .ok_or_else(|| mismatch(format!("invalid integer `{}`", string)))?;
+                let bytes = if number.is_zero() {
+                    Vec::new()
+                } else if let TypeExpr::VarInt(_) = type_expr {
+                    number.to_signed_bytes_be()
+                } else if number.sign() == Sign::Minus {
+                    return Err(mismatch(format!("negative value `{}` for unsigned type", string)));
+                } else {
+                    number.to_biguint().unwrap_or_default().to_bytes_be()
+                };
+                if bytes.len() >= *max_len {
+                    return Err(mismatch(format!("value `{}` is too big", string)));
+                }
+                builder
+                    .append_bits(bytes.len(), var_len_bits(*max_len))
+                    .and_then(|builder| builder.append_raw(&bytes, bytes.len() * 8))
+                    .map_err(|err| mismatch(err))?;
+            }
+            TypeExpr::Address => {
+                match value {
+                    Value::Null => MsgAddress::AddrNone.write_to(builder),
+                    Value::String(address) => account_decode(address)?.write_to(builder),
+                    _ => return Err(mismatch("address must be a string or null")),
+                }.map_err(|err| mismatch(err))?;
+            }
+            TypeExpr::Ref(inner) => {
+                let cell = referenced(&self.depth, &self.cell_depth, || self.encode_cell(inner, value))?;
+                builder.append_reference_cell(cell);
+            }
+            TypeExpr::Maybe(inner) => {
+                if value.is_null() {
+                    builder.append_bit_zero().map_err(|err| mismatch(err))?;
+                } else {
+                    builder.append_bit_one().map_err(|err| mismatch(err))?;
+                    self.encode(inner, value, builder, numbers)?;
+                }
+            }
+            TypeExpr::Either(left, right) => {
+                if let Some(left_value) = value.get("left") {
+                    builder.append_bit_zero().map_err(|err| mismatch(err))?;
+                    self.encode(left, left_value, builder, numbers)?;
+                } else if let Some(right_value) = value.get("right") {
+                    builder.append_bit_one().map_err(|err| mismatch(err))?;
+                    self.encode(right, right_value, builder, numbers)?;
+                } else {
+                    return Err(mismatch("`Either` value must contain `left` or `right` field"));
+                }
+            }
+            TypeExpr::HashmapE(key_size, value_type) => {
+                let entries = value
+                    .as_object()
+                    .ok_or_else(|| mismatch("dictionary must be an object"))?;
+                let mut dict = HashmapE::with_bit_len(*key_size);
+                for (key, entry) in entries {
+                    let key_number = parse_dict_key(key, *key_size)
+                        .ok_or_else(|| mismatch(format!("invalid dictionary key `{}`", key)))?;
+                    check_integer_range(&BigInt::from(key_number.clone()), *key_size, false)
+                        .map_err(|_| mismatch(format!("dictionary key `{}` exceeds {} bits", key, key_size)))?;
+                    let mut key_builder = BuilderData::new();
+                    append_number(&mut key_builder, false, key_number, *key_size, key)?;
+                    let mut value_builder = BuilderData::new();
+                    self.encode(value_type, entry, &mut value_builder, &Numbers::new())?;
+                    let key_slice = SliceData::from(key_builder.into_cell().map_err(|err| mismatch(err))?);
+                    dict.set_builder(key_slice, &value_builder).map_err(|err| mismatch(err))?;
+                }
+                dict.write_hashmap_data(builder).map_err(|err| mismatch(err))?;
+            }
+            TypeExpr::Named(type_name) => self.encode_named(type_name, value, builder)?,
+            TypeExpr::Cell | TypeExpr::Anonymous(_) => {
+                return Err(mismatch("cell can be written only as a reference"));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+pub(crate) mod codec;
+pub(crate) mod schema;
+
+use crate::boc::internal::{deserialize_cell_from_boc, serialize_cell_to_boc};
+use crate::boc::BocCacheType;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use codec::{Decoder, Encoder};
+use schema::{Schema, TypeExpr};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfParseTlb {
+    /// BOC encoded as base64 or BOC handle
+    pub boc: String,
+    /// TL-B schema text.
+    pub schema: String,
+    /// Name of the schema type describing the BOC root cell.
+    pub root_type: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfParseTlb {
+    /// JSON containing parsed BOC
+    pub parsed: Value,
+}
+
+/// Parses BOC according to the TL-B schema.
+///
+/// Schema consists of constructor declarations `name#tag field:Type ... = TypeName;`.
+/// Tags can be specified as `#hex` or `$binary`, `_` constructor name and tag
+/// denote an anonymous constructor and an empty tag.
+///
+/// Supported field types:
+/// - `#`, `uintN`, `intN`, `bitsN`, `(## N)`, `(uint N)`, `(int N)`, `(bits N)`.
+///   `N` can be a number or the name of the previously declared unsigned field.
+/// - `Bool`, `Bit`.
+/// - `Grams`, `Coins`, `(VarUInteger N)`, `(VarInteger N)`.
+/// - `MsgAddress`, `MsgAddressInt`.
+/// - `^Cell`, `^Type`, `^[ fields ]`.
+/// - `(Maybe X)`, `(Either X Y)`, `(HashmapE N X)`.
+/// - Non-parametrized types declared in the schema.
+///
+/// Implicit fields, constraints and parametrized types are not supported.
+/// Nesting depth of the schema types within a cell is limited to 256 levels,
+/// referenced cells can be nested up to 1024 levels.
+///
+/// Each constructor is returned as an object with fields named as in the schema
+/// (anonymous fields are named with their index) and the constructor name
+/// in the `@type` field. Integers are returned as decimal strings, bit strings in
+/// bitstring notation, `^Cell` as BOC encoded with `base64`, `Maybe` as `null` or
+/// the value, `Either` as `{ "left": ... }` or `{ "right": ... }`, dictionaries as
+/// objects with keys formatted as in `boc.query_cell`.
+#[api_function]
+pub async fn parse_tlb(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfParseTlb,
+) -> ClientResult<ResultOfParseTlb> {
+    let schema = Schema::parse(&params.schema)?;
+    let (_, cell) = deserialize_cell_from_boc(&context, &params.boc, "TL-B root").await?;
+    let parsed = Decoder::new(&schema).decode_cell(&TypeExpr::Named(params.root_type), cell)?;
+    Ok(ResultOfParseTlb { parsed })
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfEncodeTlb {
+    /// JSON value in the format returned by `parse_tlb`.
+    ///
+    /// The `@type` field is required only for types with several constructors.
+    pub value: Value,
+    /// TL-B schema text.
+    pub schema: String,
+    /// Name of the schema type describing the BOC root cell.
+    pub root_type: String,
+    /// Cache type to put the result. The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ResultOfEncodeTlb {
+    /// Encoded BOC or BOC cache key.
+    pub boc: String,
+}
+
+/// Encodes JSON value into BOC according to the TL-B schema.
+///
+/// Supports the same subset of TL-B as `parse_tlb`.
+#[api_function]
+pub async fn encode_tlb(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfEncodeTlb,
+) -> ClientResult<ResultOfEncodeTlb> {
+    let schema = Schema::parse(&params.schema)?;
+    let cell = Encoder::new(&schema).encode_cell(&TypeExpr::Named(params.root_type), &params.value)?;
+    Ok(ResultOfEncodeTlb {
+        boc: serialize_cell_to_boc(&context, cell, "TL-B root", params.boc_cache).await?,
+    })
+}
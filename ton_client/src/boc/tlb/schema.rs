@@ -0,0 +1,412 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::collections::HashMap;
+
+use crate::boc::Error;
+use crate::error::{ClientError, ClientResult};
+
+const MAX_DATA_BITS: usize = 1023;
+const MAX_TAG_BITS: usize = 64;
+
+#[derive(Debug, Clone)]
+pub(crate) enum Size {
+    Const(usize),
+    /// Value of the previously read numeric field of the same constructor.
+    Field(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum TypeExpr {
+    UInt(Size),
+    Int(Size),
+    Bits(Size),
+    Bool,
+    VarUInt(usize),
+    VarInt(usize),
+    Address,
+    /// Arbitrary cell. Allowed only as a reference target (`^Cell`).
+    Cell,
+    Ref(Box<TypeExpr>),
+    /// Anonymous constructor `[ ... ]`. Allowed only as a reference target.
+    Anonymous(Vec<Field>),
+    Maybe(Box<TypeExpr>),
+    Either(Box<TypeExpr>, Box<TypeExpr>),
+    HashmapE(usize, Box<TypeExpr>),
+    Named(String),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Field {
+    pub name: Option<String>,
+    pub type_expr: TypeExpr,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Tag {
+    pub value: u64,
+    pub bits: usize,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct Constructor {
+    /// Constructor name. Empty for `_` constructors.
+    pub name: String,
+    pub tag: Tag,
+    pub fields: Vec<Field>,
+}
+
+/// Parsed TL-B schema: type name -> type constructors in declaration order.
+#[derive(Debug, Default)]
+pub(crate) struct Schema {
+    pub types: HashMap<String, Vec<Constructor>>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Colon,
+    Equals,
+    Semicolon,
+    Caret,
+    Open,
+    Close,
+    OpenSquare,
+    CloseSquare,
+    OpenCurly,
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+fn schema_error(message: impl std::fmt::Display) -> ClientError {
+    Error::invalid_tlb_schema(message)
+}
+
+impl Parser {
+    fn is_word_char(c: char) -> bool {
+        !c.is_whitespace() && !":=;^()[]{}".contains(c)
+    }
+
+    fn tokenize(source: &str) -> ClientResult<Self> {
+        let chars: Vec<char> = source.chars().collect();
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if c.is_whitespace() {
+                i += 1;
+                continue;
+            }
+            if c == '/' && chars.get(i + 1) == Some(&'/') {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            if c == '/' && chars.get(i + 1) == Some(&'*') {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err(schema_error("unterminated comment"));
+                }
+                i += 2;
+                continue;
+            }
+            let token = match c {
+                ':' => Token::Colon,
+                '=' => Token::Equals,
+                ';' => Token::Semicolon,
+                '^' => Token::Caret,
+                '(' => Token::Open,
+                ')' => Token::Close,
+                '[' => Token::OpenSquare,
+                ']' => Token::CloseSquare,
+                '{' => Token::OpenCurly,
+                '}' => return Err(schema_error("unexpected `}`")),
+                _ => {
+                    let start = i;
+                    while i < chars.len() && Self::is_word_char(chars[i]) {
+                        i += 1;
+                    }
+                    tokens.push(Token::Word(chars[start..i].iter().collect()));
+                    continue;
+                }
+            };
+            tokens.push(token);
+            i += 1;
+        }
+        Ok(Self { tokens, pos: 0 })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<&Token> {
+        let token = self.tokens.get(self.pos);
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token, what: &str) -> ClientResult<()> {
+        match self.next() {
+            Some(token) if *token == expected => Ok(()),
+            _ => Err(schema_error(format!("{} expected", what))),
+        }
+    }
+
+    fn word(&mut self, what: &str) -> ClientResult<String> {
+        match self.next() {
+            Some(Token::Word(word)) => Ok(word.clone()),
+            _ => Err(schema_error(format!("{} expected", what))),
+        }
+    }
+
+    fn parse_schema(&mut self) -> ClientResult<Schema> {
+        let mut schema = Schema::default();
+        while self.peek().is_some() {
+            let (type_name, constructor) = self.parse_declaration()?;
+            schema.types.entry(type_name).or_insert_with(Vec::new).push(constructor);
+        }
+        Ok(schema)
+    }
+
+    fn parse_declaration(&mut self) -> ClientResult<(String, Constructor)> {
+        let (name, tag) = Self::parse_constructor_name(&self.word("constructor name")?)?;
+        let fields = self.parse_fields(Token::Equals)?;
+        self.expect(Token::Equals, "`=`")?;
+        let type_name = self.word("type name")?;
+        if self.peek() != Some(&Token::Semicolon) {
+            return Err(schema_error(format!(
+                "parametrized type `{}` is not supported", type_name
+            )));
+        }
+        self.expect(Token::Semicolon, "`;`")?;
+        Ok((type_name, Constructor { name, tag, fields }))
+    }
+
+    fn parse_constructor_name(word: &str) -> ClientResult<(String, Tag)> {
+        let (name, tag) = if let Some(pos) = word.find(|c| c == '#' || c == '$') {
+            let (name, tag) = word.split_at(pos);
+            (name, Self::parse_tag(tag)?)
+        } else {
+            (word, Tag::default())
+        };
+        let name = if name == "_" { "" } else { name };
+        Ok((name.to_string(), tag))
+    }
+
+    fn parse_tag(tag: &str) -> ClientResult<Tag> {
+        let invalid_tag = || schema_error(format!("invalid constructor tag `{}`", tag));
+        let (radix, digits) = tag.split_at(1);
+        if digits == "_" {
+            return Ok(Tag::default());
+        }
+        let (bits_per_digit, radix) = if radix == "#" { (4, 16) } else { (1, 2) };
+        let bits = digits.len() * bits_per_digit;
+        if digits.is_empty() || bits > MAX_TAG_BITS {
+            return Err(invalid_tag());
+        }
+        let value = u64::from_str_radix(digits, radix).map_err(|_| invalid_tag())?;
+        Ok(Tag { value, bits })
+    }
+
+    fn parse_fields(&mut self, terminator: Token) -> ClientResult<Vec<Field>> {
+        let mut fields = Vec::new();
+        while let Some(token) = self.peek() {
+            if *token == terminator {
+                break;
+            }
+            if *token == Token::OpenCurly {
+                return Err(schema_error("implicit fields and constraints are not supported"));
+            }
+            let name = if let (Some(Token::Word(word)), Some(Token::Colon)) =
+                (self.tokens.get(self.pos), self.tokens.get(self.pos + 1))
+            {
+                let name = if word == "_" { None } else { Some(word.clone()) };
+                self.pos += 2;
+                name
+            } else {
+                None
+            };
+            let type_expr = self.parse_type()?;
+            fields.push(Field { name, type_expr });
+        }
+        Ok(fields)
+    }
+
+    fn parse_type(&mut self) -> ClientResult<TypeExpr> {
+        match self.next() {
+            Some(Token::Caret) => {
+                if self.peek() == Some(&Token::OpenSquare) {
+                    self.pos += 1;
+                    let fields = self.parse_fields(Token::CloseSquare)?;
+                    self.expect(Token::CloseSquare, "`]`")?;
+                    Ok(TypeExpr::Ref(Box::new(TypeExpr::Anonymous(fields))))
+                } else {
+                    Ok(TypeExpr::Ref(Box::new(self.parse_ref_target()?)))
+                }
+            }
+            Some(Token::Open) => {
+                let head = self.word("type name")?;
+                let type_expr = self.parse_application(&head)?;
+                self.expect(Token::Close, "`)`")?;
+                Ok(type_expr)
+            }
+            Some(Token::Word(word)) => {
+                let word = word.clone();
+                Self::parse_simple_type(&word)
+            }
+            _ => Err(schema_error("type expected")),
+        }
+    }
+
+    fn parse_ref_target(&mut self) -> ClientResult<TypeExpr> {
+        if let Some(Token::Word(word)) = self.peek() {
+            if word == "Cell" {
+                self.pos += 1;
+                return Ok(TypeExpr::Cell);
+            }
+        }
+        self.parse_type()
+    }
+
+    fn parse_simple_type(word: &str) -> ClientResult<TypeExpr> {
+        let sized = |prefix: &str| -> Option<usize> {
+            if word.starts_with(prefix) {
+                word[prefix.len()..].parse::<usize>().ok()
+            } else {
+                None
+            }
+        };
+        Ok(match word {
+            "#" => TypeExpr::UInt(Size::Const(32)),
+            "Bool" => TypeExpr::Bool,
+            "Bit" => TypeExpr::UInt(Size::Const(1)),
+            "Grams" | "Coins" => TypeExpr::VarUInt(16),
+            "MsgAddress" | "MsgAddressInt" => TypeExpr::Address,
+            "Cell" => {
+                return Err(schema_error("`Cell` can be used only as a reference `^Cell`"));
+            }
+            _ => {
+                if let Some(size) = sized("uint") {
+                    TypeExpr::UInt(Self::check_size(size, 256)?)
+                } else if let Some(size) = sized("int") {
+                    TypeExpr::Int(Self::check_size(size, 257)?)
+                } else if let Some(size) = sized("bits") {
+                    TypeExpr::Bits(Self::check_size(size, MAX_DATA_BITS)?)
+                } else if word.chars().next().map(|c| c.is_ascii_uppercase()).unwrap_or(false) {
+                    TypeExpr::Named(word.to_string())
+                } else {
+                    return Err(schema_error(format!("unknown type `{}`", word)));
+                }
+            }
+        })
+    }
+
+    fn check_size(size: usize, max: usize) -> ClientResult<Size> {
+        if size > max {
+            Err(schema_error(format!("size {} exceeds maximum {}", size, max)))
+        } else {
+            Ok(Size::Const(size))
+        }
+    }
+
+    fn parse_size(&mut self, max: usize) -> ClientResult<Size> {
+        let word = self.word("size")?;
+        if let Ok(size) = word.parse::<usize>() {
+            Self::check_size(size, max)
+        } else if word.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            Ok(Size::Field(word))
+        } else {
+            Err(schema_error(format!("invalid size `{}`", word)))
+        }
+    }
+
+    fn parse_const(&mut self, what: &str) -> ClientResult<usize> {
+        let word = self.word(what)?;
+        word.parse::<usize>()
+            .map_err(|_| schema_error(format!("{} must be a number: `{}`", what, word)))
+    }
+
+    fn parse_application(&mut self, head: &str) -> ClientResult<TypeExpr> {
+        Ok(match head {
+            "##" | "uint" => TypeExpr::UInt(self.parse_size(256)?),
+            "int" => TypeExpr::Int(self.parse_size(257)?),
+            "bits" => TypeExpr::Bits(self.parse_size(MAX_DATA_BITS)?),
+            "VarUInteger" => TypeExpr::VarUInt(self.parse_const("VarUInteger size")?),
+            "VarInteger" => TypeExpr::VarInt(self.parse_const("VarInteger size")?),
+            "Maybe" => TypeExpr::Maybe(Box::new(self.parse_type()?)),
+            "Either" => {
+                let left = self.parse_type()?;
+                let right = self.parse_type()?;
+                TypeExpr::Either(Box::new(left), Box::new(right))
+            }
+            "HashmapE" => {
+                let key_size = self.parse_const("HashmapE key size")?;
+                if key_size == 0 || key_size > MAX_DATA_BITS {
+                    return Err(schema_error(format!("invalid HashmapE key size {}", key_size)));
+                }
+                TypeExpr::HashmapE(key_size, Box::new(self.parse_type()?))
+            }
+            _ => return Err(schema_error(format!("unsupported type application `{}`", head))),
+        })
+    }
+}
+
+impl Schema {
+    pub(crate) fn parse(source: &str) -> ClientResult<Self> {
+        let schema = Parser::tokenize(source)?.parse_schema()?;
+        schema.check_references()?;
+        Ok(schema)
+    }
+
+    pub(crate) fn constructors(&self, type_name: &str) -> ClientResult<&Vec<Constructor>> {
+        self.types
+            .get(type_name)
+            .ok_or_else(|| schema_error(format!("type `{}` is not declared", type_name)))
+    }
+
+    fn check_references(&self) -> ClientResult<()> {
+        fn check_fields(schema: &Schema, fields: &Vec<Field>) -> ClientResult<()> {
+            fields.iter().try_for_each(|field| check_type(schema, &field.type_expr))
+        }
+
+        fn check_type(schema: &Schema, type_expr: &TypeExpr) -> ClientResult<()> {
+            match type_expr {
+                TypeExpr::Named(name) => schema.constructors(name).map(|_| ()),
+                TypeExpr::Ref(inner) | TypeExpr::Maybe(inner) | TypeExpr::HashmapE(_, inner) => {
+                    check_type(schema, inner)
+                }
+                TypeExpr::Either(left, right) => {
+                    check_type(schema, left)?;
+                    check_type(schema, right)
+                }
+                TypeExpr::Anonymous(fields) => check_fields(schema, fields),
+                _ => Ok(()),
+            }
+        }
+
+        self.types
+            .values()
+            .flatten()
+            .try_for_each(|constructor| check_fields(self, &constructor.fields))
+    }
+}
@@ -14,12 +14,12 @@
 use ton_block::{Number5, StateInit, StateInitLib, TickTock};
 use ton_types::{BuilderData, Cell, SliceData};
 
-use crate::boc::internal::{deserialize_cell_from_boc, deserialize_object_from_boc, serialize_cell_to_boc, serialize_object_to_boc};
+use crate::boc::internal::{deserialize_cell_from_boc, deserialize_object_from_boc, serialize_cell_to_boc, serialize_object_to_boc_ex};
 use crate::boc::Error;
 use crate::client::ClientContext;
 use crate::error::ClientResult;
 
-use super::BocCacheType;
+use super::{BocCacheType, BocSerializationOptions};
 
 const OLD_CPP_SELECTOR_DATA: &[u8] = &[0xff, 0x00, 0x20, 0xc1, 0x01, 0xf4, 0xa4, 0x20, 0x58, 0x92, 0xf4, 0xa0, 0xe0, 0x5f, 0x02, 0x8a, 0x20, 0xed, 0x53, 0xd9];
 const OLD_SOL_SELECTOR_DATA: &[u8] = &[0xff, 0x00, 0xf4, 0xa4, 0x20, 0x22, 0xc0, 0x01, 0x92, 0xf4, 0xa0, 0xe1, 0x8a, 0xed, 0x53, 0x58, 0x30, 0xf4, 0xa1];
@@ -30,6 +30,9 @@ const MYCODE_SELECTOR_DATA: &[u8] = &[0x8A, 0xDB, 0x35];
 pub struct ParamsOfGetCodeFromTvc {
     /// Contract TVC image or image BOC handle
     pub tvc: String,
+    /// Replace the library reference code cell with the library cell from
+    /// the client library store. Default is `false`.
+    pub resolve_libraries: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Clone, ApiType, Default)]
@@ -47,6 +50,11 @@ pub async fn get_code_from_tvc(
     let object = deserialize_object_from_boc::<ton_block::StateInit>(&context, &params.tvc, "TVC").await?;
     
     let code = object.object.code.ok_or(Error::invalid_boc("TVC image has no code"))?;
+    let code = if params.resolve_libraries.unwrap_or(false) {
+        context.libraries.resolve(code)?
+    } else {
+        code
+    };
 
     Ok(ResultOfGetCodeFromTvc {
         code: super::internal::serialize_cell_to_base64(&code, "code")?,
@@ -108,7 +116,8 @@ pub async fn get_code_salt(
     params: ParamsOfGetCodeSalt,
 ) -> ClientResult<ResultOfGetCodeSalt> {
     let (_, code) = deserialize_cell_from_boc(&context, &params.code, "contract code").await?;
-    
+    let code = context.libraries.resolve(code)?;
+
     let (salt, _) = get_salt_and_ver(code)?;
     
     let salt = if let Some(salt) = salt {
@@ -236,6 +245,7 @@ pub async fn get_compiler_version(
     params: ParamsOfGetCompilerVersion,
 ) -> ClientResult<ResultOfGetCompilerVersion> {
     let (_, code) = deserialize_cell_from_boc(&context, &params.code, "contract code").await?;
+    let code = context.libraries.resolve(code)?;
     let version = get_compiler_version_from_cell(code)?;
 
     Ok(ResultOfGetCompilerVersion { version })
@@ -259,6 +269,8 @@ pub struct ParamsOfEncodeTvc {
     /// Cache type to put the result.
     /// The BOC itself returned if no cache type provided.
     pub boc_cache: Option<BocCacheType>,
+    /// BOC serialization options. Ignored if `boc_cache` is specified.
+    pub boc_serialization: Option<BocSerializationOptions>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -300,7 +312,14 @@ pub async fn encode_tvc(
     let state = StateInit { code, data, library, special, split_depth };
 
     Ok(ResultOfEncodeTvc { 
-        tvc: serialize_object_to_boc(&context, &state, "TVC", params.boc_cache).await?
+        tvc: serialize_object_to_boc_ex(
+            &context,
+            &state,
+            "TVC",
+            params.boc_cache,
+            params.boc_serialization.as_ref(),
+        )
+        .await?
     })
 }
 
@@ -24,11 +24,12 @@ use super::std_client_env::ClientEnv;
 #[cfg(feature = "wasm-base")]
 use super::wasm_client_env::ClientEnv;
 use super::{AppRequestResult, Error, ParamsOfAppRequest};
-use crate::abi::AbiConfig;
-use crate::boc::{cache::Bocs, BocConfig};
+use crate::abi::{registry::RegisteredAbiEntry, AbiConfig};
+use crate::boc::{cache::Bocs, libraries::Libraries, shardstate_iterator::ShardstateIterator, BocConfig};
 use crate::client::storage::KeyValueStorage;
 use crate::crypto::boxes::crypto_box::{CryptoBox, DerivedKeys};
-use crate::crypto::boxes::{encryption_box::EncryptionBox, signing_box::SigningBox};
+use crate::crypto::boxes::encryption_box::{EncryptionBox, EncryptionStream};
+use crate::crypto::boxes::signing_box::SigningBox;
 use crate::crypto::CryptoConfig;
 use crate::debot::DEngine;
 use crate::error::ClientResult;
@@ -42,6 +43,7 @@ pub struct Boxes {
     pub(crate) crypto_boxes: LockfreeMap<u32, CryptoBox>,
     pub(crate) signing_boxes: LockfreeMap<u32, Box<dyn SigningBox>>,
     pub(crate) encryption_boxes: LockfreeMap<u32, Box<dyn EncryptionBox>>,
+    pub(crate) encryption_streams: LockfreeMap<u32, std::sync::Mutex<Box<dyn EncryptionStream>>>,
 }
 
 #[derive(Debug)]
@@ -63,7 +65,10 @@ pub struct ClientContext {
     pub(crate) env: Arc<ClientEnv>,
     pub(crate) debots: LockfreeMap<u32, Mutex<DEngine>>,
     pub(crate) boxes: Boxes,
+    pub(crate) abis: LockfreeMap<u32, Arc<RegisteredAbiEntry>>,
     pub(crate) bocs: Bocs,
+    pub(crate) shardstate_iterators: LockfreeMap<u32, Arc<Mutex<ShardstateIterator>>>,
+    pub(crate) libraries: Libraries,
     pub(crate) blockchain_config: RwLock<Option<Arc<ton_executor::BlockchainConfig>>>,
 
     pub(crate) app_requests: Mutex<HashMap<u32, oneshot::Sender<AppRequestResult>>>,
@@ -104,7 +109,7 @@ Note that default values are used if parameters are omitted in config"#,
             None
         };
 
-        let bocs = Bocs::new(config.boc.cache_max_size);
+        let bocs = Bocs::new(&config.boc, config.local_storage_path.clone());
         Ok(Self {
             net: NetworkContext {
                 server_link,
@@ -116,7 +121,10 @@ Note that default values are used if parameters are omitted in config"#,
             env: env.clone(),
             debots: LockfreeMap::new(),
             boxes: Default::default(),
+            abis: LockfreeMap::new(),
             bocs,
+            shardstate_iterators: LockfreeMap::new(),
+            libraries: Libraries::default(),
             blockchain_config: RwLock::new(None),
             app_requests: Mutex::new(HashMap::new()),
             proofs_storage: Default::default(),
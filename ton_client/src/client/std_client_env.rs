@@ -27,6 +27,7 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::runtime::Runtime;
 #[cfg(test)]
@@ -250,6 +251,8 @@ lazy_static! {
     static ref KEY_FORMAT_RE: regex::Regex = regex::Regex::new(r#"^[a-zA-Z0-9_\.]+?$"#).unwrap();
 }
 
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
 pub(crate) struct LocalStorage {
     local_storage_path: Option<String>,
     storage_name: String,
@@ -314,13 +317,27 @@ impl KeyValueStorage for LocalStorage {
         }
     }
 
-    /// Put binary value by a given key into the storage
+    /// Put binary value by a given key into the storage. The value is written to a temporary
+    /// file first and then renamed, so readers never see a partially written value
     async fn put_bin(&self, key: &str, value: &[u8]) -> ClientResult<()> {
         let path = self.key_to_path(key)?;
-
-        tokio::fs::write(&path, value)
-            .await
-            .map_err(|err| Error::local_storage_error(err))
+        // `~` is not allowed in storage keys, so temporary files never clash with values
+        let temp_path = path.with_file_name(format!(
+            "{}~{}.{}",
+            key,
+            std::process::id(),
+            TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed),
+        ));
+
+        if let Err(err) = tokio::fs::write(&temp_path, value).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(Error::local_storage_error(err));
+        }
+        if let Err(err) = tokio::fs::rename(&temp_path, &path).await {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            return Err(Error::local_storage_error(err));
+        }
+        Ok(())
     }
 
     /// Get string value by a given key from the storage
@@ -1,9 +1,16 @@
 use crate::client::ClientEnv;
 use crate::crypto;
+use crate::crypto::encscrypt::argon2id_derive;
 use crate::crypto::internal::SecretBuf;
 use crate::error::ClientResult;
 use std::sync::{Arc, RwLock};
 
+use super::SecretEncryptionKdf;
+
+const ARGON2ID_MEMORY_COST: u32 = 65536;
+const ARGON2ID_ITERATIONS: u32 = 3;
+const ARGON2ID_PARALLELISM: u32 = 4;
+
 struct SecretHash(u64);
 
 impl Drop for SecretHash {
@@ -20,19 +27,33 @@ struct DerivedKey {
 }
 
 impl DerivedKey {
-    fn calc_hash(password: &[u8], salt: &str) -> SecretHash {
+    fn calc_hash(password: &[u8], salt: &str, kdf: SecretEncryptionKdf) -> SecretHash {
         let crc = crc::Crc::<u64>::new(&crc::CRC_64_ECMA_182);
         let mut digest = crc.digest();
         digest.update(password);
         digest.update(salt.as_bytes());
+        digest.update(&[kdf as u8]);
         SecretHash(digest.finalize())
     }
 
-    fn calc_key(password: &[u8], salt: &str) -> ClientResult<SecretBuf> {
-        let scrypt_params = scrypt::Params::new(14, 8, 1).expect("Scrypt params setup failed");
+    fn calc_key(password: &[u8], salt: &str, kdf: SecretEncryptionKdf) -> ClientResult<SecretBuf> {
         let mut key = SecretBuf(vec![0; 32]);
-        scrypt::scrypt(password, salt.as_bytes(), &scrypt_params, &mut key.0)
-            .map_err(|err| crypto::Error::scrypt_failed(err))?;
+        match kdf {
+            SecretEncryptionKdf::Scrypt => {
+                let scrypt_params =
+                    scrypt::Params::new(14, 8, 1).expect("Scrypt params setup failed");
+                scrypt::scrypt(password, salt.as_bytes(), &scrypt_params, &mut key.0)
+                    .map_err(|err| crypto::Error::scrypt_failed(err))?;
+            }
+            SecretEncryptionKdf::Argon2id => argon2id_derive(
+                password,
+                salt.as_bytes(),
+                ARGON2ID_MEMORY_COST,
+                ARGON2ID_ITERATIONS,
+                ARGON2ID_PARALLELISM,
+                &mut key.0,
+            )?,
+        }
         Ok(key)
     }
 }
@@ -73,6 +94,10 @@ impl DerivedKeysCache {
         self.keys.len() == 1
     }
 
+    fn remove(&mut self, hash: &SecretHash) {
+        self.keys.retain(|key| key.hash.0 != hash.0);
+    }
+
     fn clean_and_check_stop_timer(&mut self) -> bool {
         let now = self.env.now_ms();
         for i in (0..self.keys.len()).rev() {
@@ -101,13 +126,18 @@ impl DerivedKeys {
         }
     }
 
-    pub(crate) fn derive(&self, password: &[u8], salt: &str) -> ClientResult<SecretBuf> {
-        let hash = DerivedKey::calc_hash(password, salt);
+    pub(crate) fn derive(
+        &self,
+        password: &[u8],
+        salt: &str,
+        kdf: SecretEncryptionKdf,
+    ) -> ClientResult<SecretBuf> {
+        let hash = DerivedKey::calc_hash(password, salt, kdf);
         if let Some(existing) = self.touch(&hash) {
             return Ok(existing);
         }
         let calculation_start = self.env.now_ms();
-        let key = DerivedKey::calc_key(password, salt)?;
+        let key = DerivedKey::calc_key(password, salt, kdf)?;
         let calculation_time = self.env.now_ms() - calculation_start;
         let start_timer = self.put_and_check_start_timer(&hash, &key, calculation_time);
         if start_timer {
@@ -124,6 +154,12 @@ impl DerivedKeys {
         Ok(key)
     }
 
+    /// Removes the key derived from the password and salt from the cache
+    pub(crate) fn remove(&self, password: &[u8], salt: &str, kdf: SecretEncryptionKdf) {
+        let hash = DerivedKey::calc_hash(password, salt, kdf);
+        self.cache.write().unwrap().remove(&hash);
+    }
+
     fn touch(&self, hash: &SecretHash) -> Option<SecretBuf> {
         self.cache.write().unwrap().touch(&hash).map(|x| x.clone())
     }
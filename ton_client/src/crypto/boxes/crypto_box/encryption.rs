@@ -9,7 +9,7 @@ use crate::crypto::nacl::nacl_box_open_internal;
 use crate::crypto::{boxes::crypto_box::SecretInternal, internal::SecretBuf};
 use crate::error::ClientResult;
 
-use super::{Error, PasswordProvider};
+use super::{Error, PasswordProvider, SecretEncryptionKdf};
 
 const NONCE_LEN: usize = 12;
 
@@ -29,10 +29,22 @@ async fn apply_chacha20(
     secret: &[u8],
     password_provider: &PasswordProvider,
     salt: &str,
+    kdf: SecretEncryptionKdf,
     nonce: &[u8],
 ) -> ClientResult<SecretBuf> {
     let password = get_password(password_provider).await?;
-    let key = context.derived_keys.derive(&password.0, salt)?;
+    apply_chacha20_with_password(context, secret, &password.0, salt, kdf, nonce)
+}
+
+fn apply_chacha20_with_password(
+    context: Arc<ClientContext>,
+    secret: &[u8],
+    password: &[u8],
+    salt: &str,
+    kdf: SecretEncryptionKdf,
+    nonce: &[u8],
+) -> ClientResult<SecretBuf> {
+    let key = context.derived_keys.derive(password, salt, kdf)?;
     let mut cipher = chacha20::ChaCha20::new(
         chacha20::Key::from_slice(&key.0),
         chacha20::Nonce::from_slice(nonce),
@@ -48,13 +60,14 @@ pub(crate) async fn encrypt_secret(
     secret: &SecretInternal,
     password_provider: &PasswordProvider,
     salt: &str,
+    kdf: SecretEncryptionKdf,
 ) -> ClientResult<SecretBuf> {
     let mut result = generate_nonce();
     let serialized = SecretBuf(
         bincode::serialize(secret)
             .map_err(|err| Error::crypto_box_secret_serialization_error(err))?,
     );
-    apply_chacha20(context, &serialized.0, password_provider, salt, &result.0)
+    apply_chacha20(context, &serialized.0, password_provider, salt, kdf, &result.0)
         .await
         .map(|mut output| {
             result.0.append(&mut output.0);
@@ -67,12 +80,38 @@ pub(crate) async fn decrypt_secret(
     encrypted_secret: &[u8],
     password_provider: &PasswordProvider,
     salt: &str,
+    kdf: SecretEncryptionKdf,
 ) -> ClientResult<SecretInternal> {
     let (nonce, encrypted_secret) = encrypted_secret.split_at(NONCE_LEN);
-    let data = apply_chacha20(context, encrypted_secret, password_provider, salt, nonce).await?;
+    let data =
+        apply_chacha20(context, encrypted_secret, password_provider, salt, kdf, nonce).await?;
     bincode::deserialize(&data.0).map_err(|err| Error::crypto_box_secret_deserialization_error(err))
 }
 
+/// Decrypts the secret with the old password, salt and KDF and encrypts it with the new ones.
+/// Keys derived from the old password and salt are removed from the cache.
+pub(crate) async fn reencrypt_secret(
+    context: Arc<ClientContext>,
+    encrypted_secret: &[u8],
+    old_password_provider: &PasswordProvider,
+    old_salt: &str,
+    old_kdf: SecretEncryptionKdf,
+    new_password_provider: &PasswordProvider,
+    new_salt: &str,
+    new_kdf: SecretEncryptionKdf,
+) -> ClientResult<SecretBuf> {
+    let (nonce, encrypted_secret) = encrypted_secret.split_at(NONCE_LEN);
+    let old_password = get_password(old_password_provider).await?;
+    let data = apply_chacha20_with_password(
+        context.clone(), encrypted_secret, &old_password.0, old_salt, old_kdf, nonce,
+    )?;
+    context.derived_keys.remove(&old_password.0, old_salt, old_kdf);
+    let secret: SecretInternal = bincode::deserialize(&data.0)
+        .map_err(|err| Error::crypto_box_secret_deserialization_error(err))?;
+
+    encrypt_secret(context, &secret, new_password_provider, new_salt, new_kdf).await
+}
+
 async fn get_password(password_provider: &PasswordProvider) -> ClientResult<SecretBuf> {
     let (secret_key, public_key) = gen_nacl_box_keypair();
 
@@ -6,13 +6,16 @@ use lockfree::map::ReadGuard;
 use tokio::sync::RwLock;
 use zeroize::Zeroize;
 
-use crate::crypto::boxes::crypto_box::encryption::{decrypt_secret, encrypt_secret};
+use crate::crypto::boxes::crypto_box::encryption::{decrypt_secret, encrypt_secret, reencrypt_secret};
+use crate::crypto::boxes::encryption_box::aead::AeadEncryptionBox;
 use crate::crypto::boxes::encryption_box::chacha20::ChaCha20EncryptionBox;
+use crate::crypto::boxes::encryption_box::EncryptionStream;
 use crate::crypto::boxes::encryption_box::nacl_box::NaclEncryptionBox;
 use crate::crypto::boxes::encryption_box::nacl_secret_box::NaclSecretEncryptionBox;
 use crate::crypto::boxes::signing_box::KeysSigningBox;
 use crate::crypto::internal::{SecretBuf, SecretString};
-use crate::crypto::mnemonic::mnemonics;
+use crate::crypto::hdkey::HDKeyDerivationScheme;
+use crate::crypto::mnemonic::{mnemonics, TON_DICTIONARY};
 use crate::crypto::{
     register_encryption_box, register_signing_box, CryptoConfig, EncryptionBox, EncryptionBoxInfo,
     Error, RegisteredEncryptionBox, RegisteredSigningBox, SigningBox,
@@ -64,6 +67,42 @@ pub(crate) enum SecretInternal {
         dictionary: u8,
         wordcount: u8,
     },
+    // Separate variant keeps the encoding of secrets without passphrase unchanged
+    SeedPhraseWithPassphrase {
+        phrase: SecretString,
+        passphrase: SecretString,
+        dictionary: u8,
+        wordcount: u8,
+    },
+}
+
+impl SecretInternal {
+    fn seed_phrase(
+        phrase: String,
+        passphrase: Option<String>,
+        dictionary: u8,
+        wordcount: u8,
+    ) -> ClientResult<Self> {
+        Ok(match passphrase {
+            Some(passphrase) if !passphrase.is_empty() => {
+                // TON phrases can't be salted, the box would be unusable
+                if dictionary == TON_DICTIONARY {
+                    return Err(Error::mnemonic_passphrase_not_supported(dictionary));
+                }
+                SecretInternal::SeedPhraseWithPassphrase {
+                    phrase: SecretString(phrase),
+                    passphrase: SecretString(passphrase),
+                    dictionary,
+                    wordcount,
+                }
+            }
+            _ => SecretInternal::SeedPhrase {
+                phrase: SecretString(phrase),
+                dictionary,
+                wordcount,
+            },
+        })
+    }
 }
 
 impl Default for SecretInternal {
@@ -76,9 +115,26 @@ impl Default for SecretInternal {
     }
 }
 
+/// Key derivation function used to derive the secret encryption key from the password and salt.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ApiType, PartialEq)]
+pub enum SecretEncryptionKdf {
+    /// scrypt with `log_n = 14`, `r = 8`, `p = 1`.
+    Scrypt,
+    /// Argon2id with 64 MiB of memory, 3 iterations and 4 lanes.
+    /// Requires the salt to be at least 8 bytes long.
+    Argon2id,
+}
+
+impl Default for SecretEncryptionKdf {
+    fn default() -> Self {
+        SecretEncryptionKdf::Scrypt
+    }
+}
+
 pub(crate) struct CryptoBox {
     pub password_provider: PasswordProvider,
     pub secret_encryption_salt: SecretString,
+    pub secret_encryption_kdf: SecretEncryptionKdf,
     pub encrypted_secret: SecretBuf,
 }
 
@@ -94,7 +150,13 @@ pub enum CryptoBoxSecret {
     /// should use `EncryptedSecret` type instead.
     ///
     /// Get `encrypted_secret` with `get_crypto_box_info` function and store it on your side.
-    RandomSeedPhrase { dictionary: u8, wordcount: u8 },
+    RandomSeedPhrase {
+        dictionary: u8,
+        wordcount: u8,
+        /// BIP-39 passphrase ("25th word") used to derive keys from the seed phrase.
+        /// Not supported for TON dictionary.
+        passphrase: Option<String>,
+    },
 
     /// Restores crypto box instance from an existing seed phrase.
     /// This type should be used when Crypto Box is initialized from a seed phrase, entered by a user.
@@ -107,6 +169,9 @@ pub enum CryptoBoxSecret {
         phrase: String,
         dictionary: u8,
         wordcount: u8,
+        /// BIP-39 passphrase ("25th word") used to derive keys from the seed phrase.
+        /// Not supported for TON dictionary.
+        passphrase: Option<String>,
     },
 
     /// Use this type for wallet reinitializations, when you already have `encrypted_secret` on hands.
@@ -115,10 +180,13 @@ pub enum CryptoBoxSecret {
     ///
     /// It is an object, containing seed phrase or private key, encrypted with
     /// `secret_encryption_salt` and password from `password_provider`.
+    /// `secret_encryption_kdf` must be the same as the one used for the encryption: it is not
+    /// stored in `encrypted_secret`, so store the one returned by `get_crypto_box_info`
+    /// along with `encrypted_secret`.
     ///
-    /// Note that if you want to change salt or password provider, then you need to reinitialize
-    /// the wallet with `PredefinedSeedPhrase`, then get `EncryptedSecret` via `get_crypto_box_info`,
-    /// store it somewhere, and only after that initialize the wallet with `EncryptedSecret` type.
+    /// Note that if you want to change salt or password provider, then you need to call
+    /// `change_crypto_box_password`, store the returned `encrypted_secret`, salt and KDF, and only
+    /// after that initialize the wallet with `EncryptedSecret` type.
     EncryptedSecret {
         /// It is an object, containing encrypted seed phrase or private key (now we support only seed phrase).
         encrypted_secret: String,
@@ -131,6 +199,7 @@ impl Default for CryptoBoxSecret {
             phrase: Default::default(),
             dictionary: DEFAULT_DICTIONARY,
             wordcount: DEFAULT_WORDCOUNT,
+            passphrase: None,
         }
     }
 }
@@ -143,6 +212,13 @@ pub struct ParamsOfCreateCryptoBox {
     /// For example, a mobile device can use device ID as salt.
     pub secret_encryption_salt: String,
 
+    /// Key derivation function used for secret encryption. Default is `Scrypt`.
+    ///
+    /// For `EncryptedSecret` it must be the KDF returned by `get_crypto_box_info`
+    /// or `change_crypto_box_password`, otherwise a wrong key is derived.
+    #[zeroize(skip)]
+    pub secret_encryption_kdf: Option<SecretEncryptionKdf>,
+
     /// Cryptobox secret
     pub secret: CryptoBoxSecret,
 }
@@ -163,25 +239,29 @@ pub async fn create_crypto_box(
     params: ParamsOfCreateCryptoBox,
     password_provider: PasswordProvider,
 ) -> ClientResult<RegisteredCryptoBox> {
+    let kdf = params.secret_encryption_kdf.unwrap_or_default();
     let encrypted_secret = match &params.secret {
         CryptoBoxSecret::RandomSeedPhrase {
             dictionary,
             wordcount,
+            passphrase,
         } => {
             let config = CryptoConfig::default();
             let phrase = {
                 let mnemonics = mnemonics(&config, Some(*dictionary), Some(*wordcount))?;
-                SecretInternal::SeedPhrase {
-                    phrase: SecretString(mnemonics.generate_random_phrase()?),
-                    dictionary: *dictionary,
-                    wordcount: *wordcount,
-                }
+                SecretInternal::seed_phrase(
+                    mnemonics.generate_random_phrase()?,
+                    passphrase.clone(),
+                    *dictionary,
+                    *wordcount,
+                )?
             };
             encrypt_secret(
                 context.clone(),
                 &phrase,
                 &password_provider,
                 &params.secret_encryption_salt,
+                kdf,
             )
             .await?
         }
@@ -190,16 +270,19 @@ pub async fn create_crypto_box(
             phrase,
             dictionary,
             wordcount,
+            passphrase,
         } => {
             encrypt_secret(
                 context.clone(),
-                &SecretInternal::SeedPhrase {
-                    phrase: SecretString(phrase.clone()),
-                    dictionary: *dictionary,
-                    wordcount: *wordcount,
-                },
+                &SecretInternal::seed_phrase(
+                    phrase.clone(),
+                    passphrase.clone(),
+                    *dictionary,
+                    *wordcount,
+                )?,
                 &password_provider,
                 &params.secret_encryption_salt,
+                kdf,
             )
             .await?
         }
@@ -212,6 +295,7 @@ pub async fn create_crypto_box(
     let crypto_box = CryptoBox {
         password_provider,
         secret_encryption_salt: SecretString(params.secret_encryption_salt.clone()),
+        secret_encryption_kdf: kdf,
         encrypted_secret,
     };
     let id = context.get_next_id();
@@ -230,6 +314,7 @@ pub async fn remove_crypto_box(
     params: RegisteredCryptoBox,
 ) -> ClientResult<()> {
     context.boxes.crypto_boxes.remove(&params.handle.0);
+    remove_encryption_streams(&context, params.handle);
     Ok(())
 }
 
@@ -240,6 +325,8 @@ pub struct ResultOfGetCryptoBoxSeedPhrase {
     pub phrase: String,
     pub dictionary: u8,
     pub wordcount: u8,
+    /// BIP-39 passphrase, if the crypto box was created with it.
+    pub passphrase: Option<String>,
 }
 
 /// Get Crypto Box Seed Phrase.
@@ -250,11 +337,7 @@ pub async fn get_crypto_box_seed_phrase(
     context: Arc<ClientContext>,
     params: RegisteredCryptoBox,
 ) -> ClientResult<ResultOfGetCryptoBoxSeedPhrase> {
-    let SecretInternal::SeedPhrase {
-        phrase,
-        dictionary,
-        wordcount,
-    } = {
+    let secret = {
         let guard = get_crypto_box(&context, &params.handle)?;
         let crypto_box = guard.val();
         decrypt_secret(
@@ -262,14 +345,33 @@ pub async fn get_crypto_box_seed_phrase(
             &crypto_box.encrypted_secret.0,
             &crypto_box.password_provider,
             &crypto_box.secret_encryption_salt.0,
+            crypto_box.secret_encryption_kdf,
         )
         .await?
     };
 
-    Ok(ResultOfGetCryptoBoxSeedPhrase {
-        phrase: phrase.0.clone(),
-        dictionary,
-        wordcount,
+    Ok(match &secret {
+        SecretInternal::SeedPhrase {
+            phrase,
+            dictionary,
+            wordcount,
+        } => ResultOfGetCryptoBoxSeedPhrase {
+            phrase: phrase.0.clone(),
+            dictionary: *dictionary,
+            wordcount: *wordcount,
+            passphrase: None,
+        },
+        SecretInternal::SeedPhraseWithPassphrase {
+            phrase,
+            passphrase,
+            dictionary,
+            wordcount,
+        } => ResultOfGetCryptoBoxSeedPhrase {
+            phrase: phrase.0.clone(),
+            dictionary: *dictionary,
+            wordcount: *wordcount,
+            passphrase: Some(passphrase.0.clone()),
+        },
     })
 }
 
@@ -277,26 +379,103 @@ pub async fn get_crypto_box_seed_phrase(
 pub struct ResultOfGetCryptoBoxInfo {
     /// Secret (seed phrase) encrypted with salt and password.
     pub encrypted_secret: String,
+    /// Key derivation function used for the secret encryption.
+    /// Must be passed to `create_crypto_box` together with `encrypted_secret`.
+    pub secret_encryption_kdf: SecretEncryptionKdf,
 }
 
 /// Get Crypto Box Info.
-/// Used to get `encrypted_secret` that should be used
+/// Used to get `encrypted_secret` and `secret_encryption_kdf` that should be used
 /// for all the cryptobox initializations except the first one.
 #[api_function]
 pub async fn get_crypto_box_info(
     context: Arc<ClientContext>,
     params: RegisteredCryptoBox,
 ) -> ClientResult<ResultOfGetCryptoBoxInfo> {
+    let guard = get_crypto_box(&context, &params.handle)?;
+    let crypto_box = guard.val();
     Ok(ResultOfGetCryptoBoxInfo {
-        encrypted_secret: base64::encode(
-            &get_crypto_box(&context, &params.handle)?
-                .val()
-                .encrypted_secret
-                .0,
-        ),
+        encrypted_secret: base64::encode(&crypto_box.encrypted_secret.0),
+        secret_encryption_kdf: crypto_box.secret_encryption_kdf,
     })
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfChangeCryptoBoxPassword {
+    /// Crypto Box Handle.
+    pub handle: CryptoBoxHandle,
+    /// Key derivation function used for the new secret encryption.
+    /// By default, the current one is kept.
+    pub secret_encryption_kdf: Option<SecretEncryptionKdf>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfChangeCryptoBoxPassword {
+    /// Secret (seed phrase) encrypted with the new salt and password.
+    pub encrypted_secret: String,
+    /// New salt used for secret encryption.
+    /// Must be passed to `create_crypto_box` together with `encrypted_secret`.
+    pub secret_encryption_salt: String,
+    /// Key derivation function used for the new secret encryption.
+    /// Must be passed to `create_crypto_box` together with `encrypted_secret`.
+    pub secret_encryption_kdf: SecretEncryptionKdf,
+}
+
+/// Changes Crypto Box password.
+///
+/// Decrypts the secret with the password from the current `password_provider`
+/// and encrypts it with a fresh random salt and the password from the new `password_provider`.
+/// Crypto box keeps its handle and uses the new password provider for further operations.
+/// Pass `secret_encryption_kdf` to switch the key derivation function, e.g. to `Argon2id`.
+/// Keys derived from the old password are removed from the cache.
+pub async fn change_crypto_box_password(
+    context: Arc<ClientContext>,
+    params: ParamsOfChangeCryptoBoxPassword,
+    password_provider: PasswordProvider,
+) -> ClientResult<ResultOfChangeCryptoBoxPassword> {
+    let secret_encryption_salt = generate_salt();
+    let (encrypted_secret, secret_encryption_kdf) = {
+        let guard = get_crypto_box(&context, &params.handle)?;
+        let crypto_box = guard.val();
+        let kdf = params.secret_encryption_kdf.unwrap_or(crypto_box.secret_encryption_kdf);
+        let encrypted_secret = reencrypt_secret(
+            context.clone(),
+            &crypto_box.encrypted_secret.0,
+            &crypto_box.password_provider,
+            &crypto_box.secret_encryption_salt.0,
+            crypto_box.secret_encryption_kdf,
+            &password_provider,
+            &secret_encryption_salt,
+            kdf,
+        )
+        .await?;
+        (encrypted_secret, kdf)
+    };
+    let result = ResultOfChangeCryptoBoxPassword {
+        encrypted_secret: base64::encode(&encrypted_secret.0),
+        secret_encryption_salt: secret_encryption_salt.clone(),
+        secret_encryption_kdf,
+    };
+
+    context.boxes.crypto_boxes.insert(
+        params.handle.0,
+        CryptoBox {
+            password_provider,
+            secret_encryption_salt: SecretString(secret_encryption_salt),
+            secret_encryption_kdf,
+            encrypted_secret,
+        },
+    );
+
+    Ok(result)
+}
+
+fn generate_salt() -> String {
+    let mut salt = [0u8; 32];
+    rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut salt);
+    hex::encode(salt)
+}
+
 fn get_crypto_box<'context>(
     context: &'context Arc<ClientContext>,
     handle: &CryptoBoxHandle,
@@ -395,7 +574,9 @@ impl<T: Send + Sync + 'static> BoxFromCryptoBoxLifeCycleManager<T> {
                 .derive_ed25519_keys_from_phrase(
                     &context.config.crypto,
                     &seed_phrase.phrase,
+                    seed_phrase.passphrase.as_deref().unwrap_or_default(),
                     hdpath,
+                    HDKeyDerivationScheme::Bip32,
                 )
                 .map::<ClientResult<Keypair>, _>(|keypair| {
                     Ok(Keypair {
@@ -486,6 +667,9 @@ pub struct NaclBoxParamsCB {
     pub their_public: String,
     /// 96-bit nonce. Must be encoded with `hex`.
     pub nonce: String,
+    /// Convert the derived ed25519 key to X25519 key. `their_public` must be ed25519 key
+    /// in this case. Default is `false`: the derived key is used as X25519 secret as is.
+    pub ed25519_keys: Option<bool>,
 }
 
 impl NaclBoxParamsCB {
@@ -497,6 +681,7 @@ impl NaclBoxParamsCB {
             their_public: self.their_public.clone(),
             secret: secret.0.clone(),
             nonce: self.nonce.clone(),
+            ed25519_keys: self.ed25519_keys,
         }
     }
 }
@@ -519,12 +704,50 @@ impl NaclSecretBoxParamsCB {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct AesGcmParamsCB {
+    /// Associated data. Must be encoded with `base64`.
+    pub aad: Option<String>,
+}
+
+impl AesGcmParamsCB {
+    fn to_encryption_box_params(
+        &self,
+        key: SecretString,
+    ) -> super::encryption_box::aead::AesGcmParamsEB {
+        super::encryption_box::aead::AesGcmParamsEB {
+            key: key.0.clone(),
+            aad: self.aad.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ChaCha20Poly1305ParamsCB {
+    /// Associated data. Must be encoded with `base64`.
+    pub aad: Option<String>,
+}
+
+impl ChaCha20Poly1305ParamsCB {
+    fn to_encryption_box_params(
+        &self,
+        key: SecretString,
+    ) -> super::encryption_box::aead::ChaCha20Poly1305ParamsEB {
+        super::encryption_box::aead::ChaCha20Poly1305ParamsEB {
+            key: key.0.clone(),
+            aad: self.aad.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
 #[serde(tag = "type", content = "value")]
 pub enum BoxEncryptionAlgorithm {
     ChaCha20(ChaCha20ParamsCB),
     NaclBox(NaclBoxParamsCB),
     NaclSecretBox(NaclSecretBoxParamsCB),
+    AesGcm(AesGcmParamsCB),
+    ChaCha20Poly1305(ChaCha20Poly1305ParamsCB),
 }
 
 impl Default for BoxEncryptionAlgorithm {
@@ -595,7 +818,7 @@ impl EncryptionBoxFromCryptoBox {
             BoxEncryptionAlgorithm::NaclBox(params) => Box::new(NaclEncryptionBox::new(
                 params.to_encryption_box_params(secret),
                 self.manager.params.hdpath.clone(),
-            )),
+            )?),
 
             BoxEncryptionAlgorithm::NaclSecretBox(params) => {
                 Box::new(NaclSecretEncryptionBox::new(
@@ -603,6 +826,18 @@ impl EncryptionBoxFromCryptoBox {
                     self.manager.params.hdpath.clone(),
                 ))
             }
+
+            BoxEncryptionAlgorithm::AesGcm(params) => Box::new(AeadEncryptionBox::aes_gcm(
+                params.to_encryption_box_params(secret),
+                self.manager.params.hdpath.clone(),
+            )?),
+
+            BoxEncryptionAlgorithm::ChaCha20Poly1305(params) => {
+                Box::new(AeadEncryptionBox::chacha20_poly1305(
+                    params.to_encryption_box_params(secret),
+                    self.manager.params.hdpath.clone(),
+                )?)
+            }
         })
     }
 }
@@ -648,6 +883,43 @@ impl EncryptionBox for EncryptionBoxFromCryptoBox {
             .await
     }
 
+    async fn create_encryption_stream(
+        &self,
+        context: Arc<ClientContext>,
+        chunk_size: u32,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        let stream = self.manager
+            .with_internal_box(
+                Arc::clone(&context),
+                move |encryption_box| {
+                    let context = Arc::clone(&context);
+                    async move {
+                        encryption_box.create_encryption_stream(Arc::clone(&context), chunk_size).await
+                    }
+                },
+                |key_pair| self.factory(key_pair),
+            )
+            .await?;
+        Ok(Box::new(CryptoBoxEncryptionStream::new(self.manager.params.handle, stream)))
+    }
+
+    async fn create_decryption_stream(
+        &self,
+        context: Arc<ClientContext>,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        let stream = self.manager
+            .with_internal_box(
+                Arc::clone(&context),
+                move |encryption_box| {
+                    let context = Arc::clone(&context);
+                    async move { encryption_box.create_decryption_stream(Arc::clone(&context)).await }
+                },
+                |key_pair| self.factory(key_pair),
+            )
+            .await?;
+        Ok(Box::new(CryptoBoxEncryptionStream::new(self.manager.params.handle, stream)))
+    }
+
     async fn drop_secret(&self, crypto_box_handle: CryptoBoxHandle) {
         if self.manager.params.handle == crypto_box_handle.0 {
             *self.manager.internal_box.write().await = None;
@@ -655,8 +927,48 @@ impl EncryptionBox for EncryptionBoxFromCryptoBox {
     }
 }
 
+/// Encryption stream created by the encryption box derived from crypto box.
+struct CryptoBoxEncryptionStream {
+    crypto_box: CryptoBoxHandle,
+    stream: Box<dyn EncryptionStream>,
+}
+
+impl CryptoBoxEncryptionStream {
+    fn new(crypto_box: u32, stream: Box<dyn EncryptionStream>) -> Self {
+        Self { crypto_box: CryptoBoxHandle(crypto_box), stream }
+    }
+}
+
+impl EncryptionStream for CryptoBoxEncryptionStream {
+    fn update(&mut self, data: &[u8]) -> ClientResult<Vec<u8>> {
+        self.stream.update(data)
+    }
+
+    fn finish(&mut self) -> ClientResult<Vec<u8>> {
+        self.stream.finish()
+    }
+
+    fn crypto_box(&self) -> Option<CryptoBoxHandle> {
+        Some(self.crypto_box)
+    }
+}
+
+/// Removes encryption streams holding the keys derived from crypto box.
+/// Stream keys are overwritten with zeroes on drop.
+fn remove_encryption_streams(context: &ClientContext, handle: CryptoBoxHandle) {
+    for item in context.boxes.encryption_streams.iter() {
+        let derived = item.val()
+            .lock()
+            .map(|stream| stream.crypto_box() == Some(handle))
+            .unwrap_or(true);
+        if derived {
+            context.boxes.encryption_streams.remove(item.key());
+        }
+    }
+}
+
 /// Removes cached secrets (overwrites with zeroes) from all signing and encryption boxes,
-/// derived from crypto box.
+/// derived from crypto box. Encryption streams of the derived encryption boxes are removed.
 #[api_function]
 pub async fn clear_crypto_box_secret_cache(
     context: Arc<ClientContext>,
@@ -670,5 +982,7 @@ pub async fn clear_crypto_box_secret_cache(
         item.val().drop_secret(params.handle).await;
     }
 
+    remove_encryption_streams(&context, params.handle);
+
     Ok(())
 }
@@ -0,0 +1,391 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::sync::Arc;
+
+use aes_gcm::{Aes128Gcm, Aes256Gcm};
+use chacha20poly1305::ChaCha20Poly1305;
+use rand::RngCore;
+use zeroize::Zeroize;
+
+use crate::ClientContext;
+use crate::crypto::internal::SecretBuf;
+use crate::crypto::{EncryptionBox, EncryptionBoxInfo, Error};
+use crate::crypto::boxes::encryption_box::EncryptionStream;
+use crate::encoding::{base64_decode, hex_decode};
+use crate::error::ClientResult;
+
+const NONCE_SIZE: usize = 12;
+const TAG_SIZE: usize = 16;
+const STREAM_VERSION: u8 = 1;
+const STREAM_NONCE_PREFIX_SIZE: usize = 7;
+const STREAM_HEADER_SIZE: usize = 1 + 4 + STREAM_NONCE_PREFIX_SIZE;
+// chunk size is read from the untrusted stream header and defines the buffered data size
+const MAX_STREAM_CHUNK_SIZE: usize = 16 << 20;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct AesGcmParamsEB {
+    /// 128 or 256-bit key. Must be encoded with `hex`.
+    pub key: String,
+    /// Associated data authenticated along with the encrypted data. Must be encoded with `base64`.
+    pub aad: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, Zeroize, ZeroizeOnDrop)]
+pub struct ChaCha20Poly1305ParamsEB {
+    /// 256-bit key. Must be encoded with `hex`.
+    pub key: String,
+    /// Associated data authenticated along with the encrypted data. Must be encoded with `base64`.
+    pub aad: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AeadAlgorithm {
+    AesGcm,
+    ChaCha20Poly1305,
+}
+
+#[derive(Debug, Clone)]
+struct AeadCipher {
+    algorithm: AeadAlgorithm,
+    key: SecretBuf,
+    aad: Vec<u8>,
+}
+
+/// Authenticated encryption box. Encrypted data is the random 12-byte nonce followed by
+/// the ciphertext and 16-byte tag. Nonce is generated for each encryption, because reusing
+/// the nonce with the same key breaks both confidentiality and authenticity.
+#[derive(Debug)]
+pub struct AeadEncryptionBox {
+    cipher: AeadCipher,
+    hdpath: Option<String>,
+}
+
+impl AeadEncryptionBox {
+    pub fn aes_gcm(params: AesGcmParamsEB, hdpath: Option<String>) -> ClientResult<Self> {
+        Self::new(AeadAlgorithm::AesGcm, &params.key, &params.aad, hdpath)
+    }
+
+    pub fn chacha20_poly1305(
+        params: ChaCha20Poly1305ParamsEB,
+        hdpath: Option<String>,
+    ) -> ClientResult<Self> {
+        Self::new(AeadAlgorithm::ChaCha20Poly1305, &params.key, &params.aad, hdpath)
+    }
+
+    fn new(
+        algorithm: AeadAlgorithm,
+        key: &str,
+        aad: &Option<String>,
+        hdpath: Option<String>,
+    ) -> ClientResult<Self> {
+        let key = SecretBuf(hex_decode(key)?);
+        match algorithm {
+            AeadAlgorithm::AesGcm if key.len() != 16 && key.len() != 32 => {
+                return Err(Error::invalid_key_size(key.len(), &[16, 32]));
+            }
+            AeadAlgorithm::ChaCha20Poly1305 if key.len() != 32 => {
+                return Err(Error::invalid_key_size(key.len(), &[32]));
+            }
+            _ => {}
+        }
+        let aad = aad.as_ref().map(|aad| base64_decode(aad)).transpose()?.unwrap_or_default();
+
+        Ok(Self { cipher: AeadCipher { algorithm, key, aad }, hdpath })
+    }
+}
+
+impl AeadCipher {
+    fn seal(&self, nonce: &[u8], data: &[u8]) -> ClientResult<Vec<u8>> {
+        let result = match self.algorithm {
+            AeadAlgorithm::AesGcm if self.key.len() == 16 => {
+                aes_gcm_encrypt::<Aes128Gcm>(&self.key, nonce, data, &self.aad)
+            }
+            AeadAlgorithm::AesGcm => {
+                aes_gcm_encrypt::<Aes256Gcm>(&self.key, nonce, data, &self.aad)
+            }
+            AeadAlgorithm::ChaCha20Poly1305 => {
+                use chacha20poly1305::aead::{generic_array::GenericArray, Aead, NewAead, Payload};
+                ChaCha20Poly1305::new(GenericArray::from_slice(&self.key))
+                    .encrypt(GenericArray::from_slice(nonce), Payload { msg: data, aad: &self.aad })
+                    .map_err(|_| ())
+            }
+        };
+        result.map_err(|_| Error::encrypt_data_error("AEAD encryption failed"))
+    }
+
+    fn open(&self, nonce: &[u8], data: &[u8]) -> ClientResult<SecretBuf> {
+        let result = match self.algorithm {
+            AeadAlgorithm::AesGcm if self.key.len() == 16 => {
+                aes_gcm_decrypt::<Aes128Gcm>(&self.key, nonce, data, &self.aad)
+            }
+            AeadAlgorithm::AesGcm => {
+                aes_gcm_decrypt::<Aes256Gcm>(&self.key, nonce, data, &self.aad)
+            }
+            AeadAlgorithm::ChaCha20Poly1305 => {
+                use chacha20poly1305::aead::{generic_array::GenericArray, Aead, NewAead, Payload};
+                ChaCha20Poly1305::new(GenericArray::from_slice(&self.key))
+                    .decrypt(GenericArray::from_slice(nonce), Payload { msg: data, aad: &self.aad })
+                    .map_err(|_| ())
+            }
+        };
+        result
+            .map(SecretBuf)
+            .map_err(|_| Error::decrypt_data_error("authentication failed"))
+    }
+}
+
+fn aes_gcm_encrypt<C>(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8]) -> Result<Vec<u8>, ()>
+where
+    C: aes_gcm::aead::NewAead + aes_gcm::aead::Aead,
+{
+    use aes_gcm::aead::{generic_array::GenericArray, Payload};
+    C::new(GenericArray::from_slice(key))
+        .encrypt(GenericArray::from_slice(nonce), Payload { msg: data, aad })
+        .map_err(|_| ())
+}
+
+fn aes_gcm_decrypt<C>(key: &[u8], nonce: &[u8], data: &[u8], aad: &[u8]) -> Result<Vec<u8>, ()>
+where
+    C: aes_gcm::aead::NewAead + aes_gcm::aead::Aead,
+{
+    use aes_gcm::aead::{generic_array::GenericArray, Payload};
+    C::new(GenericArray::from_slice(key))
+        .decrypt(GenericArray::from_slice(nonce), Payload { msg: data, aad })
+        .map_err(|_| ())
+}
+
+#[async_trait::async_trait]
+impl EncryptionBox for AeadEncryptionBox {
+    async fn get_info(&self, _context: Arc<ClientContext>) -> ClientResult<EncryptionBoxInfo> {
+        let algorithm = match self.cipher.algorithm {
+            AeadAlgorithm::AesGcm => "AES-GCM",
+            AeadAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+        };
+        Ok(EncryptionBoxInfo {
+            algorithm: Some(algorithm.to_owned()),
+            hdpath: self.hdpath.clone(),
+            public: None,
+            options: Some(json!({ "aad": base64::encode(&self.cipher.aad) })),
+        })
+    }
+
+    async fn encrypt(&self, _context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        let data = SecretBuf(base64_decode(data)?);
+        let mut nonce = vec![0u8; NONCE_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let mut encrypted = self.cipher.seal(&nonce, &data)?;
+        nonce.append(&mut encrypted);
+        Ok(base64::encode(&nonce))
+    }
+
+    async fn decrypt(&self, _context: Arc<ClientContext>, data: &String) -> ClientResult<String> {
+        let data = base64_decode(data)?;
+        if data.len() < NONCE_SIZE {
+            return Err(Error::decrypt_data_error("encrypted data is too short"));
+        }
+        let decrypted = self.cipher.open(&data[..NONCE_SIZE], &data[NONCE_SIZE..])?;
+        Ok(base64::encode(&decrypted.0))
+    }
+
+    async fn create_encryption_stream(
+        &self,
+        _context: Arc<ClientContext>,
+        chunk_size: u32,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        Ok(Box::new(AeadStreamEncryptor::new(self.cipher.clone(), chunk_size)?))
+    }
+
+    async fn create_decryption_stream(
+        &self,
+        _context: Arc<ClientContext>,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        Ok(Box::new(AeadStreamDecryptor::new(self.cipher.clone())))
+    }
+}
+
+// Stream format is the header followed by the encrypted chunks:
+//   header = version (1 byte) || chunk size (4 bytes BE) || nonce prefix (7 bytes)
+//   chunk  = encrypted data (chunk size bytes, the last chunk can be shorter) || tag (16 bytes)
+// Each chunk is encrypted with nonce = nonce prefix || chunk index (4 bytes BE) || last chunk flag,
+// so reordered, truncated or extended streams fail to decrypt.
+// Nonce prefix is random, so streams encrypted with one key are distinct with the probability
+// of about n^2 / 2^57 for n streams. This limits one key to about 2^20 streams.
+
+fn stream_chunk_nonce(prefix: &[u8], index: u32, last: bool) -> Vec<u8> {
+    let mut nonce = Vec::with_capacity(NONCE_SIZE);
+    nonce.extend_from_slice(prefix);
+    nonce.extend_from_slice(&index.to_be_bytes());
+    nonce.push(last as u8);
+    nonce
+}
+
+fn next_chunk_index(index: u32) -> ClientResult<u32> {
+    index.checked_add(1).ok_or(Error::encrypt_data_error("stream is too long"))
+}
+
+struct AeadStreamEncryptor {
+    cipher: AeadCipher,
+    chunk_size: usize,
+    nonce_prefix: Vec<u8>,
+    header_written: bool,
+    index: u32,
+    buffer: SecretBuf,
+}
+
+impl AeadStreamEncryptor {
+    fn new(cipher: AeadCipher, chunk_size: u32) -> ClientResult<Self> {
+        if chunk_size == 0 {
+            return Err(Error::encrypt_data_error("chunk size can not be zero"));
+        }
+        if chunk_size as usize > MAX_STREAM_CHUNK_SIZE {
+            return Err(Error::encrypt_data_error(format!(
+                "chunk size can not exceed {} bytes",
+                MAX_STREAM_CHUNK_SIZE
+            )));
+        }
+        let mut nonce_prefix = vec![0u8; STREAM_NONCE_PREFIX_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce_prefix);
+        Ok(Self {
+            cipher,
+            chunk_size: chunk_size as usize,
+            nonce_prefix,
+            header_written: false,
+            index: 0,
+            buffer: SecretBuf(Vec::new()),
+        })
+    }
+
+    fn header(&mut self, output: &mut Vec<u8>) {
+        if !self.header_written {
+            output.push(STREAM_VERSION);
+            output.extend_from_slice(&(self.chunk_size as u32).to_be_bytes());
+            output.extend_from_slice(&self.nonce_prefix);
+            self.header_written = true;
+        }
+    }
+
+    fn seal_chunk(&mut self, chunk: &[u8], last: bool, output: &mut Vec<u8>) -> ClientResult<()> {
+        let nonce = stream_chunk_nonce(&self.nonce_prefix, self.index, last);
+        output.append(&mut self.cipher.seal(&nonce, chunk)?);
+        self.index = next_chunk_index(self.index)?;
+        Ok(())
+    }
+}
+
+impl EncryptionStream for AeadStreamEncryptor {
+    fn update(&mut self, data: &[u8]) -> ClientResult<Vec<u8>> {
+        let mut output = Vec::new();
+        self.header(&mut output);
+        self.buffer.0.extend_from_slice(data);
+        // the last chunk is kept in the buffer until `finish`, because it is sealed
+        // with the last chunk flag
+        let mut offset = 0;
+        while self.buffer.len() - offset > self.chunk_size {
+            let chunk = SecretBuf(self.buffer[offset..offset + self.chunk_size].to_vec());
+            self.seal_chunk(&chunk, false, &mut output)?;
+            offset += self.chunk_size;
+        }
+        self.buffer = SecretBuf(self.buffer[offset..].to_vec());
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> ClientResult<Vec<u8>> {
+        let mut output = Vec::new();
+        self.header(&mut output);
+        let chunk = std::mem::take(&mut self.buffer);
+        self.seal_chunk(&chunk, true, &mut output)?;
+        Ok(output)
+    }
+}
+
+struct AeadStreamDecryptor {
+    cipher: AeadCipher,
+    chunk_size: usize,
+    nonce_prefix: Vec<u8>,
+    index: u32,
+    buffer: Vec<u8>,
+}
+
+impl AeadStreamDecryptor {
+    fn new(cipher: AeadCipher) -> Self {
+        Self {
+            cipher,
+            chunk_size: 0,
+            nonce_prefix: Vec::new(),
+            index: 0,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn read_header(&mut self) -> ClientResult<bool> {
+        if self.chunk_size != 0 {
+            return Ok(true);
+        }
+        if self.buffer.len() < STREAM_HEADER_SIZE {
+            return Ok(false);
+        }
+        if self.buffer[0] != STREAM_VERSION {
+            return Err(Error::decrypt_data_error(format!(
+                "unsupported stream version {}",
+                self.buffer[0]
+            )));
+        }
+        let mut chunk_size = [0u8; 4];
+        chunk_size.copy_from_slice(&self.buffer[1..5]);
+        let chunk_size = u32::from_be_bytes(chunk_size) as usize;
+        if chunk_size == 0 || chunk_size > MAX_STREAM_CHUNK_SIZE {
+            return Err(Error::decrypt_data_error("invalid stream header"));
+        }
+        self.chunk_size = chunk_size;
+        self.nonce_prefix = self.buffer[5..STREAM_HEADER_SIZE].to_vec();
+        self.buffer.drain(..STREAM_HEADER_SIZE);
+        Ok(true)
+    }
+
+    fn open_chunk(&mut self, chunk: &[u8], last: bool, output: &mut Vec<u8>) -> ClientResult<()> {
+        let nonce = stream_chunk_nonce(&self.nonce_prefix, self.index, last);
+        output.extend_from_slice(&self.cipher.open(&nonce, chunk)?);
+        self.index = next_chunk_index(self.index)?;
+        Ok(())
+    }
+}
+
+impl EncryptionStream for AeadStreamDecryptor {
+    fn update(&mut self, data: &[u8]) -> ClientResult<Vec<u8>> {
+        let mut output = Vec::new();
+        self.buffer.extend_from_slice(data);
+        if !self.read_header()? {
+            return Ok(output);
+        }
+        let encrypted_chunk_size = self.chunk_size + TAG_SIZE;
+        let mut offset = 0;
+        while self.buffer.len() - offset > encrypted_chunk_size {
+            let chunk = self.buffer[offset..offset + encrypted_chunk_size].to_vec();
+            self.open_chunk(&chunk, false, &mut output)?;
+            offset += encrypted_chunk_size;
+        }
+        self.buffer.drain(..offset);
+        Ok(output)
+    }
+
+    fn finish(&mut self) -> ClientResult<Vec<u8>> {
+        let mut output = Vec::new();
+        if !self.read_header()? || self.buffer.len() < TAG_SIZE {
+            return Err(Error::decrypt_data_error("encrypted stream is truncated"));
+        }
+        let chunk = std::mem::take(&mut self.buffer);
+        self.open_chunk(&chunk, true, &mut output)?;
+        Ok(output)
+    }
+}
@@ -20,6 +20,7 @@ use crate::client::ClientContext;
 use crate::crypto::{CryptoBoxHandle, Error};
 use crate::error::ClientResult;
 
+pub(crate) mod aead;
 pub(crate) mod aes;
 pub(crate) mod chacha20;
 pub(crate) mod nacl_box;
@@ -47,6 +48,19 @@ pub struct EncryptionBoxInfo {
     pub public: Option<Value>,
 }
 
+/// Stateful encryptor or decryptor of the data stream.
+pub trait EncryptionStream: Send {
+    /// Processes the next part of the data. Returns the data processed so far.
+    fn update(&mut self, data: &[u8]) -> ClientResult<Vec<u8>>;
+    /// Processes the rest of the data.
+    fn finish(&mut self) -> ClientResult<Vec<u8>>;
+    /// Crypto box the stream key is derived from. Such streams are removed
+    /// when the crypto box secret is dropped.
+    fn crypto_box(&self) -> Option<CryptoBoxHandle> {
+        None
+    }
+}
+
 #[async_trait::async_trait]
 pub trait EncryptionBox: Send + Sync {
     /// Gets encryption box information
@@ -55,6 +69,22 @@ pub trait EncryptionBox: Send + Sync {
     async fn encrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String>;
     /// Decrypts data
     async fn decrypt(&self, context: Arc<ClientContext>, data: &String) -> ClientResult<String>;
+    /// Creates stream encryptor splitting the data into chunks of `chunk_size` bytes.
+    /// Not supported by default.
+    async fn create_encryption_stream(
+        &self,
+        _context: Arc<ClientContext>,
+        _chunk_size: u32,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        Err(Error::encryption_stream_not_supported())
+    }
+    /// Creates stream decryptor. Not supported by default.
+    async fn create_decryption_stream(
+        &self,
+        _context: Arc<ClientContext>,
+    ) -> ClientResult<Box<dyn EncryptionStream>> {
+        Err(Error::encryption_stream_not_supported())
+    }
     /// Zeroize all secret data
     async fn drop_secret(&self, _crypto_box_handle: CryptoBoxHandle) {
         // Not implemented by default, but must be implemented for encryption boxes that created
@@ -189,6 +219,179 @@ pub async fn encryption_box_decrypt(
     })
 }
 
+//------------------------------------------------------------------------------ encryption streams
+
+const DEFAULT_STREAM_CHUNK_SIZE: u32 = 65536;
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct EncryptionStreamHandle(pub u32);
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct RegisteredEncryptionStream {
+    /// Handle of the encryption stream.
+    pub stream: EncryptionStreamHandle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxEncryptInit {
+    /// Encryption box handle
+    pub encryption_box: EncryptionBoxHandle,
+    /// Size of the data chunk encrypted at once. Default is 65536 bytes, maximum is 16 MiB.
+    pub chunk_size: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxDecryptInit {
+    /// Encryption box handle
+    pub encryption_box: EncryptionBoxHandle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionStreamUpdate {
+    /// Encryption stream handle
+    pub stream: EncryptionStreamHandle,
+    /// Next part of the data, encoded in Base64
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionStreamUpdate {
+    /// Data processed so far, encoded in Base64. Can be empty.
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionStreamFinish {
+    /// The rest of the processed data, encoded in Base64.
+    pub data: String,
+}
+
+fn register_encryption_stream(
+    context: &ClientContext,
+    stream: Box<dyn EncryptionStream>,
+) -> RegisteredEncryptionStream {
+    let id = context.get_next_id();
+    context.boxes.encryption_streams.insert(id, std::sync::Mutex::new(stream));
+
+    RegisteredEncryptionStream {
+        stream: EncryptionStreamHandle(id),
+    }
+}
+
+fn encryption_stream_update(
+    context: &ClientContext,
+    params: ParamsOfEncryptionStreamUpdate,
+) -> ClientResult<ResultOfEncryptionStreamUpdate> {
+    let data = crate::encoding::base64_decode(&params.data)?;
+    let result = context.boxes.encryption_streams
+        .get(&params.stream.0)
+        .ok_or(Error::encryption_stream_not_registered(params.stream.0))?
+        .val()
+        .lock()
+        .unwrap()
+        .update(&data);
+    match result {
+        Ok(data) => Ok(ResultOfEncryptionStreamUpdate { data: base64::encode(&data) }),
+        Err(err) => {
+            context.boxes.encryption_streams.remove(&params.stream.0);
+            Err(err)
+        }
+    }
+}
+
+fn encryption_stream_finish(
+    context: &ClientContext,
+    params: RegisteredEncryptionStream,
+) -> ClientResult<ResultOfEncryptionStreamFinish> {
+    let stream = context.boxes.encryption_streams
+        .remove(&params.stream.0)
+        .ok_or(Error::encryption_stream_not_registered(params.stream.0))?;
+    let data = stream.val().lock().unwrap().finish()?;
+
+    Ok(ResultOfEncryptionStreamFinish { data: base64::encode(&data) })
+}
+
+/// Starts stream encryption using given encryption box
+///
+/// The data is passed to `encryption_box_encrypt_update` in parts of any size and
+/// encrypted in chunks, so it can be decrypted with `encryption_box_decrypt_update`
+/// without loading the whole data into memory. Stream is released by `encryption_box_encrypt_finish`
+/// or when an error occurs.
+///
+/// Streams started with an encryption box derived from a crypto box are released when
+/// the crypto box secret is dropped by `clear_crypto_box_secret_cache` or `remove_crypto_box`.
+///
+/// Each stream uses a random 7-byte nonce prefix, so the probability of the nonce reuse
+/// for `n` streams encrypted with one key is about `n^2 / 2^57`. Do not encrypt more than
+/// 2^20 streams with one key (the probability is about 2^-17 then).
+#[api_function]
+pub async fn encryption_box_encrypt_init(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxEncryptInit,
+) -> ClientResult<RegisteredEncryptionStream> {
+    let stream = get_registered_encryption_box(&context, &params.encryption_box)?
+        .val()
+        .create_encryption_stream(
+            Arc::clone(&context),
+            params.chunk_size.unwrap_or(DEFAULT_STREAM_CHUNK_SIZE),
+        )
+        .await?;
+
+    Ok(register_encryption_stream(&context, stream))
+}
+
+/// Encrypts the next part of the data. Returns encrypted chunks completed so far.
+#[api_function]
+pub fn encryption_box_encrypt_update(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionStreamUpdate,
+) -> ClientResult<ResultOfEncryptionStreamUpdate> {
+    encryption_stream_update(&context, params)
+}
+
+/// Encrypts the rest of the data and releases the stream.
+#[api_function]
+pub fn encryption_box_encrypt_finish(
+    context: Arc<ClientContext>,
+    params: RegisteredEncryptionStream,
+) -> ClientResult<ResultOfEncryptionStreamFinish> {
+    encryption_stream_finish(&context, params)
+}
+
+/// Starts stream decryption of the data encrypted with `encryption_box_encrypt_init`.
+#[api_function]
+pub async fn encryption_box_decrypt_init(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxDecryptInit,
+) -> ClientResult<RegisteredEncryptionStream> {
+    let stream = get_registered_encryption_box(&context, &params.encryption_box)?
+        .val()
+        .create_decryption_stream(Arc::clone(&context))
+        .await?;
+
+    Ok(register_encryption_stream(&context, stream))
+}
+
+/// Decrypts the next part of the data. Returns the data of the chunks decrypted so far.
+#[api_function]
+pub fn encryption_box_decrypt_update(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionStreamUpdate,
+) -> ClientResult<ResultOfEncryptionStreamUpdate> {
+    encryption_stream_update(&context, params)
+}
+
+/// Decrypts the rest of the data and releases the stream.
+///
+/// Fails if the encrypted data is truncated.
+#[api_function]
+pub fn encryption_box_decrypt_finish(
+    context: Arc<ClientContext>,
+    params: RegisteredEncryptionStream,
+) -> ClientResult<ResultOfEncryptionStreamFinish> {
+    encryption_stream_finish(&context, params)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
 pub enum CipherMode {
     CBC,
@@ -211,6 +414,8 @@ pub enum EncryptionAlgorithm {
     ChaCha20(chacha20::ChaCha20ParamsEB),
     NaclBox(nacl_box::NaclBoxParamsEB),
     NaclSecretBox(nacl_secret_box::NaclSecretBoxParamsEB),
+    AesGcm(aead::AesGcmParamsEB),
+    ChaCha20Poly1305(aead::ChaCha20Poly1305ParamsEB),
 }
 
 impl Default for EncryptionAlgorithm {
@@ -239,9 +444,15 @@ pub async fn create_encryption_box(
             register_encryption_box(context, chacha20::ChaCha20EncryptionBox::new(params, None)?).await,
 
         EncryptionAlgorithm::NaclBox(params) =>
-            register_encryption_box(context, nacl_box::NaclEncryptionBox::new(params, None)).await,
+            register_encryption_box(context, nacl_box::NaclEncryptionBox::new(params, None)?).await,
 
         EncryptionAlgorithm::NaclSecretBox(params) =>
             register_encryption_box(context, nacl_secret_box::NaclSecretEncryptionBox::new(params, None)).await,
+
+        EncryptionAlgorithm::AesGcm(params) =>
+            register_encryption_box(context, aead::AeadEncryptionBox::aes_gcm(params, None)?).await,
+
+        EncryptionAlgorithm::ChaCha20Poly1305(params) =>
+            register_encryption_box(context, aead::AeadEncryptionBox::chacha20_poly1305(params, None)?).await,
     }
 }
@@ -4,6 +4,7 @@ use zeroize::Zeroize;
 
 use crate::ClientContext;
 use crate::crypto::{EncryptionBox, EncryptionBoxInfo, nacl_box, nacl_box_keypair_from_secret_key, nacl_box_open, ParamsOfNaclBox, ParamsOfNaclBoxKeyPairFromSecret, ParamsOfNaclBoxOpen};
+use crate::crypto::keys::{ed25519_public_to_x25519, ed25519_secret_to_x25519};
 use crate::error::ClientResult;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq, Zeroize, ZeroizeOnDrop)]
@@ -14,6 +15,11 @@ pub struct NaclBoxParamsEB {
     pub secret: String,
     /// 96-bit nonce. Must be encoded with `hex`.
     pub nonce: String,
+    /// `their_public` and `secret` are ed25519 keys. Default is `false`.
+    ///
+    /// Keys are converted to X25519 keys, so the data can be encrypted to the owner
+    /// of an ed25519 key pair, for example using the account owner's public key.
+    pub ed25519_keys: Option<bool>,
 }
 
 #[derive(Debug)]
@@ -23,8 +29,13 @@ pub struct NaclEncryptionBox {
 }
 
 impl NaclEncryptionBox {
-    pub fn new(params: NaclBoxParamsEB, hdpath: Option<String>) -> Self {
-        Self { params, hdpath }
+    pub fn new(mut params: NaclBoxParamsEB, hdpath: Option<String>) -> ClientResult<Self> {
+        if params.ed25519_keys.unwrap_or(false) {
+            params.their_public = hex::encode(ed25519_public_to_x25519(&params.their_public)?);
+            params.secret = hex::encode(ed25519_secret_to_x25519(&params.secret)?.0);
+            params.ed25519_keys = None;
+        }
+        Ok(Self { params, hdpath })
     }
 }
 
@@ -12,5 +12,6 @@
 */
 
 pub(crate) mod signing_box;
+pub(crate) mod policy_signing_box;
 pub(crate) mod encryption_box;
 pub(crate) mod crypto_box;
@@ -0,0 +1,334 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use lockfree::map::Removed;
+use num_bigint::BigInt;
+use serde_json::Value;
+use ton_abi::contract::ABI_VERSION_2_3;
+use ton_abi::token::Detokenizer;
+use ton_block::{MsgAddressInt, Serializable};
+use ton_sdk::AbiContract;
+use ton_types::{BuilderData, IBitstring, SliceData};
+
+use crate::abi::Abi;
+use crate::client::ClientContext;
+use crate::crypto::boxes::signing_box::{
+    RegisteredSigningBox, SigningBox, SigningBoxHandle, SigningBoxMessage,
+};
+use crate::crypto::{CryptoBoxHandle, Error};
+use crate::encoding::{account_decode, decode_abi_bigint};
+use crate::error::ClientResult;
+
+const DEFAULT_VALUE_PARAM: &str = "value";
+
+/// Limit of signatures made by the signing box.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct SigningBoxRateLimit {
+    /// Maximum number of signatures made within the period.
+    pub max_signatures: u32,
+    /// Period length in seconds.
+    pub period: u32,
+}
+
+/// Restrictions checked before the message is signed.
+///
+/// Omitted restrictions are not checked.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct SigningBoxPolicy {
+    /// Allowed destination addresses of the messages.
+    pub allowed_addresses: Option<Vec<String>>,
+    /// Allowed ABI function ids.
+    pub allowed_function_ids: Option<Vec<u32>>,
+    /// Maximum attached value in nanotokens. Decimal or `0x` prefixed hex string.
+    pub max_value: Option<String>,
+    /// Name of the function input holding the attached value. Default is `value`.
+    ///
+    /// Functions without this input are treated as attaching no value.
+    pub value_param: Option<String>,
+    /// Limit of signatures per period.
+    pub rate_limit: Option<SigningBoxRateLimit>,
+}
+
+pub(crate) struct PolicySigningBox {
+    /// Wrapped signing box. It is removed from the registered signing boxes,
+    /// so it can not be used bypassing the policy
+    signing_box: Removed<u32, Box<dyn SigningBox>>,
+    abi: Arc<AbiContract>,
+    allowed_addresses: Option<Vec<MsgAddressInt>>,
+    allowed_function_ids: Option<Vec<u32>>,
+    max_value: Option<BigInt>,
+    value_param: String,
+    rate_limit: Option<SigningBoxRateLimit>,
+    /// Times of the signatures made within the rate limit period, in ms
+    signed_at: Mutex<VecDeque<u64>>,
+}
+
+impl PolicySigningBox {
+    /// Takes the wrapped signing box from the registered ones if the policy is valid
+    fn new(
+        context: &ClientContext,
+        signing_box: &SigningBoxHandle,
+        abi: Arc<AbiContract>,
+        policy: SigningBoxPolicy,
+    ) -> ClientResult<Self> {
+        let allowed_addresses = policy.allowed_addresses
+            .map(|addresses| {
+                addresses.iter()
+                    .map(|address| account_decode(address))
+                    .collect::<ClientResult<Vec<_>>>()
+            })
+            .transpose()
+            .map_err(|err| Error::invalid_signing_box_policy(err))?;
+        let max_value = policy.max_value
+            .map(|value| decode_abi_bigint(&value))
+            .transpose()
+            .map_err(|err| Error::invalid_signing_box_policy(err))?;
+        if let Some(rate_limit) = &policy.rate_limit {
+            if rate_limit.period == 0 {
+                return Err(Error::invalid_signing_box_policy("rate limit period can not be zero"));
+            }
+        }
+        let signing_box = context.boxes.signing_boxes
+            .remove(&signing_box.0)
+            .ok_or(Error::signing_box_not_registered(signing_box.0))?;
+        Ok(Self {
+            signing_box,
+            abi,
+            allowed_addresses,
+            allowed_function_ids: policy.allowed_function_ids,
+            max_value,
+            value_param: policy.value_param.unwrap_or(DEFAULT_VALUE_PARAM.to_string()),
+            rate_limit: policy.rate_limit,
+            signed_at: Mutex::new(VecDeque::new()),
+        })
+    }
+
+    fn data_to_sign(&self, message: &SigningBoxMessage) -> ClientResult<Vec<u8>> {
+        let body = BuilderData::from_slice(&SliceData::from(message.unsigned_body.clone()));
+        let data = if self.abi.version() >= &ABI_VERSION_2_3 {
+            let mut data = message.dst.clone()
+                .unwrap_or_default()
+                .write_to_new_cell()
+                .map_err(|err| Error::signing_box_policy_violation(err))?;
+            data.append_builder(&body)
+                .map_err(|err| Error::signing_box_policy_violation(err))?;
+            data
+        } else {
+            body
+        };
+        Ok(data
+            .into_cell()
+            .map_err(|err| Error::signing_box_policy_violation(err))?
+            .repr_hash()
+            .as_slice()
+            .to_vec())
+    }
+
+    fn check_address(&self, message: &SigningBoxMessage) -> ClientResult<()> {
+        if let Some(allowed) = &self.allowed_addresses {
+            match &message.dst {
+                Some(dst) if allowed.contains(dst) => {}
+                Some(dst) => {
+                    return Err(Error::signing_box_policy_violation(format!(
+                        "destination address {} is not allowed",
+                        dst
+                    )))
+                }
+                None => {
+                    return Err(Error::signing_box_policy_violation(
+                        "destination address is unknown",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_function_call(&self, message: &SigningBoxMessage) -> ClientResult<()> {
+        // Signed body starts with the signature flag, so the flag is restored to decode
+        // the body as unsigned one
+        let mut body = BuilderData::new();
+        body.append_bit_zero()
+            .and_then(|body| {
+                body.append_builder(&BuilderData::from_slice(
+                    &SliceData::from(message.unsigned_body.clone()),
+                ))
+            })
+            .map_err(|err| Error::signing_box_policy_violation(err))?;
+        let body = body
+            .into_cell()
+            .map_err(|err| Error::signing_box_policy_violation(err))?;
+        let decoded = self.abi
+            .decode_input(body.into(), false, false)
+            .map_err(|err| {
+                Error::signing_box_policy_violation(format!(
+                    "message body does not match the ABI: {}",
+                    err
+                ))
+            })?;
+
+        if let Some(allowed) = &self.allowed_function_ids {
+            let function_id = self.abi
+                .function(&decoded.function_name)
+                .map_err(|err| Error::signing_box_policy_violation(err))?
+                .get_input_id();
+            if !allowed.contains(&function_id) {
+                return Err(Error::signing_box_policy_violation(format!(
+                    "function `{}` (id 0x{:08x}) is not allowed",
+                    decoded.function_name, function_id
+                )));
+            }
+        }
+
+        if let Some(max_value) = &self.max_value {
+            let input = Detokenizer::detokenize_to_json_value(&decoded.tokens)
+                .map_err(|err| Error::signing_box_policy_violation(err))?;
+            let value = match &input[&self.value_param] {
+                Value::Null => None,
+                Value::String(value) => Some(decode_abi_bigint(value)?),
+                Value::Number(value) => Some(decode_abi_bigint(&value.to_string())?),
+                _ => {
+                    return Err(Error::signing_box_policy_violation(format!(
+                        "function input `{}` is not a number",
+                        self.value_param
+                    )))
+                }
+            };
+            if let Some(value) = value {
+                if &value > max_value {
+                    return Err(Error::signing_box_policy_violation(format!(
+                        "attached value {} exceeds the maximum {}",
+                        value, max_value
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the rate limit and reserves the signature slot
+    fn reserve_signature(&self, now: u64) -> ClientResult<()> {
+        if let Some(rate_limit) = &self.rate_limit {
+            let mut signed_at = self.signed_at.lock().unwrap();
+            let period = rate_limit.period as u64 * 1000;
+            while signed_at.front().map(|time| *time + period <= now).unwrap_or(false) {
+                signed_at.pop_front();
+            }
+            if signed_at.len() >= rate_limit.max_signatures as usize {
+                return Err(Error::signing_box_policy_violation(format!(
+                    "rate limit of {} signatures per {} seconds is exceeded",
+                    rate_limit.max_signatures, rate_limit.period
+                )));
+            }
+            signed_at.push_back(now);
+        }
+        Ok(())
+    }
+
+    fn release_signature(&self, now: u64) {
+        if self.rate_limit.is_some() {
+            let mut signed_at = self.signed_at.lock().unwrap();
+            if let Some(index) = signed_at.iter().position(|time| *time == now) {
+                signed_at.remove(index);
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBox for PolicySigningBox {
+    async fn get_public_key(&self, context: Arc<ClientContext>) -> ClientResult<Vec<u8>> {
+        self.signing_box.val().get_public_key(context).await
+    }
+
+    async fn sign(&self, _context: Arc<ClientContext>, _unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        Err(Error::signing_box_policy_violation(
+            "only ABI messages can be signed with this signing box",
+        ))
+    }
+
+    async fn sign_message(
+        &self,
+        context: Arc<ClientContext>,
+        message: &SigningBoxMessage,
+        unsigned: &[u8],
+    ) -> ClientResult<Vec<u8>> {
+        if self.data_to_sign(message)? != unsigned {
+            return Err(Error::signing_box_policy_violation(
+                "data to sign does not match the message",
+            ));
+        }
+        self.check_address(message)?;
+        self.check_function_call(message)?;
+
+        let now = context.env.now_ms();
+        self.reserve_signature(now)?;
+        let result = self.signing_box.val()
+            .sign_message(Arc::clone(&context), message, unsigned)
+            .await;
+        if result.is_err() {
+            self.release_signature(now);
+        }
+        result
+    }
+
+    fn check_abi_signing(&self) -> ClientResult<()> {
+        self.signing_box.val().check_abi_signing()
+    }
+
+    async fn drop_secret(&self, crypto_box_handle: CryptoBoxHandle) {
+        self.signing_box.val().drop_secret(crypto_box_handle).await
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct ParamsOfGetPolicySigningBox {
+    /// Signing box that makes the signatures. It is unregistered and owned by the created
+    /// signing box.
+    pub signing_box: SigningBoxHandle,
+    /// ABI used to decode the messages being signed.
+    pub abi: Abi,
+    /// Signing policy.
+    pub policy: SigningBoxPolicy,
+}
+
+/// Creates a signing box that enforces the policy before signing with another signing box.
+///
+/// The box signs only ABI messages: message body is decoded with the provided ABI and
+/// checked against the policy. Arbitrary data can not be signed with `signing_box_sign`.
+/// Rate limit counts successful signatures only.
+///
+/// The wrapped signing box is moved into the created one: its handle becomes invalid,
+/// so the policy can not be bypassed by signing with it directly. The wrapped box is released
+/// when the created signing box is removed.
+#[api_function]
+pub async fn get_policy_signing_box(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetPolicySigningBox,
+) -> ClientResult<RegisteredSigningBox> {
+    let signing_box = PolicySigningBox::new(
+        &context,
+        &params.signing_box,
+        params.abi.abi(&context)?,
+        params.policy,
+    )?;
+    let id = context.get_next_id();
+    context.boxes.signing_boxes.insert(id, Box::new(signing_box));
+
+    Ok(RegisteredSigningBox {
+        handle: SigningBoxHandle(id),
+    })
+}
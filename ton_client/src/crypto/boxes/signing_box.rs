@@ -16,7 +16,10 @@ use std::sync::Arc;
 use crate::client::ClientContext;
 use crate::crypto::{CryptoBoxHandle, Error};
 use crate::crypto::KeyPair;
+use crate::crypto::secp256k1::{decode_secp256k1_secret, secp256k1_sign_hash};
 use crate::error::ClientResult;
+use ton_block::MsgAddressInt;
+use ton_types::Cell;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct SigningBoxHandle(pub u32);
@@ -27,12 +30,34 @@ impl From<u32> for SigningBoxHandle {
     }
 }
 
+/// ABI message that is being signed.
+pub struct SigningBoxMessage {
+    /// Destination address of the message.
+    pub dst: Option<MsgAddressInt>,
+    /// Message body without signature.
+    pub unsigned_body: Cell,
+}
+
 #[async_trait::async_trait]
 pub trait SigningBox: Send + Sync {
     /// Get public key of key pair
     async fn get_public_key(&self, context: Arc<ClientContext>) -> ClientResult<Vec<u8>>;
     /// Sign data with key pair
     async fn sign(&self, context: Arc<ClientContext>, unsigned: &[u8]) -> ClientResult<Vec<u8>>;
+    /// Sign data of the ABI message. `unsigned` is the data to sign calculated from `message`.
+    /// By default the message is ignored and `unsigned` is signed as is.
+    async fn sign_message(
+        &self,
+        context: Arc<ClientContext>,
+        _message: &SigningBoxMessage,
+        unsigned: &[u8],
+    ) -> ClientResult<Vec<u8>> {
+        self.sign(context, unsigned).await
+    }
+    /// Check that the box makes Ed25519 signatures required by ABI messages
+    fn check_abi_signing(&self) -> ClientResult<()> {
+        Ok(())
+    }
     /// Zeroize all secret data
     async fn drop_secret(&self, _crypto_box_handle: CryptoBoxHandle) {
         // Not implemented by default, but must be implemented for signing boxes that created from
@@ -67,6 +92,36 @@ impl SigningBox for KeysSigningBox {
     }
 }
 
+pub(crate) struct Secp256k1SigningBox {
+    secret: libsecp256k1::SecretKey,
+}
+
+impl Secp256k1SigningBox {
+    pub fn new(secret: libsecp256k1::SecretKey) -> Self {
+        Self { secret }
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBox for Secp256k1SigningBox {
+    async fn get_public_key(&self, _context: Arc<ClientContext>) -> ClientResult<Vec<u8>> {
+        Ok(libsecp256k1::PublicKey::from_secret_key(&self.secret).serialize_compressed().to_vec())
+    }
+
+    async fn sign(&self, _context: Arc<ClientContext>, unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        let (signature, recovery_id) = secp256k1_sign_hash(unsigned, &self.secret)?;
+        let mut result = signature.to_vec();
+        result.push(recovery_id);
+        Ok(result)
+    }
+
+    fn check_abi_signing(&self) -> ClientResult<()> {
+        Err(Error::signing_box_not_supported_by_abi(
+            "ABI messages require Ed25519 signature, but secp256k1 signing box is used",
+        ))
+    }
+}
+
 /// Creates a default signing box implementation.
 #[api_function]
 pub async fn get_signing_box(
@@ -82,6 +137,31 @@ pub async fn get_signing_box(
     })
 }
 
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct ParamsOfGetSecp256k1SigningBox {
+    /// Secret key (32 bytes) encoded in `hex`.
+    pub secret: String,
+}
+
+/// Creates a secp256k1 signing box.
+///
+/// The signing box signs 32-byte hashes. Signature is `r || s || v` (65 bytes)
+/// where `v` is the recovery id (0 or 1). Public key is returned in the compressed form.
+/// The box can not be used as the ABI message signer.
+#[api_function]
+pub async fn get_secp256k1_signing_box(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfGetSecp256k1SigningBox,
+) -> ClientResult<RegisteredSigningBox> {
+    let signing_box = Secp256k1SigningBox::new(decode_secp256k1_secret(&params.secret)?);
+    let id = context.get_next_id();
+    context.boxes.signing_boxes.insert(id, Box::new(signing_box));
+
+    Ok(RegisteredSigningBox {
+        handle: SigningBoxHandle(id),
+    })
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct RegisteredSigningBox {
     /// Handle of the signing box.
@@ -157,6 +237,33 @@ pub async fn signing_box_sign(
     })
 }
 
+/// Checks that the registered signing box can sign ABI messages.
+pub(crate) fn signing_box_check_abi_signing(
+    context: &ClientContext,
+    handle: &SigningBoxHandle,
+) -> ClientResult<()> {
+    context.boxes.signing_boxes
+        .get(&handle.0)
+        .ok_or(Error::signing_box_not_registered(handle.0))?
+        .1
+        .check_abi_signing()
+}
+
+/// Signs data of the ABI message with the registered signing box.
+pub(crate) async fn signing_box_sign_message(
+    context: Arc<ClientContext>,
+    handle: &SigningBoxHandle,
+    message: &SigningBoxMessage,
+    unsigned: &[u8],
+) -> ClientResult<Vec<u8>> {
+    let signing_box = context.boxes.signing_boxes
+        .get(&handle.0)
+        .ok_or(Error::signing_box_not_registered(handle.0))?;
+
+    signing_box.1.check_abi_signing()?;
+    signing_box.1.sign_message(Arc::clone(&context), message, unsigned).await
+}
+
 /// Removes signing box from SDK.
 #[api_function]
 pub fn remove_signing_box(
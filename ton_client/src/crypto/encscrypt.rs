@@ -11,6 +11,7 @@
 * limitations under the License.
 */
 
+extern crate argon2;
 extern crate scrypt;
 use crate::client::ClientContext;
 use crate::crypto;
@@ -77,3 +78,80 @@ pub fn scrypt(
         key: hex::encode(&key),
     })
 }
+
+//---------------------------------------------------------------------------------------- argon2id
+
+pub(crate) fn argon2id_derive(
+    password: &[u8],
+    salt: &[u8],
+    memory_cost: u32,
+    iterations: u32,
+    parallelism: u32,
+    key: &mut [u8],
+) -> ClientResult<()> {
+    let params = argon2::Params::new(memory_cost, iterations, parallelism, Some(key.len()))
+        .map_err(|err| crypto::Error::argon2_failed(err))?;
+    argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+        .hash_password_into(password, salt, key)
+        .map_err(|err| crypto::Error::argon2_failed(err))
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfArgon2id {
+    /// The password bytes to be hashed.
+    /// Must be encoded with `base64`.
+    pub password: String,
+    /// Salt bytes. Must be at least 8 bytes long.
+    /// Must be encoded with `base64`.
+    pub salt: String,
+    /// Memory size in KiB.
+    pub memory_cost: u32,
+    /// Number of iterations.
+    pub iterations: u32,
+    /// Degree of parallelism (number of lanes).
+    pub parallelism: u32,
+    /// Intended output length in octets of the derived key.
+    pub dk_len: u32,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfArgon2id {
+    /// Derived key. Encoded with `hex`.
+    pub key: String,
+}
+
+/// Perform `argon2id` key derivation
+///
+/// Derives key from `password` and `salt` using `Argon2id` algorithm (RFC 9106, version 0x13).
+/// Argon2id is memory-hard, so it resists GPU and ASIC cracking better than `scrypt`.
+///
+/// # Conditions
+/// - `memory_cost` must be at least `8 * parallelism`
+/// - `iterations` must be greater than `0`
+/// - `parallelism` must be from `1` to `16777215`
+/// - `dk_len` must be at least `4`
+/// # Recommended values sufficient for most use-cases
+/// - `memory_cost = 65536` (64 MiB)
+/// - `iterations = 3`
+/// - `parallelism = 4`
+#[api_function]
+pub fn argon2id(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfArgon2id,
+) -> ClientResult<ResultOfArgon2id> {
+    let mut key = Vec::new();
+    key.resize(params.dk_len as usize, 0);
+    let password = base64_decode(&params.password)?;
+    let salt = base64_decode(&params.salt)?;
+    argon2id_derive(
+        &password,
+        &salt,
+        params.memory_cost,
+        params.iterations,
+        params.parallelism,
+        &mut key,
+    )?;
+    Ok(ResultOfArgon2id {
+        key: hex::encode(&key),
+    })
+}
@@ -37,6 +37,19 @@ pub enum ErrorCode {
     CryptoBoxSecretSerializationError = 132,
     CryptoBoxSecretDeserializationError = 133,
     InvalidNonceSize = 134,
+    MnemonicPassphraseNotSupported = 135,
+    Slip39InvalidParameters = 136,
+    Slip39InvalidShare = 137,
+    InvalidKeystore = 138,
+    KeystoreDecryptionFailed = 139,
+    SigningBoxPolicyViolation = 140,
+    InvalidSigningBoxPolicy = 141,
+    EncryptionStreamNotSupported = 142,
+    EncryptionStreamNotRegistered = 143,
+    InvalidOutputLength = 144,
+    Argon2Failed = 145,
+    MnemonicRepairTooManyCandidates = 146,
+    SigningBoxNotSupportedByAbi = 147,
 }
 
 pub struct Error;
@@ -70,6 +83,23 @@ impl Error {
         )
     }
 
+    pub fn argon2_failed<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::Argon2Failed,
+            format!("Argon2 failed: {}", err),
+        )
+    }
+
+    pub fn mnemonic_repair_too_many_candidates(candidates: usize, max: usize) -> ClientError {
+        error(
+            ErrorCode::MnemonicRepairTooManyCandidates,
+            format!(
+                "Too many candidate phrases to check: {} (maximum {}). Correct some of the words or reduce `max_distance`",
+                candidates, max
+            ),
+        )
+    }
+
     pub fn invalid_key_size(actual: usize, expected: &[usize]) -> ClientError {
         error(
             ErrorCode::InvalidKeySize,
@@ -274,4 +304,84 @@ impl Error {
             ),
         )
     }
+
+    pub fn mnemonic_passphrase_not_supported(dictionary: u8) -> ClientError {
+        error(
+            ErrorCode::MnemonicPassphraseNotSupported,
+            format!("Mnemonic passphrase is not supported for dictionary {}", dictionary),
+        )
+    }
+
+    pub fn slip39_invalid_parameters(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::Slip39InvalidParameters,
+            format!("Invalid SLIP-39 parameters: {}", err),
+        )
+    }
+
+    pub fn slip39_invalid_share(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::Slip39InvalidShare,
+            format!("Invalid SLIP-39 share: {}", err),
+        )
+    }
+
+    pub fn invalid_keystore(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::InvalidKeystore,
+            format!("Invalid keystore: {}", err),
+        )
+    }
+
+    pub fn keystore_decryption_failed() -> ClientError {
+        error(
+            ErrorCode::KeystoreDecryptionFailed,
+            "Keystore decryption failed: invalid password or corrupted keystore".to_string(),
+        )
+    }
+
+    pub fn signing_box_policy_violation(reason: impl Display) -> ClientError {
+        error(
+            ErrorCode::SigningBoxPolicyViolation,
+            format!("Signing box policy violation: {}", reason),
+        )
+    }
+
+    pub fn signing_box_not_supported_by_abi(reason: impl Display) -> ClientError {
+        error(
+            ErrorCode::SigningBoxNotSupportedByAbi,
+            format!("Signing box can not sign ABI messages: {}", reason),
+        )
+    }
+
+    pub fn invalid_signing_box_policy(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::InvalidSigningBoxPolicy,
+            format!("Invalid signing box policy: {}", err),
+        )
+    }
+
+    pub fn encryption_stream_not_supported() -> ClientError {
+        error(
+            ErrorCode::EncryptionStreamNotSupported,
+            "Encryption box does not support stream encryption".to_string(),
+        )
+    }
+
+    pub fn encryption_stream_not_registered(id: u32) -> ClientError {
+        error(
+            ErrorCode::EncryptionStreamNotRegistered,
+            format!("Encryption stream is not registered. ID {}", id),
+        )
+    }
+
+    pub fn invalid_output_length(actual: u32, min: u32, max: u32) -> ClientError {
+        error(
+            ErrorCode::InvalidOutputLength,
+            format!(
+                "Invalid output length {}. Length must be from {} to {}",
+                actual, min, max
+            ),
+        )
+    }
 }
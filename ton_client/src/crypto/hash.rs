@@ -12,10 +12,15 @@
 */
 
 use crate::client::ClientContext;
+use crate::crypto;
 use crate::encoding::base64_decode;
 use crate::error::ClientResult;
+use blake2::digest::VariableOutput;
+use hmac::{Hmac, Mac, NewMac};
 use sha2::Digest;
 
+const BLAKE2B_MAX_OUTPUT_LEN: u32 = 64;
+
 //--------------------------------------------------------------------------------------------- sha
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -55,3 +60,115 @@ pub fn sha512(
         hash: hex::encode(hasher.finalize().to_vec()),
     })
 }
+
+//-------------------------------------------------------------------------------------------- sha3
+
+/// Calculates Keccak-256 hash of the specified data.
+///
+/// This is the original Keccak padding used by Ethereum, which differs from the standard SHA3-256.
+#[api_function]
+pub fn keccak256(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHash,
+) -> ClientResult<ResultOfHash> {
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update(base64_decode(&params.data)?);
+    Ok(ResultOfHash {
+        hash: hex::encode(hasher.finalize().to_vec()),
+    })
+}
+
+/// Calculates SHA3-256 hash of the specified data.
+#[api_function]
+pub fn sha3_256(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHash,
+) -> ClientResult<ResultOfHash> {
+    let mut hasher = sha3::Sha3_256::new();
+    hasher.update(base64_decode(&params.data)?);
+    Ok(ResultOfHash {
+        hash: hex::encode(hasher.finalize().to_vec()),
+    })
+}
+
+/// Calculates SHA3-512 hash of the specified data.
+#[api_function]
+pub fn sha3_512(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHash,
+) -> ClientResult<ResultOfHash> {
+    let mut hasher = sha3::Sha3_512::new();
+    hasher.update(base64_decode(&params.data)?);
+    Ok(ResultOfHash {
+        hash: hex::encode(hasher.finalize().to_vec()),
+    })
+}
+
+//----------------------------------------------------------------------------------------- blake2b
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfBlake2b {
+    /// Input data for hash calculation. Encoded with `base64`.
+    pub data: String,
+    /// Hash length in bytes, from 1 to 64. Default is 64.
+    pub output_len: Option<u32>,
+}
+
+/// Calculates BLAKE2b hash of the specified data.
+#[api_function]
+pub fn blake2b(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfBlake2b,
+) -> ClientResult<ResultOfHash> {
+    let output_len = params.output_len.unwrap_or(BLAKE2B_MAX_OUTPUT_LEN);
+    let mut hasher = blake2::VarBlake2b::new(output_len as usize)
+        .map_err(|_| crypto::Error::invalid_output_length(output_len, 1, BLAKE2B_MAX_OUTPUT_LEN))?;
+    blake2::digest::Update::update(&mut hasher, base64_decode(&params.data)?);
+    let mut hash = String::new();
+    hasher.finalize_variable(|result| hash = hex::encode(result));
+    Ok(ResultOfHash { hash })
+}
+
+//-------------------------------------------------------------------------------------------- hmac
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfHmac {
+    /// Secret key. Encoded with `base64`.
+    pub key: String,
+    /// Input data. Encoded with `base64`.
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfHmac {
+    /// Message authentication code of input `data`. Encoded with `hex`.
+    pub hmac: String,
+}
+
+fn calc_hmac<M: Mac + NewMac>(params: &ParamsOfHmac) -> ClientResult<ResultOfHmac> {
+    let key = crypto::internal::SecretBuf(base64_decode(&params.key)?);
+    // HMAC accepts keys of any length
+    let mut mac = M::new_from_slice(&key.0).unwrap();
+    mac.update(&base64_decode(&params.data)?);
+    Ok(ResultOfHmac {
+        hmac: hex::encode(mac.finalize().into_bytes()),
+    })
+}
+
+/// Calculates HMAC-SHA256 of the specified data.
+#[api_function]
+pub fn hmac_sha256(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHmac,
+) -> ClientResult<ResultOfHmac> {
+    calc_hmac::<Hmac<sha2::Sha256>>(&params)
+}
+
+/// Calculates HMAC-SHA512 of the specified data.
+#[api_function]
+pub fn hmac_sha512(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHmac,
+) -> ClientResult<ResultOfHmac> {
+    calc_hmac::<Hmac<sha2::Sha512>>(&params)
+}
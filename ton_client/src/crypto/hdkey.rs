@@ -24,6 +24,26 @@ use libsecp256k1::{SecretKey, PublicKey};
 use sha2::{Digest, Sha512};
 use crate::crypto::default_hdkey_compliant;
 
+/// Derivation scheme of the extended private key.
+///
+/// The scheme is recorded in the serialized extended private key: `Bip32` keys are
+/// serialized with the standard `xprv` version prefix, `Slip10Ed25519` keys with
+/// the distinct version prefix, so they can not be derived with the other scheme.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ApiType, PartialEq)]
+pub enum HDKeyDerivationScheme {
+    /// BIP-0032 derivation over secp256k1. The derived private key is used
+    /// as the ed25519 secret key.
+    Bip32,
+    /// SLIP-0010 derivation for ed25519. Only hardened child keys can be derived.
+    Slip10Ed25519,
+}
+
+impl Default for HDKeyDerivationScheme {
+    fn default() -> Self {
+        HDKeyDerivationScheme::Bip32
+    }
+}
+
 //----------------------------------------------------------------- crypto.hdkey_xprv_from_mnemonic
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -34,6 +54,10 @@ pub struct ParamsOfHDKeyXPrvFromMnemonic {
     pub dictionary: Option<u8>,
     /// Mnemonic word count
     pub word_count: Option<u8>,
+    /// Derivation scheme. Default is `Bip32`.
+    pub derivation_scheme: Option<HDKeyDerivationScheme>,
+    /// BIP-39 passphrase ("25th word") mixed into the seed. Default is empty.
+    pub passphrase: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -53,7 +77,12 @@ pub fn hdkey_xprv_from_mnemonic(
         &params.phrase,
     )?;
     Ok(ResultOfHDKeyXPrvFromMnemonic {
-        xprv: HDPrivateKey::from_mnemonic(&params.phrase)?.serialize_to_string(),
+        xprv: HDPrivateKey::from_mnemonic(
+            &params.phrase,
+            params.passphrase.as_deref().unwrap_or_default(),
+            params.derivation_scheme.unwrap_or_default(),
+        )?
+        .serialize_to_string(),
     })
 }
 
@@ -97,6 +126,9 @@ pub struct ResultOfHDKeyPublicFromXPrv {
 }
 
 /// Extracts the public key from the serialized extended private key
+///
+/// The private key is used as the ed25519 secret key for both derivation schemes,
+/// so the returned key is the ed25519 public key.
 #[api_function]
 pub fn hdkey_public_from_xprv(
     _context: std::sync::Arc<ClientContext>,
@@ -121,6 +153,9 @@ pub struct ParamsOfHDKeyDeriveFromXPrv {
     pub child_index: u32,
     /// Indicates the derivation of hardened/not-hardened key (see BIP-0032)
     pub hardened: bool,
+    /// Derivation scheme. Default is the scheme of the extended private key.
+    /// Must match the scheme of the extended private key if specified.
+    pub derivation_scheme: Option<HDKeyDerivationScheme>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -136,6 +171,7 @@ pub fn hdkey_derive_from_xprv(
     params: ParamsOfHDKeyDeriveFromXPrv,
 ) -> ClientResult<ResultOfHDKeyDeriveFromXPrv> {
     let xprv = HDPrivateKey::from_serialized_string(&params.xprv)?;
+    xprv.check_scheme(params.derivation_scheme)?;
     let derived = xprv.derive(
         params.child_index,
         params.hardened,
@@ -154,6 +190,9 @@ pub struct ParamsOfHDKeyDeriveFromXPrvPath {
     pub xprv: String,
     /// Derivation path, for instance "m/44'/396'/0'/0/0"
     pub path: String,
+    /// Derivation scheme. Default is the scheme of the extended private key.
+    /// Must match the scheme of the extended private key if specified.
+    pub derivation_scheme: Option<HDKeyDerivationScheme>,
 }
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -169,6 +208,7 @@ pub fn hdkey_derive_from_xprv_path(
     params: ParamsOfHDKeyDeriveFromXPrvPath,
 ) -> ClientResult<ResultOfHDKeyDeriveFromXPrvPath> {
     let xprv = HDPrivateKey::from_serialized_string(&params.xprv)?;
+    xprv.check_scheme(params.derivation_scheme)?;
     Ok(ResultOfHDKeyDeriveFromXPrvPath {
         xprv: xprv
             .derive_path(&params.path, default_hdkey_compliant())?
@@ -185,9 +225,20 @@ pub(crate) struct HDPrivateKey {
     child_number: [u8; 4],
     child_chain: Key256,
     key: Key256,
+    scheme: HDKeyDerivationScheme,
 }
 
 static XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+// There is no registered version for SLIP-0010 ed25519 keys, so the version differs
+// from `XPRV_VERSION` in the last byte to keep the keys of different schemes apart
+static SLIP10_ED25519_XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE5];
+
+fn xprv_version(scheme: HDKeyDerivationScheme) -> [u8; 4] {
+    match scheme {
+        HDKeyDerivationScheme::Bip32 => XPRV_VERSION,
+        HDKeyDerivationScheme::Slip10Ed25519 => SLIP10_ED25519_XPRV_VERSION,
+    }
+}
 
 impl HDPrivateKey {
     pub(crate) fn master(child_chain: &Key256, key: &Key256) -> HDPrivateKey {
@@ -197,20 +248,47 @@ impl HDPrivateKey {
             child_number: [0; 4],
             child_chain: child_chain.clone(),
             key: key.clone(),
+            scheme: HDKeyDerivationScheme::Bip32,
         }
     }
 
-    pub(crate) fn from_mnemonic(phrase: &String) -> ClientResult<HDPrivateKey> {
-        let salt = "mnemonic";
+    pub(crate) fn from_mnemonic(
+        phrase: &String,
+        passphrase: &str,
+        scheme: HDKeyDerivationScheme,
+    ) -> ClientResult<HDPrivateKey> {
+        let salt = format!("mnemonic{}", passphrase);
         let mut seed = vec![0u8; 64];
         pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), 2048, &mut seed);
-        let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(b"Bitcoin seed").unwrap();
-        hmac.update(&seed);
+        Self::from_seed(&seed, scheme)
+    }
+
+    /// Generates master key from the seed according to the derivation scheme
+    pub(crate) fn from_seed(seed: &[u8], scheme: HDKeyDerivationScheme) -> ClientResult<HDPrivateKey> {
+        let curve_key: &[u8] = match scheme {
+            HDKeyDerivationScheme::Bip32 => b"Bitcoin seed",
+            HDKeyDerivationScheme::Slip10Ed25519 => b"ed25519 seed",
+        };
+        let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(curve_key).unwrap();
+        hmac.update(seed);
         let child_chain_with_key = key512(&hmac.finalize().into_bytes())?;
-        Ok(HDPrivateKey::master(
+        let mut master = HDPrivateKey::master(
             &key256(&child_chain_with_key[32..])?,
             &key256(&child_chain_with_key[..32])?,
-        ))
+        );
+        master.scheme = scheme;
+        Ok(master)
+    }
+
+    /// Checks that the requested derivation scheme matches the scheme of the key
+    pub(crate) fn check_scheme(&self, scheme: Option<HDKeyDerivationScheme>) -> ClientResult<()> {
+        match scheme {
+            Some(scheme) if scheme != self.scheme => Err(crypto::Error::bip32_invalid_key(format!(
+                "key is derived with {:?} scheme, but {:?} scheme is requested",
+                self.scheme, scheme
+            ))),
+            _ => Ok(()),
+        }
     }
 
     pub(crate) fn secret(&self) -> Key256 {
@@ -252,6 +330,52 @@ impl HDPrivateKey {
         child_index: u32,
         hardened: bool,
         compliant: bool,
+    ) -> ClientResult<HDPrivateKey> {
+        match self.scheme {
+            HDKeyDerivationScheme::Bip32 => self.derive_bip32(child_index, hardened, compliant),
+            HDKeyDerivationScheme::Slip10Ed25519 => self.derive_slip10_ed25519(child_index, hardened),
+        }
+    }
+
+    fn derive_slip10_ed25519(&self, child_index: u32, hardened: bool) -> ClientResult<HDPrivateKey> {
+        if !hardened {
+            return Err(crypto::Error::bip32_invalid_derive_path(format!(
+                "SLIP-0010 ed25519 supports only hardened derivation, index {} is not hardened",
+                child_index
+            )));
+        }
+        let mut child: HDPrivateKey = Default::default();
+        child.depth = self.depth + 1;
+        child.scheme = self.scheme;
+
+        let secret = ed25519_dalek::SecretKey::from_bytes(&self.key.0)
+            .map_err(|err| crypto::Error::bip32_invalid_key(err))?;
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        let mut sha_hasher = sha2::Sha256::new();
+        sha_hasher.update(&[0]);
+        sha_hasher.update(public.as_bytes());
+        let sha: Key256 = <[u8; 32]>::from(sha_hasher.finalize()).into();
+        let fingerprint = Ripemd160::new().update(&sha).digest();
+        child.parent_fingerprint.copy_from_slice(&fingerprint[0..4]);
+
+        BigEndian::write_u32(&mut child.child_number, 0x80000000 | child_index);
+
+        let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(&self.child_chain)
+            .map_err(|err| crypto::Error::bip32_invalid_key(err))?;
+        hmac.update(&[0]);
+        hmac.update(&self.key.0);
+        hmac.update(&child.child_number);
+        let result = key512(&hmac.finalize().into_bytes())?;
+        child.key.0.copy_from_slice(&result[..32]);
+        child.child_chain.0.copy_from_slice(&result[32..]);
+        Ok(child)
+    }
+
+    fn derive_bip32(
+        &self,
+        child_index: u32,
+        hardened: bool,
+        compliant: bool,
     ) -> ClientResult<HDPrivateKey> {
         let mut child: HDPrivateKey = Default::default();
         child.depth = self.depth + 1;
@@ -304,7 +428,11 @@ impl HDPrivateKey {
         Ok(child)
     }
 
-    pub(crate) fn derive_path(&self, path: &String, compliant: bool) -> ClientResult<HDPrivateKey> {
+    pub(crate) fn derive_path(
+        &self,
+        path: &String,
+        compliant: bool,
+    ) -> ClientResult<HDPrivateKey> {
         let mut child: HDPrivateKey = self.clone();
         for step in path.split("/") {
             if step == "m" {
@@ -331,10 +459,15 @@ impl HDPrivateKey {
         }
         let mut version = [0u8; 4];
         version.clone_from_slice(&bytes[0..4]);
-        if version != XPRV_VERSION {
+        let scheme = if version == XPRV_VERSION {
+            HDKeyDerivationScheme::Bip32
+        } else if version == SLIP10_ED25519_XPRV_VERSION {
+            HDKeyDerivationScheme::Slip10Ed25519
+        } else {
             return Err(crypto::Error::bip32_invalid_key(bytes.to_base58()));
-        }
+        };
         let mut xprv: HDPrivateKey = Default::default();
+        xprv.scheme = scheme;
         xprv.depth = bytes[4];
         xprv.parent_fingerprint.copy_from_slice(&bytes[5..9]);
         xprv.child_number.copy_from_slice(&bytes[9..13]);
@@ -348,7 +481,7 @@ impl HDPrivateKey {
 
     fn serialize(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::new();
-        bytes.extend(&XPRV_VERSION);
+        bytes.extend(&xprv_version(self.scheme));
         bytes.push(self.depth);
         bytes.extend(&self.parent_fingerprint);
         bytes.extend(&self.child_number);
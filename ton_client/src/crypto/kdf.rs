@@ -0,0 +1,138 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::internal::SecretBuf;
+use crate::encoding::{base64_decode, hex_decode};
+use crate::error::ClientResult;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Hash function used by HKDF.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ApiType, PartialEq)]
+pub enum HkdfHashAlgorithm {
+    Sha256,
+    Sha512,
+}
+
+impl Default for HkdfHashAlgorithm {
+    fn default() -> Self {
+        HkdfHashAlgorithm::Sha256
+    }
+}
+
+impl HkdfHashAlgorithm {
+    fn output_len(&self) -> usize {
+        match self {
+            HkdfHashAlgorithm::Sha256 => Sha256::output_size(),
+            HkdfHashAlgorithm::Sha512 => Sha512::output_size(),
+        }
+    }
+}
+
+//------------------------------------------------------------------------------------ hkdf_extract
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfHkdfExtract {
+    /// Input key material. Must be encoded with `base64`.
+    pub ikm: String,
+    /// Optional salt. Must be encoded with `base64`.
+    /// If omitted, a string of zero bytes of the hash length is used.
+    pub salt: Option<String>,
+    /// Hash function. Default is `Sha256`.
+    pub hash_algorithm: Option<HkdfHashAlgorithm>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfHkdfExtract {
+    /// Pseudorandom key. Encoded with `hex`.
+    pub prk: String,
+}
+
+/// Performs HKDF-Extract step (RFC 5869).
+///
+/// Extracts a pseudorandom key from the input key material and salt.
+/// Use `hkdf_expand` to derive the output keys from it.
+#[api_function]
+pub fn hkdf_extract(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHkdfExtract,
+) -> ClientResult<ResultOfHkdfExtract> {
+    let ikm = SecretBuf(base64_decode(&params.ikm)?);
+    let salt = params.salt.as_ref().map(|salt| base64_decode(salt)).transpose()?;
+    let prk = match params.hash_algorithm.unwrap_or_default() {
+        HkdfHashAlgorithm::Sha256 => {
+            hex::encode(Hkdf::<Sha256>::extract(salt.as_deref(), &ikm.0).0)
+        }
+        HkdfHashAlgorithm::Sha512 => {
+            hex::encode(Hkdf::<Sha512>::extract(salt.as_deref(), &ikm.0).0)
+        }
+    };
+    Ok(ResultOfHkdfExtract { prk })
+}
+
+//------------------------------------------------------------------------------------- hkdf_expand
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfHkdfExpand {
+    /// Pseudorandom key, usually the result of `hkdf_extract`. Must be encoded with `hex`.
+    pub prk: String,
+    /// Optional context and application specific information. Must be encoded with `base64`.
+    pub info: Option<String>,
+    /// Output key length in bytes. Must not exceed 255 hash lengths.
+    pub length: u32,
+    /// Hash function. Default is `Sha256`.
+    pub hash_algorithm: Option<HkdfHashAlgorithm>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfHkdfExpand {
+    /// Output key material. Encoded with `hex`.
+    pub okm: String,
+}
+
+/// Performs HKDF-Expand step (RFC 5869).
+///
+/// Derives output key material of the requested length from the pseudorandom key and
+/// the context information. Different `info` values give independent keys, for example
+/// per-session keys.
+#[api_function]
+pub fn hkdf_expand(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHkdfExpand,
+) -> ClientResult<ResultOfHkdfExpand> {
+    let hash_algorithm = params.hash_algorithm.unwrap_or_default();
+    let max_length = 255 * hash_algorithm.output_len() as u32;
+    if params.length == 0 || params.length > max_length {
+        return Err(crypto::Error::invalid_output_length(params.length, 1, max_length));
+    }
+    let prk = SecretBuf(hex_decode(&params.prk)?);
+    let info = params.info.as_ref().map(|info| base64_decode(info)).transpose()?;
+    let info = info.as_deref().unwrap_or_default();
+    let mut okm = SecretBuf(vec![0; params.length as usize]);
+    let invalid_prk =
+        |_| crypto::Error::invalid_key_size(prk.0.len(), &[hash_algorithm.output_len()]);
+    match hash_algorithm {
+        HkdfHashAlgorithm::Sha256 => {
+            Hkdf::<Sha256>::from_prk(&prk.0).map_err(invalid_prk)?.expand(info, &mut okm.0)
+        }
+        HkdfHashAlgorithm::Sha512 => {
+            Hkdf::<Sha512>::from_prk(&prk.0).map_err(invalid_prk)?.expand(info, &mut okm.0)
+        }
+    }
+    .map_err(|_| crypto::Error::invalid_output_length(params.length, 1, max_length))?;
+    Ok(ResultOfHkdfExpand {
+        okm: hex::encode(&okm.0),
+    })
+}
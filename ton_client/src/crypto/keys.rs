@@ -13,12 +13,17 @@
 
 use crate::client::ClientContext;
 use crate::crypto;
-use crate::crypto::internal::{decode_public_key, decode_secret_key, sign_using_keys, ton_crc16};
+use crate::crypto::internal::{
+    decode_public_key, decode_secret_key, hex_decode_secret, sign_using_keys, ton_crc16, Key256,
+};
 use crate::encoding::{base64_decode, hex_decode};
 use crate::error::ClientResult;
 use base64::URL_SAFE;
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use ed25519_dalek::Keypair;
+use sha2::{Digest, Sha512};
 use std::fmt::{Debug, Formatter};
+use zeroize::Zeroize;
 
 use super::internal::hex_decode_secret_const;
 
@@ -102,6 +107,92 @@ pub fn convert_public_key_to_ton_safe_format(
     })
 }
 
+//---------------------------------------------------------------- convert_ed25519_to_x25519_public
+
+pub(crate) fn ed25519_public_to_x25519(public: &String) -> ClientResult<[u8; 32]> {
+    let bytes = hex_decode(public)?;
+    if bytes.len() != 32 {
+        return Err(crypto::Error::invalid_key_size(bytes.len(), &[32]));
+    }
+    CompressedEdwardsY::from_slice(&bytes)
+        .decompress()
+        .map(|point| point.to_montgomery().to_bytes())
+        .ok_or_else(|| crypto::Error::invalid_public_key("not a valid ed25519 point", public))
+}
+
+pub(crate) fn ed25519_secret_to_x25519(secret: &String) -> ClientResult<Key256> {
+    let bytes = hex_decode_secret(secret)?;
+    // 64-byte NaCl sign secret consists of the 32-byte seed followed by the public key
+    if bytes.len() != 32 && bytes.len() != 64 {
+        return Err(crypto::Error::invalid_key_size(bytes.len(), &[32, 64]));
+    }
+    let mut hash = Sha512::digest(&bytes[..32]);
+    let mut key = Key256::default();
+    key.0.copy_from_slice(&hash[..32]);
+    hash.as_mut_slice().zeroize();
+    key.0[0] &= 248;
+    key.0[31] &= 127;
+    key.0[31] |= 64;
+    Ok(key)
+}
+
+///
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfConvertEd25519ToX25519Public {
+    /// Ed25519 public key - 64 symbols hex string
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfConvertEd25519ToX25519Public {
+    /// X25519 public key - 64 symbols hex string
+    pub public: String,
+}
+
+/// Converts ed25519 public key to X25519 (Curve25519) public key.
+///
+/// Converted key can be used as `their_public` in NaCl box functions, so the data can be
+/// encrypted to the owner of the ed25519 key pair.
+#[api_function]
+pub fn convert_ed25519_to_x25519_public(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfConvertEd25519ToX25519Public,
+) -> ClientResult<ResultOfConvertEd25519ToX25519Public> {
+    Ok(ResultOfConvertEd25519ToX25519Public {
+        public: hex::encode(ed25519_public_to_x25519(&params.public)?),
+    })
+}
+
+//---------------------------------------------------------------- convert_ed25519_to_x25519_secret
+
+///
+#[derive(Serialize, Deserialize, ApiType, Default, Zeroize, ZeroizeOnDrop)]
+pub struct ParamsOfConvertEd25519ToX25519Secret {
+    /// Ed25519 secret key - 64 symbols hex string.
+    /// 128 symbols NaCl sign secret key is also accepted.
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Zeroize, ZeroizeOnDrop)]
+pub struct ResultOfConvertEd25519ToX25519Secret {
+    /// X25519 secret key - 64 symbols hex string
+    pub secret: String,
+}
+
+/// Converts ed25519 secret key to X25519 (Curve25519) secret key.
+///
+/// Converted key can be used as `secret` in NaCl box functions. Its public key
+/// matches the result of `convert_ed25519_to_x25519_public` for the ed25519 public key.
+#[api_function]
+pub fn convert_ed25519_to_x25519_secret(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfConvertEd25519ToX25519Secret,
+) -> ClientResult<ResultOfConvertEd25519ToX25519Secret> {
+    Ok(ResultOfConvertEd25519ToX25519Secret {
+        secret: hex::encode(ed25519_secret_to_x25519(&params.secret)?.0),
+    })
+}
+
 //----------------------------------------------------------------------- generate_random_sign_keys
 
 /// Generates random ed25519 key pair.
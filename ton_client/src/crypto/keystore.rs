@@ -0,0 +1,330 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::internal::{SecretBuf, SecretBufConst};
+use crate::crypto::keys::KeyPair;
+use crate::crypto::nacl::{nacl_secret_box_internal, nacl_secret_box_open_internal};
+use crate::encoding::{base64_decode, hex_decode};
+use crate::error::ClientResult;
+use chacha20poly1305::aead::{Aead, NewAead};
+use chacha20poly1305::ChaCha20Poly1305;
+use rand::RngCore;
+
+const KEYSTORE_VERSION: u32 = 1;
+const KDF_SCRYPT: &str = "scrypt";
+const SALT_LEN: usize = 32;
+// Scrypt parameters are read from the keystore, so the memory (`128 * r * 2^log_n` bytes)
+// and the work (`p * r` blocks mixed per `N` step) of the key derivation are limited
+const MAX_SCRYPT_MEMORY: u128 = 256 << 20;
+const MAX_SCRYPT_WORK: u64 = 64;
+
+/// Secret stored in the keystore.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "type")]
+pub enum KeystoreSecret {
+    /// Signing key pair.
+    KeyPair { keys: KeyPair },
+    /// Seed phrase with its dictionary and word count.
+    SeedPhrase {
+        phrase: String,
+        dictionary: u8,
+        word_count: u8,
+    },
+}
+
+impl Default for KeystoreSecret {
+    fn default() -> Self {
+        KeystoreSecret::KeyPair {
+            keys: Default::default(),
+        }
+    }
+}
+
+/// Cipher used to encrypt the keystore secret.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, ApiType, PartialEq)]
+pub enum KeystoreCipher {
+    /// ChaCha20-Poly1305 AEAD with 12-byte nonce.
+    ChaCha20Poly1305,
+    /// NaCl `secretbox` (XSalsa20-Poly1305) with 24-byte nonce.
+    NaclSecretBox,
+}
+
+impl Default for KeystoreCipher {
+    fn default() -> Self {
+        KeystoreCipher::ChaCha20Poly1305
+    }
+}
+
+impl KeystoreCipher {
+    fn name(&self) -> &'static str {
+        match self {
+            KeystoreCipher::ChaCha20Poly1305 => "chacha20-poly1305",
+            KeystoreCipher::NaclSecretBox => "nacl-secretbox",
+        }
+    }
+
+    fn from_name(name: &str) -> ClientResult<Self> {
+        match name {
+            "chacha20-poly1305" => Ok(KeystoreCipher::ChaCha20Poly1305),
+            "nacl-secretbox" => Ok(KeystoreCipher::NaclSecretBox),
+            _ => Err(crypto::Error::invalid_keystore(format!(
+                "unsupported cipher `{}`",
+                name
+            ))),
+        }
+    }
+
+    fn nonce_len(&self) -> usize {
+        match self {
+            KeystoreCipher::ChaCha20Poly1305 => 12,
+            KeystoreCipher::NaclSecretBox => 24,
+        }
+    }
+
+    fn encrypt(&self, key: &[u8], nonce: &[u8], data: &[u8]) -> ClientResult<Vec<u8>> {
+        match self {
+            KeystoreCipher::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(key))
+                    .encrypt(chacha20poly1305::Nonce::from_slice(nonce), data)
+                    .map_err(|err| crypto::Error::invalid_keystore(err))
+            }
+            KeystoreCipher::NaclSecretBox => nacl_secret_box_internal(data, nonce, key),
+        }
+    }
+
+    fn decrypt(&self, key: &[u8], nonce: &[u8], data: &[u8]) -> ClientResult<SecretBuf> {
+        let decrypted = match self {
+            KeystoreCipher::ChaCha20Poly1305 => {
+                ChaCha20Poly1305::new(chacha20poly1305::Key::from_slice(key))
+                    .decrypt(chacha20poly1305::Nonce::from_slice(nonce), data)
+                    .map_err(|_| crypto::Error::keystore_decryption_failed())
+            }
+            KeystoreCipher::NaclSecretBox => nacl_secret_box_open_internal(data, nonce, key)
+                .map_err(|_| crypto::Error::keystore_decryption_failed()),
+        };
+        decrypted.map(SecretBuf)
+    }
+}
+
+/// Scrypt parameters of the keystore key derivation.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+pub struct KeystoreKdfParams {
+    /// The log2 of the Scrypt parameter `N`. Default is `15`.
+    pub log_n: u8,
+    /// The Scrypt parameter `r`. Default is `8`.
+    pub r: u32,
+    /// The Scrypt parameter `p`. Default is `1`.
+    ///
+    /// Memory `128 * r * 2^log_n` bytes must not exceed 256 MiB and `p * r` must not exceed 64.
+    pub p: u32,
+}
+
+impl Default for KeystoreKdfParams {
+    fn default() -> Self {
+        Self {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Serialized keystore layout.
+#[derive(Serialize, Deserialize)]
+struct Keystore {
+    version: u32,
+    crypto: KeystoreCrypto,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCrypto {
+    cipher: String,
+    cipher_params: KeystoreCipherParams,
+    /// Encrypted secret in `base64`
+    ciphertext: String,
+    kdf: String,
+    kdf_params: KeystoreScryptParams,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreCipherParams {
+    /// Nonce in `hex`
+    nonce: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreScryptParams {
+    log_n: u8,
+    r: u32,
+    p: u32,
+    dk_len: u32,
+    /// Salt in `hex`
+    salt: String,
+}
+
+fn derive_key(password: &[u8], params: &KeystoreScryptParams) -> ClientResult<SecretBufConst<32>> {
+    if params.dk_len != 32 {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "unsupported derived key length {}",
+            params.dk_len
+        )));
+    }
+    let memory = if params.log_n < 64 {
+        (128 * params.r as u128) << params.log_n
+    } else {
+        u128::MAX
+    };
+    let work = params.p as u64 * params.r as u64;
+    if memory > MAX_SCRYPT_MEMORY || work > MAX_SCRYPT_WORK {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "scrypt parameters log_n = {}, r = {}, p = {} exceed the limits: \
+                memory 128 * r * 2^log_n must not exceed {} MiB, p * r must not exceed {}",
+            params.log_n, params.r, params.p, MAX_SCRYPT_MEMORY >> 20, MAX_SCRYPT_WORK,
+        )));
+    }
+    let scrypt_params = scrypt::Params::new(params.log_n, params.r, params.p)
+        .map_err(|err| crypto::Error::scrypt_failed(err))?;
+    let mut key = SecretBufConst::<32>::default();
+    scrypt::scrypt(password, &hex_decode(&params.salt)?, &scrypt_params, &mut key.0)
+        .map_err(|err| crypto::Error::scrypt_failed(err))?;
+    Ok(key)
+}
+
+//--------------------------------------------------------------------------------- export_keystore
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfExportKeystore {
+    /// Secret to store.
+    pub secret: KeystoreSecret,
+    /// Keystore password. Must be encoded with `base64`.
+    pub password: String,
+    /// Cipher. Default is `ChaCha20Poly1305`.
+    pub cipher: Option<KeystoreCipher>,
+    /// Scrypt parameters of the key derivation.
+    pub kdf_params: Option<KeystoreKdfParams>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfExportKeystore {
+    /// Keystore JSON.
+    pub keystore: String,
+}
+
+/// Exports the key pair or the seed phrase into encrypted JSON keystore.
+///
+/// The encryption key is derived from the password with `scrypt` and random salt.
+/// The keystore contains the version, the cipher and the KDF parameters, so it can be
+/// imported with `import_keystore` knowing the password only.
+#[api_function]
+pub fn export_keystore(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfExportKeystore,
+) -> ClientResult<ResultOfExportKeystore> {
+    let cipher = params.cipher.unwrap_or_default();
+    let kdf_params = params.kdf_params.unwrap_or_default();
+
+    let mut rng = rand::thread_rng();
+    let mut salt = vec![0u8; SALT_LEN];
+    rng.fill_bytes(&mut salt);
+    let mut nonce = vec![0u8; cipher.nonce_len()];
+    rng.fill_bytes(&mut nonce);
+
+    let scrypt_params = KeystoreScryptParams {
+        log_n: kdf_params.log_n,
+        r: kdf_params.r,
+        p: kdf_params.p,
+        dk_len: 32,
+        salt: hex::encode(&salt),
+    };
+    let key = derive_key(&SecretBuf(base64_decode(&params.password)?), &scrypt_params)?;
+    let plain = SecretBuf(
+        serde_json::to_vec(&params.secret).map_err(|err| crypto::Error::invalid_keystore(err))?,
+    );
+    let ciphertext = cipher.encrypt(&key.0, &nonce, &plain)?;
+
+    let keystore = Keystore {
+        version: KEYSTORE_VERSION,
+        crypto: KeystoreCrypto {
+            cipher: cipher.name().to_string(),
+            cipher_params: KeystoreCipherParams {
+                nonce: hex::encode(&nonce),
+            },
+            ciphertext: base64::encode(&ciphertext),
+            kdf: KDF_SCRYPT.to_string(),
+            kdf_params: scrypt_params,
+        },
+    };
+    Ok(ResultOfExportKeystore {
+        keystore: serde_json::to_string(&keystore)
+            .map_err(|err| crypto::Error::invalid_keystore(err))?,
+    })
+}
+
+//--------------------------------------------------------------------------------- import_keystore
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfImportKeystore {
+    /// Keystore JSON produced by `export_keystore`.
+    pub keystore: String,
+    /// Keystore password. Must be encoded with `base64`.
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfImportKeystore {
+    /// Decrypted secret.
+    pub secret: KeystoreSecret,
+}
+
+/// Decrypts the secret stored in JSON keystore.
+#[api_function]
+pub fn import_keystore(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfImportKeystore,
+) -> ClientResult<ResultOfImportKeystore> {
+    let keystore: Keystore = serde_json::from_str(&params.keystore)
+        .map_err(|err| crypto::Error::invalid_keystore(err))?;
+    if keystore.version != KEYSTORE_VERSION {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "unsupported version {}",
+            keystore.version
+        )));
+    }
+    if keystore.crypto.kdf != KDF_SCRYPT {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "unsupported KDF `{}`",
+            keystore.crypto.kdf
+        )));
+    }
+    let cipher = KeystoreCipher::from_name(&keystore.crypto.cipher)?;
+    let nonce = hex_decode(&keystore.crypto.cipher_params.nonce)?;
+    if nonce.len() != cipher.nonce_len() {
+        return Err(crypto::Error::invalid_nonce_size(nonce.len(), &[cipher.nonce_len()]));
+    }
+
+    let key = derive_key(
+        &SecretBuf(base64_decode(&params.password)?),
+        &keystore.crypto.kdf_params,
+    )?;
+    let plain = cipher.decrypt(
+        &key.0,
+        &nonce,
+        &base64_decode(&keystore.crypto.ciphertext)?,
+    )?;
+    Ok(ResultOfImportKeystore {
+        secret: serde_json::from_slice(&plain)
+            .map_err(|err| crypto::Error::invalid_keystore(err))?,
+    })
+}
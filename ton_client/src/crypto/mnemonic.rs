@@ -13,9 +13,10 @@
 
 use crate::client::ClientContext;
 use crate::crypto;
-use crate::crypto::hdkey::HDPrivateKey;
-use crate::crypto::internal::{hmac_sha512, key256, pbkdf2_hmac_sha512};
+use crate::crypto::hdkey::{HDKeyDerivationScheme, HDPrivateKey};
+use crate::crypto::internal::{hmac_sha512, key256, pbkdf2_hmac_sha512, SecretBuf};
 use crate::crypto::keys::KeyPair;
+use crate::crypto::slip39;
 use crate::crypto::{CryptoConfig, default_hdkey_compliant};
 use crate::encoding::hex_decode;
 use crate::error::ClientResult;
@@ -26,7 +27,7 @@ use pbkdf2::pbkdf2;
 use rand::RngCore;
 use sha2::Sha512;
 
-const TON_DICTIONARY: u8 = 0;
+pub(crate) const TON_DICTIONARY: u8 = 0;
 const ENGLISH_DICTIONARY: u8 = 1;
 const CHINESE_SIMPLIFIED_DICTIONARY: u8 = 2;
 const CHINESE_TRADITIONAL_DICTIONARY: u8 = 3;
@@ -36,6 +37,15 @@ const JAPANESE_DICTIONARY: u8 = 6;
 const KOREAN_DICTIONARY: u8 = 7;
 const SPANISH_DICTIONARY: u8 = 8;
 
+const DEFAULT_MAX_EDIT_DISTANCE: u32 = 2;
+const DEFAULT_MAX_SUGGESTIONS: u32 = 5;
+const DEFAULT_MAX_REPAIRED_PHRASES: u32 = 10;
+const MAX_REPAIR_ATTEMPTS: usize = 10_000;
+// Validation of the TON phrase runs PBKDF2, so far fewer candidates fit in a reasonable time.
+// A word has about 15 dictionary words within 2 edits, so the limit grows with the phrase
+// length and the edit distance.
+const MAX_TON_REPAIR_ATTEMPTS_PER_WORD_AND_DISTANCE: usize = 40;
+
 //---------------------------------------------------------------------------------- mnemonic_words
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -171,6 +181,15 @@ pub struct ParamsOfMnemonicDeriveSignKeys {
     pub dictionary: Option<u8>,
     /// Word count
     pub word_count: Option<u8>,
+    /// Derivation scheme. Default is `Bip32`.
+    ///
+    /// `Slip10Ed25519` requires the path with hardened indexes only,
+    /// for instance "m/44'/396'/0'/0'/0'".
+    pub derivation_scheme: Option<HDKeyDerivationScheme>,
+    /// BIP-39 passphrase ("25th word") mixed into the seed. Default is empty.
+    ///
+    /// Not supported for the TON dictionary.
+    pub passphrase: Option<String>,
 }
 
 /// Derives a key pair for signing from the seed phrase
@@ -186,7 +205,368 @@ pub fn mnemonic_derive_sign_keys(
     let path = params
         .path
         .unwrap_or(context.config.crypto.hdkey_derivation_path.clone());
-    Ok(mnemonic.derive_ed25519_keys_from_phrase(&context.config.crypto, &params.phrase, &path)?)
+    Ok(mnemonic.derive_ed25519_keys_from_phrase(
+        &context.config.crypto,
+        &params.phrase,
+        params.passphrase.as_deref().unwrap_or_default(),
+        &path,
+        params.derivation_scheme.unwrap_or_default(),
+    )?)
+}
+
+//---------------------------------------------------------------------------------- mnemonic_split
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMnemonicSplit {
+    /// Phrase to split
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Word count
+    pub word_count: Option<u8>,
+    /// Number of shares required to restore the phrase. Must be in range 1..=`share_count`
+    pub threshold: u8,
+    /// Total number of shares. Maximum is 16
+    pub share_count: u8,
+    /// SLIP-0039 passphrase used to encrypt the entropy. Default is empty.
+    pub passphrase: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMnemonicSplit {
+    /// SLIP-0039 share mnemonics
+    pub shares: Vec<String>,
+}
+
+/// Splits the phrase into SLIP-0039 shares
+///
+/// Entropy of the phrase is split with Shamir's secret sharing into `share_count`
+/// share mnemonics of the SLIP-0039 dictionary. Any `threshold` of them restore the phrase
+/// with `mnemonic_combine`. Entropy of odd length (24-word TON phrase) is padded with zero byte.
+#[api_function]
+pub fn mnemonic_split(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfMnemonicSplit,
+) -> ClientResult<ResultOfMnemonicSplit> {
+    let mnemonic = mnemonics(&context.config.crypto, params.dictionary, params.word_count)?;
+    let mut entropy = mnemonic.raw_entropy_from_phrase(&params.phrase)?;
+    if entropy.len() % 2 != 0 {
+        entropy.0.push(0);
+    }
+    Ok(ResultOfMnemonicSplit {
+        shares: slip39::split(
+            &entropy,
+            params.passphrase.as_deref().unwrap_or_default(),
+            params.threshold,
+            params.share_count,
+        )?,
+    })
+}
+
+//-------------------------------------------------------------------------------- mnemonic_combine
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMnemonicCombine {
+    /// SLIP-0039 share mnemonics produced by `mnemonic_split`
+    pub shares: Vec<String>,
+    /// Dictionary identifier of the restored phrase
+    pub dictionary: Option<u8>,
+    /// Word count of the restored phrase
+    pub word_count: Option<u8>,
+    /// SLIP-0039 passphrase passed to `mnemonic_split`. Default is empty.
+    pub passphrase: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMnemonicCombine {
+    /// Restored phrase
+    pub phrase: String,
+}
+
+/// Restores the phrase from SLIP-0039 shares
+///
+/// Dictionary and word count must be the same as passed to `mnemonic_split`.
+#[api_function]
+pub fn mnemonic_combine(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfMnemonicCombine,
+) -> ClientResult<ResultOfMnemonicCombine> {
+    let mnemonic = mnemonics(&context.config.crypto, params.dictionary, params.word_count)?;
+    let entropy = slip39::combine(
+        &params.shares,
+        params.passphrase.as_deref().unwrap_or_default(),
+    )?;
+    let size = mnemonic.raw_entropy_size();
+    if entropy.len() != size + size % 2 || entropy[size..].iter().any(|byte| *byte != 0) {
+        return Err(crypto::Error::slip39_invalid_share(format!(
+            "restored secret does not match the dictionary and word count, expected {} bytes",
+            size
+        )));
+    }
+    Ok(ResultOfMnemonicCombine {
+        phrase: mnemonic.phrase_from_entropy(&entropy[..size])?,
+    })
+}
+
+//-------------------------------------------------------------------------------- mnemonic_suggest
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMnemonicSuggest {
+    /// Phrase. Words may be incomplete or mistyped.
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Maximum edit distance of the suggested words. Default is 2.
+    pub max_distance: Option<u32>,
+    /// Maximum number of suggestions for each word. Default is 5.
+    pub max_suggestions: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, PartialEq)]
+pub struct MnemonicWordSuggestions {
+    /// Word of the phrase
+    pub word: String,
+    /// Flag indicating if the word belongs to the dictionary
+    pub valid: bool,
+    /// Dictionary words that complete the word or are closest to it by edit distance,
+    /// the most likely first
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMnemonicSuggest {
+    /// Suggestions for each word of the phrase
+    pub words: Vec<MnemonicWordSuggestions>,
+}
+
+/// Suggests dictionary words for each word of the phrase
+///
+/// Dictionary words starting with the entered word go first, followed by the words
+/// within `max_distance` edits (insertion, deletion, substitution or transposition
+/// of adjacent letters). The checksum of the phrase is not checked, use `mnemonic_repair`
+/// to find the valid phrases.
+#[api_function]
+pub fn mnemonic_suggest(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfMnemonicSuggest,
+) -> ClientResult<ResultOfMnemonicSuggest> {
+    let mnemonic = mnemonics(
+        &context.config.crypto,
+        params.dictionary,
+        Some(context.config.crypto.mnemonic_word_count),
+    )?;
+    let dictionary = mnemonic.get_words()?;
+    let dictionary: Vec<&str> = dictionary.split(' ').collect();
+    let max_distance = params.max_distance.unwrap_or(DEFAULT_MAX_EDIT_DISTANCE) as usize;
+    let max_suggestions = params.max_suggestions.unwrap_or(DEFAULT_MAX_SUGGESTIONS) as usize;
+    Ok(ResultOfMnemonicSuggest {
+        words: params
+            .phrase
+            .split_whitespace()
+            .map(|word| MnemonicWordSuggestions {
+                word: word.to_string(),
+                valid: dictionary.iter().any(|known| *known == word),
+                suggestions: word_candidates(&dictionary, word, max_distance)
+                    .into_iter()
+                    .take(max_suggestions)
+                    .map(|(candidate, _)| candidate.to_string())
+                    .collect(),
+            })
+            .collect(),
+    })
+}
+
+//--------------------------------------------------------------------------------- mnemonic_repair
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMnemonicRepair {
+    /// Phrase with mistyped words
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Word count
+    pub word_count: Option<u8>,
+    /// Maximum edit distance of the substituted words. Default is 2.
+    pub max_distance: Option<u32>,
+    /// Maximum number of returned phrases. Default is 10.
+    pub max_phrases: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMnemonicRepair {
+    /// Valid phrases, the closest to the original phrase first.
+    /// Contains the original phrase only if it is valid.
+    pub phrases: Vec<String>,
+}
+
+/// Repairs a mnemonic phrase with mistyped words
+///
+/// Words missing from the dictionary are substituted with the candidates suggested by
+/// `mnemonic_suggest`. If all the words are in the dictionary, but the checksum does not
+/// match, each word in turn is substituted with the words within `max_distance` edits.
+/// Only the phrases that pass validation are returned.
+///
+/// A short checksum can be matched by several phrases, so the result must be confirmed by
+/// the user, for example by the address derived from the phrase.
+///
+/// The number of the checked phrases is limited, otherwise `MnemonicRepairTooManyCandidates`
+/// error is returned. The limit is 10000 phrases, for the TON dictionary validation is much
+/// slower and the limit is 40 phrases per word and per unit of `max_distance`, e.g. 1920
+/// phrases for 24 words and the default distance.
+#[api_function]
+pub fn mnemonic_repair(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfMnemonicRepair,
+) -> ClientResult<ResultOfMnemonicRepair> {
+    let mnemonic = mnemonics(&context.config.crypto, params.dictionary, params.word_count)?;
+    let word_count = params.word_count.unwrap_or(context.config.crypto.mnemonic_word_count);
+    let words: Vec<&str> = params.phrase.split_whitespace().collect();
+    if words.len() != word_count as usize {
+        return Err(crypto::Error::bip39_invalid_word_count(
+            words.len().min(u8::MAX as usize) as u8,
+        ));
+    }
+    let dictionary = mnemonic.get_words()?;
+    let dictionary: Vec<&str> = dictionary.split(' ').collect();
+    let max_distance = params.max_distance.unwrap_or(DEFAULT_MAX_EDIT_DISTANCE) as usize;
+    let max_phrases = params.max_phrases.unwrap_or(DEFAULT_MAX_REPAIRED_PHRASES) as usize;
+    let max_attempts =
+        if params.dictionary.unwrap_or(context.config.crypto.mnemonic_dictionary) == TON_DICTIONARY {
+            (MAX_TON_REPAIR_ATTEMPTS_PER_WORD_AND_DISTANCE * words.len())
+                .saturating_mul(max_distance.max(1))
+                .min(MAX_REPAIR_ATTEMPTS)
+        } else {
+            MAX_REPAIR_ATTEMPTS
+        };
+
+    // Each attempt is the list of substitutions: word index, candidate and its distance
+    let unknown: Vec<usize> = (0..words.len())
+        .filter(|i| !dictionary.iter().any(|known| *known == words[*i]))
+        .collect();
+    let attempts: Vec<Vec<(usize, &str, usize)>> = if unknown.is_empty() {
+        if mnemonic.is_phrase_valid(&words.join(" "))? {
+            return Ok(ResultOfMnemonicRepair {
+                phrases: vec![words.join(" ")],
+            });
+        }
+        let mut attempts = Vec::new();
+        for (i, word) in words.iter().enumerate() {
+            for (candidate, distance) in word_candidates(&dictionary, word, max_distance) {
+                attempts.push(vec![(i, candidate, distance)]);
+            }
+        }
+        attempts
+    } else {
+        let mut attempts = vec![Vec::new()];
+        for i in unknown {
+            let candidates = word_candidates(&dictionary, words[i], max_distance);
+            if attempts.len() * candidates.len() > max_attempts {
+                return Err(crypto::Error::mnemonic_repair_too_many_candidates(
+                    attempts.len() * candidates.len(),
+                    max_attempts,
+                ));
+            }
+            attempts = attempts
+                .into_iter()
+                .flat_map(|attempt: Vec<(usize, &str, usize)>| {
+                    candidates.iter().map(move |(candidate, distance)| {
+                        let mut attempt = attempt.clone();
+                        attempt.push((i, *candidate, *distance));
+                        attempt
+                    })
+                })
+                .collect();
+        }
+        attempts
+    };
+    if attempts.len() > max_attempts {
+        return Err(crypto::Error::mnemonic_repair_too_many_candidates(
+            attempts.len(),
+            max_attempts,
+        ));
+    }
+
+    let mut repaired = Vec::new();
+    for attempt in attempts {
+        let mut phrase = words.clone();
+        for (i, candidate, _) in &attempt {
+            phrase[*i] = *candidate;
+        }
+        let phrase = phrase.join(" ");
+        if mnemonic.is_phrase_valid(&phrase)? {
+            let distance: usize = attempt.iter().map(|(_, _, distance)| distance).sum();
+            repaired.push((distance, phrase));
+        }
+    }
+    // Stable sort keeps the word order for the phrases of the same distance
+    repaired.sort_by_key(|(distance, _)| *distance);
+
+    Ok(ResultOfMnemonicRepair {
+        phrases: repaired
+            .into_iter()
+            .take(max_phrases)
+            .map(|(_, phrase)| phrase)
+            .collect(),
+    })
+}
+
+/// Dictionary words that may be meant by `word` with their distance to it,
+/// the closest first. The word itself is not included.
+fn word_candidates<'a>(
+    dictionary: &[&'a str],
+    word: &str,
+    max_distance: usize,
+) -> Vec<(&'a str, usize)> {
+    let normalized = word.to_lowercase();
+    let length = normalized.chars().count();
+    let mut candidates: Vec<(&'a str, usize)> = dictionary
+        .iter()
+        .filter(|candidate| **candidate != word)
+        .filter_map(|candidate| {
+            // Incomplete word is counted as a single typo
+            if !normalized.is_empty() && candidate.starts_with(&normalized) {
+                let distance = if candidate.len() == normalized.len() { 0 } else { 1 };
+                return Some((*candidate, distance));
+            }
+            if (candidate.chars().count() as isize - length as isize).abs() as usize > max_distance {
+                return None;
+            }
+            let distance = edit_distance(&normalized, candidate);
+            if distance <= max_distance {
+                Some((*candidate, distance))
+            } else {
+                None
+            }
+        })
+        .collect();
+    candidates.sort_by_key(|(_, distance)| *distance);
+    candidates
+}
+
+/// Optimal string alignment distance: the number of insertions, deletions, substitutions
+/// and transpositions of adjacent characters
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in 0..=a.len() {
+        d[i][0] = i;
+    }
+    for j in 0..=b.len() {
+        d[0][j] = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
 }
 
 // Internals
@@ -230,12 +610,17 @@ pub trait CryptoMnemonic {
         &self,
         config: &CryptoConfig,
         phrase: &String,
+        passphrase: &str,
         path: &String,
+        scheme: HDKeyDerivationScheme,
     ) -> ClientResult<KeyPair>;
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String>;
     fn is_phrase_valid(&self, phrase: &String) -> ClientResult<bool>;
     fn seed_from_phrase_and_salt(&self, phrase: &String, salt: &String) -> ClientResult<String>;
     fn entropy_from_phrase(&self, phrase: &String) -> ClientResult<String>;
+    /// Entropy that restores the phrase with `phrase_from_entropy`
+    fn raw_entropy_from_phrase(&self, phrase: &String) -> ClientResult<SecretBuf>;
+    fn raw_entropy_size(&self) -> usize;
 }
 
 pub(super) fn check_phrase(mnemonic: &dyn CryptoMnemonic, phrase: &String) -> ClientResult<()> {
@@ -292,11 +677,13 @@ impl CryptoMnemonic for Bip39Mnemonic {
         &self,
         _config: &CryptoConfig,
         phrase: &String,
+        passphrase: &str,
         path: &String,
+        scheme: HDKeyDerivationScheme,
     ) -> ClientResult<KeyPair> {
         check_phrase(self, phrase)?;
-        let derived =
-            HDPrivateKey::from_mnemonic(phrase)?.derive_path(path, default_hdkey_compliant())?;
+        let derived = HDPrivateKey::from_mnemonic(phrase, passphrase, scheme)?
+            .derive_path(path, default_hdkey_compliant())?;
         ed25519_keys_from_secret_bytes(&derived.secret())
     }
 
@@ -333,6 +720,17 @@ impl CryptoMnemonic for Bip39Mnemonic {
             .map_err(|err| crypto::Error::bip39_invalid_phrase(err))?;
         Ok(hex::encode(mnemonic.entropy()))
     }
+
+    fn raw_entropy_from_phrase(&self, phrase: &String) -> ClientResult<SecretBuf> {
+        check_phrase(self, phrase)?;
+        let mnemonic = Mnemonic::from_phrase(phrase, self.language)
+            .map_err(|err| crypto::Error::bip39_invalid_phrase(err))?;
+        Ok(SecretBuf(mnemonic.entropy().to_vec()))
+    }
+
+    fn raw_entropy_size(&self) -> usize {
+        self.mnemonic_type.entropy_bits() / 8
+    }
 }
 
 pub(crate) struct TonMnemonic {
@@ -411,12 +809,22 @@ impl CryptoMnemonic for TonMnemonic {
         &self,
         _config: &CryptoConfig,
         phrase: &String,
+        passphrase: &str,
         path: &String,
+        scheme: HDKeyDerivationScheme,
     ) -> ClientResult<KeyPair> {
+        if !passphrase.is_empty() {
+            return Err(crypto::Error::mnemonic_passphrase_not_supported(TON_DICTIONARY));
+        }
         check_phrase(self, phrase)?;
 
         let seed = Self::seed_from_string(&phrase, "TON default seed", 100_000);
-        let master = HDPrivateKey::master(&key256(&seed[32..])?, &key256(&seed[..32])?);
+        let master = match scheme {
+            HDKeyDerivationScheme::Bip32 => {
+                HDPrivateKey::master(&key256(&seed[32..])?, &key256(&seed[..32])?)
+            }
+            HDKeyDerivationScheme::Slip10Ed25519 => HDPrivateKey::from_seed(&seed, scheme)?,
+        };
         let derived = master.derive_path(path, default_hdkey_compliant())?;
         ed25519_keys_from_secret_bytes(&derived.secret())
     }
@@ -452,6 +860,28 @@ impl CryptoMnemonic for TonMnemonic {
         check_phrase(self, phrase)?;
         Ok(hex::encode(Self::entropy_from_string(&phrase).as_ref()))
     }
+
+    fn raw_entropy_from_phrase(&self, phrase: &String) -> ClientResult<SecretBuf> {
+        check_phrase(self, phrase)?;
+        let mut bytes = vec![0u8; self.raw_entropy_size()];
+        for (i, word) in phrase.split(" ").enumerate() {
+            let word_i = TON_WORDS
+                .iter()
+                .position(|w| *w == word)
+                .ok_or_else(|| crypto::Error::bip39_invalid_phrase(phrase))?;
+            for j in 0usize..11 {
+                if word_i & (1 << j) != 0 {
+                    let offset = i * 11 + j;
+                    bytes[offset / 8] |= (1 << (offset & 7)) as u8;
+                }
+            }
+        }
+        Ok(SecretBuf(bytes))
+    }
+
+    fn raw_entropy_size(&self) -> usize {
+        (self.word_count as usize * 11 + 7) / 8
+    }
 }
 
 const TON_WORDS: [&str; 2048] = [
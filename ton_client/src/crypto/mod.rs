@@ -17,10 +17,14 @@ mod errors;
 pub(crate) mod hash;
 pub(crate) mod hdkey;
 pub(crate) mod internal;
+pub(crate) mod kdf;
 pub(crate) mod keys;
+pub(crate) mod keystore;
 pub(crate) mod math;
 pub(crate) mod mnemonic;
 pub(crate) mod nacl;
+pub(crate) mod secp256k1;
+pub(crate) mod slip39;
 
 pub use errors::{Error, ErrorCode};
 pub(crate) mod encryption;
@@ -29,15 +33,20 @@ mod tests;
 
 pub use crate::crypto::boxes::crypto_box::{
     create_crypto_box, remove_crypto_box, get_crypto_box_info, get_crypto_box_seed_phrase,
+    change_crypto_box_password, ParamsOfChangeCryptoBoxPassword, ResultOfChangeCryptoBoxPassword,
     get_signing_box_from_crypto_box, get_encryption_box_from_crypto_box, clear_crypto_box_secret_cache,
     ParamsOfCreateCryptoBox, ParamsOfGetSigningBoxFromCryptoBox, CryptoBoxHandle, RegisteredCryptoBox,
-    AppPasswordProvider, CryptoBoxSecret, BoxEncryptionAlgorithm,
+    AppPasswordProvider, CryptoBoxSecret, BoxEncryptionAlgorithm, SecretEncryptionKdf,
     ResultOfGetCryptoBoxInfo, ResultOfGetCryptoBoxSeedPhrase, ResultOfGetPassword,
 };
+pub use crate::crypto::boxes::policy_signing_box::{
+    get_policy_signing_box, ParamsOfGetPolicySigningBox, SigningBoxPolicy, SigningBoxRateLimit,
+};
 pub use crate::crypto::boxes::signing_box::{
-    get_signing_box, register_signing_box, remove_signing_box, signing_box_get_public_key,
-    signing_box_sign, ParamsOfSigningBoxSign, RegisteredSigningBox, ResultOfSigningBoxGetPublicKey,
-    ResultOfSigningBoxSign, SigningBox, SigningBoxHandle,
+    get_secp256k1_signing_box, get_signing_box, register_signing_box, remove_signing_box,
+    signing_box_get_public_key, signing_box_sign, ParamsOfGetSecp256k1SigningBox,
+    ParamsOfSigningBoxSign, RegisteredSigningBox, ResultOfSigningBoxGetPublicKey,
+    ResultOfSigningBoxSign, SigningBox, SigningBoxHandle, SigningBoxMessage,
 };
 pub use crate::crypto::boxes::encryption_box::{
     register_encryption_box, remove_encryption_box, create_encryption_box,
@@ -47,6 +56,14 @@ pub use crate::crypto::boxes::encryption_box::{
     ParamsOfEncryptionBoxGetInfo, ResultOfEncryptionBoxGetInfo,
     ParamsOfEncryptionBoxEncrypt, ResultOfEncryptionBoxEncrypt,
     ParamsOfEncryptionBoxDecrypt, ResultOfEncryptionBoxDecrypt,
+    encryption_box_encrypt_init, encryption_box_encrypt_update, encryption_box_encrypt_finish,
+    encryption_box_decrypt_init, encryption_box_decrypt_update, encryption_box_decrypt_finish,
+    EncryptionStream, EncryptionStreamHandle, RegisteredEncryptionStream,
+    ParamsOfEncryptionBoxEncryptInit, ParamsOfEncryptionBoxDecryptInit,
+    ParamsOfEncryptionStreamUpdate, ResultOfEncryptionStreamUpdate, ResultOfEncryptionStreamFinish,
+};
+pub use crate::crypto::boxes::encryption_box::aead::{
+    AeadEncryptionBox, AesGcmParamsEB, ChaCha20Poly1305ParamsEB,
 };
 pub use crate::crypto::boxes::encryption_box::aes::{AesInfo, AesParamsEB, AesEncryptionBox};
 pub use crate::crypto::boxes::encryption_box::chacha20::{ChaCha20ParamsEB, ChaCha20EncryptionBox};
@@ -55,30 +72,51 @@ pub use crate::crypto::boxes::encryption_box::nacl_secret_box::{NaclSecretBoxPar
 pub use crate::crypto::boxes::crypto_box::ChaCha20ParamsCB;
 pub use crate::crypto::boxes::crypto_box::NaclBoxParamsCB;
 pub use crate::crypto::boxes::crypto_box::NaclSecretBoxParamsCB;
-pub use crate::crypto::encscrypt::{scrypt, ParamsOfScrypt, ResultOfScrypt};
-pub use crate::crypto::hash::{sha256, sha512, ParamsOfHash, ResultOfHash};
+pub use crate::crypto::boxes::crypto_box::AesGcmParamsCB;
+pub use crate::crypto::boxes::crypto_box::ChaCha20Poly1305ParamsCB;
+pub use crate::crypto::encscrypt::{
+    argon2id, scrypt, ParamsOfArgon2id, ParamsOfScrypt, ResultOfArgon2id, ResultOfScrypt,
+};
+pub use crate::crypto::hash::{
+    blake2b, hmac_sha256, hmac_sha512, keccak256, sha256, sha3_256, sha3_512, sha512,
+    ParamsOfBlake2b, ParamsOfHash, ParamsOfHmac, ResultOfHash, ResultOfHmac,
+};
+pub use crate::crypto::kdf::{
+    hkdf_expand, hkdf_extract, HkdfHashAlgorithm, ParamsOfHkdfExpand, ParamsOfHkdfExtract,
+    ResultOfHkdfExpand, ResultOfHkdfExtract,
+};
 pub use crate::crypto::hdkey::{
     hdkey_derive_from_xprv, hdkey_derive_from_xprv_path, hdkey_public_from_xprv,
-    hdkey_secret_from_xprv, hdkey_xprv_from_mnemonic, ParamsOfHDKeyDeriveFromXPrv,
+    hdkey_secret_from_xprv, hdkey_xprv_from_mnemonic, HDKeyDerivationScheme, ParamsOfHDKeyDeriveFromXPrv,
     ParamsOfHDKeyDeriveFromXPrvPath, ParamsOfHDKeyPublicFromXPrv, ParamsOfHDKeySecretFromXPrv,
     ParamsOfHDKeyXPrvFromMnemonic, ResultOfHDKeyDeriveFromXPrv, ResultOfHDKeyDeriveFromXPrvPath,
     ResultOfHDKeyPublicFromXPrv, ResultOfHDKeySecretFromXPrv, ResultOfHDKeyXPrvFromMnemonic,
 };
 pub use crate::crypto::keys::{
+    convert_ed25519_to_x25519_public, convert_ed25519_to_x25519_secret,
     convert_public_key_to_ton_safe_format, generate_random_sign_keys, sign, verify_signature,
-    KeyPair, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign, ParamsOfVerifySignature,
+    KeyPair, ParamsOfConvertEd25519ToX25519Public, ParamsOfConvertEd25519ToX25519Secret,
+    ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign, ParamsOfVerifySignature,
+    ResultOfConvertEd25519ToX25519Public, ResultOfConvertEd25519ToX25519Secret,
     ResultOfConvertPublicKeyToTonSafeFormat, ResultOfSign, ResultOfVerifySignature,
 };
+pub use crate::crypto::keystore::{
+    export_keystore, import_keystore, KeystoreCipher, KeystoreKdfParams, KeystoreSecret,
+    ParamsOfExportKeystore, ParamsOfImportKeystore, ResultOfExportKeystore, ResultOfImportKeystore,
+};
 pub use crate::crypto::math::{
     factorize, generate_random_bytes, modular_power, ton_crc16, ParamsOfFactorize,
     ParamsOfGenerateRandomBytes, ParamsOfModularPower, ParamsOfTonCrc16, ResultOfFactorize,
     ResultOfGenerateRandomBytes, ResultOfModularPower, ResultOfTonCrc16,
 };
 pub use crate::crypto::mnemonic::{
-    mnemonic_derive_sign_keys, mnemonic_from_entropy, mnemonic_from_random, mnemonic_verify,
-    mnemonic_words, ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy,
-    ParamsOfMnemonicFromRandom, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
-    ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom, ResultOfMnemonicVerify,
+    mnemonic_combine, mnemonic_derive_sign_keys, mnemonic_from_entropy, mnemonic_from_random,
+    mnemonic_repair, mnemonic_split, mnemonic_suggest, mnemonic_verify, mnemonic_words,
+    MnemonicWordSuggestions, ParamsOfMnemonicCombine, ParamsOfMnemonicDeriveSignKeys,
+    ParamsOfMnemonicFromEntropy, ParamsOfMnemonicFromRandom, ParamsOfMnemonicRepair,
+    ParamsOfMnemonicSplit, ParamsOfMnemonicSuggest, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
+    ResultOfMnemonicCombine, ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom,
+    ResultOfMnemonicRepair, ResultOfMnemonicSplit, ResultOfMnemonicSuggest, ResultOfMnemonicVerify,
     ResultOfMnemonicWords,
 };
 pub use crate::crypto::nacl::{
@@ -91,6 +129,12 @@ pub use crate::crypto::nacl::{
     ResultOfNaclBox, ResultOfNaclBoxOpen, ResultOfNaclSign, ResultOfNaclSignDetached,
     ResultOfNaclSignDetachedVerify, ResultOfNaclSignOpen,
 };
+pub use crate::crypto::secp256k1::{
+    secp256k1_generate_random_keys, secp256k1_public_key, secp256k1_recover, secp256k1_sign,
+    secp256k1_verify, ParamsOfSecp256k1PublicKey, ParamsOfSecp256k1Recover, ParamsOfSecp256k1Sign,
+    ParamsOfSecp256k1Verify, ResultOfSecp256k1PublicKey, ResultOfSecp256k1Sign,
+    ResultOfSecp256k1Verify,
+};
 pub use encryption::{chacha20, ParamsOfChaCha20, ResultOfChaCha20};
 
 use serde::{Deserialize, Deserializer};
@@ -389,19 +389,29 @@ pub fn nacl_secret_box(
     _context: std::sync::Arc<ClientContext>,
     params: ParamsOfNaclSecretBox,
 ) -> ClientResult<ResultOfNaclBox> {
-    let (mut padded_output, padded_input, nonce, key) = prepare_to_convert(
+    let encrypted = nacl_secret_box_internal(
         &base64_decode(&params.decrypted)?,
         &hex_decode(&params.nonce)?,
         &hex_decode_secret(&params.key)?,
-        32,
     )?;
+    Ok(ResultOfNaclBox {
+        encrypted: base64::encode(&encrypted),
+    })
+}
+
+pub(crate) fn nacl_secret_box_internal(
+    decrypted: &[u8],
+    nonce: &[u8],
+    key: &[u8],
+) -> ClientResult<Vec<u8>> {
+    let (mut padded_output, padded_input, nonce, key) =
+        prepare_to_convert(decrypted, nonce, key, 32)?;
 
     sodalite::secretbox(&mut padded_output, &padded_input, &nonce, &key.0)
         .map_err(|_| crypto::Error::nacl_secret_box_failed("secret box failed"))?;
     padded_output.drain(..16);
-    Ok(ResultOfNaclBox {
-        encrypted: base64::encode(&padded_output),
-    })
+
+    Ok(padded_output)
 }
 
 //---------------------------------------------------------------------------- nacl_secret_box_open
@@ -424,19 +434,29 @@ pub fn nacl_secret_box_open(
     _context: std::sync::Arc<ClientContext>,
     params: ParamsOfNaclSecretBoxOpen,
 ) -> ClientResult<ResultOfNaclBoxOpen> {
-    let (mut padded_output, padded_input, nonce, key) = prepare_to_convert(
+    let decrypted = nacl_secret_box_open_internal(
         &base64_decode(&params.encrypted)?,
         &hex_decode(&params.nonce)?,
         &hex_decode_secret(&params.key)?,
-        16,
     )?;
+    Ok(ResultOfNaclBoxOpen {
+        decrypted: base64::encode(&decrypted),
+    })
+}
+
+pub(crate) fn nacl_secret_box_open_internal(
+    encrypted: &[u8],
+    nonce: &[u8],
+    key: &[u8],
+) -> ClientResult<Vec<u8>> {
+    let (mut padded_output, padded_input, nonce, key) =
+        prepare_to_convert(encrypted, nonce, key, 16)?;
 
     sodalite::secretbox_open(&mut padded_output, &padded_input, &nonce, &key.0)
         .map_err(|_| crypto::Error::nacl_secret_box_failed("secret box open failed"))?;
     padded_output.drain(..32);
-    Ok(ResultOfNaclBoxOpen {
-        decrypted: base64::encode(&padded_output),
-    })
+
+    Ok(padded_output)
 }
 
 // Internals
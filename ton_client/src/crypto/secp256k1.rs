@@ -0,0 +1,227 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::internal::{hex_decode_secret_const, key_from_slice};
+use crate::crypto::KeyPair;
+use crate::encoding::hex_decode;
+use crate::error::ClientResult;
+use libsecp256k1::{Message, PublicKey, RecoveryId, SecretKey, Signature};
+use rand::RngCore;
+
+pub(crate) fn decode_secp256k1_secret(secret: &String) -> ClientResult<SecretKey> {
+    let bytes = hex_decode_secret_const::<32>(secret)?;
+    SecretKey::parse_slice(&bytes)
+        .map_err(|err| crypto::Error::invalid_secret_key(err, &"secp256k1 secret".to_string()))
+}
+
+fn decode_public(public: &String) -> ClientResult<PublicKey> {
+    PublicKey::parse_slice(&hex_decode(public)?, None)
+        .map_err(|err| crypto::Error::invalid_public_key(err, public))
+}
+
+fn decode_hash(hash: &String) -> ClientResult<Message> {
+    let hash = key_from_slice::<32>(&hex_decode(hash)?)?;
+    Ok(Message::parse(&hash.0))
+}
+
+/// Accepts both 64-byte `r || s` and 65-byte `r || s || v` signatures.
+fn decode_signature(signature: &String) -> ClientResult<(Signature, Option<u8>)> {
+    let bytes = hex_decode(signature)?;
+    let recovery_id = match bytes.len() {
+        64 => None,
+        65 => Some(bytes[64]),
+        len => {
+            return Err(crypto::Error::invalid_signature(
+                format!("invalid signature size {}, expected 64 or 65 bytes", len),
+                signature,
+            ))
+        }
+    };
+    let signature = Signature::parse_standard_slice(&bytes[..64])
+        .map_err(|err| crypto::Error::invalid_signature(err, signature))?;
+    Ok((signature, recovery_id))
+}
+
+/// Signs 32-byte hash and returns `r || s` signature with recovery id.
+pub(crate) fn secp256k1_sign_hash(hash: &[u8], secret: &SecretKey) -> ClientResult<([u8; 64], u8)> {
+    let hash = key_from_slice::<32>(hash)?;
+    let (signature, recovery_id) = libsecp256k1::sign(&Message::parse(&hash.0), secret);
+    Ok((signature.serialize(), recovery_id.serialize()))
+}
+
+//------------------------------------------------------------------ secp256k1_generate_random_keys
+
+/// Generates random secp256k1 key pair.
+///
+/// Public key is returned in the compressed form (33 bytes).
+#[api_function]
+pub fn secp256k1_generate_random_keys(
+    _context: std::sync::Arc<ClientContext>,
+) -> ClientResult<KeyPair> {
+    let mut rng = rand::thread_rng();
+    let mut bytes = crate::crypto::internal::Key256::default();
+    let secret = loop {
+        rng.fill_bytes(&mut bytes.0);
+        if let Ok(secret) = SecretKey::parse(&bytes.0) {
+            break secret;
+        }
+    };
+    Ok(KeyPair::new(
+        hex::encode(PublicKey::from_secret_key(&secret).serialize_compressed()),
+        hex::encode(secret.serialize()),
+    ))
+}
+
+//--------------------------------------------------------------------------- secp256k1_public_key
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1PublicKey {
+    /// Public or secret key encoded in `hex`.
+    ///
+    /// Secret key is 32 bytes, public key is 33 bytes (compressed) or 65 bytes (uncompressed).
+    pub key: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1PublicKey {
+    /// Compressed public key (33 bytes) encoded in `hex`.
+    pub public: String,
+    /// Uncompressed public key (65 bytes) encoded in `hex`.
+    pub public_uncompressed: String,
+}
+
+/// Returns secp256k1 public key in the compressed and uncompressed forms.
+#[api_function]
+pub fn secp256k1_public_key(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSecp256k1PublicKey,
+) -> ClientResult<ResultOfSecp256k1PublicKey> {
+    let public = if hex_decode(&params.key)?.len() == 32 {
+        PublicKey::from_secret_key(&decode_secp256k1_secret(&params.key)?)
+    } else {
+        decode_public(&params.key)?
+    };
+    Ok(ResultOfSecp256k1PublicKey {
+        public: hex::encode(public.serialize_compressed()),
+        public_uncompressed: hex::encode(public.serialize()),
+    })
+}
+
+//--------------------------------------------------------------------------------- secp256k1_sign
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1Sign {
+    /// Hash of the data (32 bytes) encoded in `hex`.
+    pub hash: String,
+    /// Secret key (32 bytes) encoded in `hex`.
+    pub secret: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1Sign {
+    /// Signature `r || s` (64 bytes) encoded in `hex`.
+    pub signature: String,
+    /// Recovery id (0 or 1).
+    pub recovery_id: u8,
+}
+
+/// Signs the hash using ECDSA over secp256k1.
+///
+/// Signature is normalized to the lower `s` value. Ethereum-style `v` value
+/// is `27 + recovery_id`.
+#[api_function]
+pub fn secp256k1_sign(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSecp256k1Sign,
+) -> ClientResult<ResultOfSecp256k1Sign> {
+    let secret = decode_secp256k1_secret(&params.secret)?;
+    let (signature, recovery_id) = secp256k1_sign_hash(&hex_decode(&params.hash)?, &secret)?;
+    Ok(ResultOfSecp256k1Sign {
+        signature: hex::encode(signature),
+        recovery_id,
+    })
+}
+
+//------------------------------------------------------------------------------- secp256k1_verify
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1Verify {
+    /// Hash of the data (32 bytes) encoded in `hex`.
+    pub hash: String,
+    /// Signature `r || s` (64 bytes) or `r || s || v` (65 bytes) encoded in `hex`.
+    pub signature: String,
+    /// Compressed (33 bytes) or uncompressed (65 bytes) public key encoded in `hex`.
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1Verify {
+    /// `true` if the signature is valid.
+    pub succeeded: bool,
+}
+
+/// Verifies ECDSA secp256k1 signature of the hash.
+///
+/// Signatures with the higher `s` value (greater than the half of the curve order)
+/// are rejected to prevent the signature malleability.
+#[api_function]
+pub fn secp256k1_verify(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSecp256k1Verify,
+) -> ClientResult<ResultOfSecp256k1Verify> {
+    let hash = decode_hash(&params.hash)?;
+    let (signature, _) = decode_signature(&params.signature)?;
+    let public = decode_public(&params.public)?;
+    Ok(ResultOfSecp256k1Verify {
+        succeeded: !signature.s.is_high() && libsecp256k1::verify(&hash, &signature, &public),
+    })
+}
+
+//------------------------------------------------------------------------------ secp256k1_recover
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1Recover {
+    /// Hash of the data (32 bytes) encoded in `hex`.
+    pub hash: String,
+    /// Signature `r || s` (64 bytes) or `r || s || v` (65 bytes) encoded in `hex`.
+    pub signature: String,
+    /// Recovery id (0 or 1). Can be omitted if the signature contains `v` value
+    /// (recovery id itself or Ethereum-style `27 + recovery_id`).
+    pub recovery_id: Option<u8>,
+}
+
+/// Recovers signer's public key from ECDSA secp256k1 signature of the hash.
+#[api_function]
+pub fn secp256k1_recover(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSecp256k1Recover,
+) -> ClientResult<ResultOfSecp256k1PublicKey> {
+    let hash = decode_hash(&params.hash)?;
+    let (signature, v) = decode_signature(&params.signature)?;
+    let recovery_id = params
+        .recovery_id
+        .or(v.map(|v| if v >= 27 { v - 27 } else { v }))
+        .ok_or_else(|| {
+            crypto::Error::invalid_signature("recovery id is required", &params.signature)
+        })?;
+    let recovery_id = RecoveryId::parse(recovery_id)
+        .map_err(|err| crypto::Error::invalid_signature(err, &params.signature))?;
+    let public = libsecp256k1::recover(&hash, &signature, &recovery_id)
+        .map_err(|err| crypto::Error::invalid_signature(err, &params.signature))?;
+    Ok(ResultOfSecp256k1PublicKey {
+        public: hex::encode(public.serialize_compressed()),
+        public_uncompressed: hex::encode(public.serialize()),
+    })
+}
@@ -0,0 +1,546 @@
+/*
+* Copyright 2018-2021 TON Labs LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+//! SLIP-0039 Shamir's secret sharing for a single group of member shares.
+
+use crate::crypto;
+use crate::crypto::internal::SecretBuf;
+use crate::error::ClientResult;
+use hmac::{Hmac, Mac, NewMac};
+use pbkdf2::pbkdf2;
+use rand::RngCore;
+use sha2::Sha256;
+
+const RADIX_BITS: usize = 10;
+const ITERATION_EXP_BITS: u32 = 4;
+const METADATA_WORDS: usize = 7;
+const CHECKSUM_WORDS: usize = 3;
+const DIGEST_LENGTH: usize = 4;
+const CUSTOMIZATION: &[u8] = b"shamir";
+const EXTENDABLE_CUSTOMIZATION: &[u8] = b"shamir_extendable";
+const MIN_SECRET_LENGTH: usize = 16;
+const MAX_SHARE_COUNT: u8 = 16;
+const BASE_ITERATION_COUNT: u32 = 10000;
+const ROUND_COUNT: u8 = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const DEFAULT_ITERATION_EXP: u8 = 1;
+
+struct Share {
+    identifier: u16,
+    extendable: bool,
+    iteration_exp: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: SecretBuf,
+}
+
+/// Splits the master secret into `share_count` share mnemonics,
+/// any `threshold` of which are required to restore the secret.
+pub(crate) fn split(
+    master_secret: &[u8],
+    passphrase: &str,
+    threshold: u8,
+    share_count: u8,
+) -> ClientResult<Vec<String>> {
+    if master_secret.len() < MIN_SECRET_LENGTH || master_secret.len() % 2 != 0 {
+        return Err(crypto::Error::slip39_invalid_parameters(format!(
+            "master secret must be at least {} bytes long and have an even length",
+            MIN_SECRET_LENGTH
+        )));
+    }
+    if threshold == 0 || threshold > share_count || share_count > MAX_SHARE_COUNT {
+        return Err(crypto::Error::slip39_invalid_parameters(format!(
+            "threshold {} and share count {} must satisfy 0 < threshold <= share count <= {}",
+            threshold, share_count, MAX_SHARE_COUNT
+        )));
+    }
+    if threshold == 1 && share_count > 1 {
+        return Err(crypto::Error::slip39_invalid_parameters(
+            "multiple shares with threshold 1 are not allowed, use 1-of-1 sharing instead",
+        ));
+    }
+
+    let identifier = (rand::thread_rng().next_u32() & 0x7FFF) as u16;
+    let encrypted = feistel(master_secret, passphrase, identifier, false, DEFAULT_ITERATION_EXP, false);
+    Ok(split_secret(threshold, share_count, &encrypted)?
+        .into_iter()
+        .map(|(member_index, value)| {
+            Share {
+                identifier,
+                extendable: false,
+                iteration_exp: DEFAULT_ITERATION_EXP,
+                group_index: 0,
+                group_threshold: 1,
+                group_count: 1,
+                member_index,
+                member_threshold: threshold,
+                value,
+            }
+            .to_mnemonic()
+        })
+        .collect())
+}
+
+/// Restores the master secret from the share mnemonics.
+pub(crate) fn combine(mnemonics: &[String], passphrase: &str) -> ClientResult<SecretBuf> {
+    let shares = mnemonics
+        .iter()
+        .map(|mnemonic| Share::from_mnemonic(mnemonic))
+        .collect::<ClientResult<Vec<_>>>()?;
+    let first = shares
+        .first()
+        .ok_or_else(|| crypto::Error::slip39_invalid_share("no shares provided"))?;
+    if first.group_count != 1 || first.group_threshold != 1 {
+        return Err(crypto::Error::slip39_invalid_share(
+            "shares of multiple groups are not supported",
+        ));
+    }
+    for share in &shares[1..] {
+        if share.identifier != first.identifier
+            || share.extendable != first.extendable
+            || share.iteration_exp != first.iteration_exp
+            || share.group_index != first.group_index
+            || share.group_threshold != first.group_threshold
+            || share.group_count != first.group_count
+            || share.member_threshold != first.member_threshold
+            || share.value.len() != first.value.len()
+        {
+            return Err(crypto::Error::slip39_invalid_share(
+                "shares belong to different secrets",
+            ));
+        }
+        if shares
+            .iter()
+            .filter(|other| other.member_index == share.member_index)
+            .count()
+            > 1
+        {
+            return Err(crypto::Error::slip39_invalid_share(format!(
+                "duplicate share index {}",
+                share.member_index
+            )));
+        }
+    }
+    if shares.len() < first.member_threshold as usize {
+        return Err(crypto::Error::slip39_invalid_share(format!(
+            "{} shares required, {} provided",
+            first.member_threshold,
+            shares.len()
+        )));
+    }
+
+    let points = shares
+        .iter()
+        .take(first.member_threshold as usize)
+        .map(|share| (share.member_index, share.value.0.as_slice()))
+        .collect::<Vec<_>>();
+    let encrypted = recover_secret(first.member_threshold, &points)?;
+    Ok(feistel(
+        &encrypted,
+        passphrase,
+        first.identifier,
+        first.extendable,
+        first.iteration_exp,
+        true,
+    ))
+}
+
+impl Share {
+    fn to_mnemonic(&self) -> String {
+        let mut words = Vec::new();
+        let id_exp = (self.identifier as u32) << (ITERATION_EXP_BITS + 1)
+            | (self.extendable as u32) << ITERATION_EXP_BITS
+            | self.iteration_exp as u32;
+        words.push((id_exp >> RADIX_BITS) as u16);
+        words.push((id_exp & 0x3FF) as u16);
+        let params = (self.group_index as u32) << 16
+            | ((self.group_threshold - 1) as u32) << 12
+            | ((self.group_count - 1) as u32) << 8
+            | (self.member_index as u32) << 4
+            | (self.member_threshold - 1) as u32;
+        words.push((params >> RADIX_BITS) as u16);
+        words.push((params & 0x3FF) as u16);
+        words.extend(bytes_to_words(&self.value));
+        let checksum = rs1024_checksum(&words, self.extendable);
+        words.extend(checksum.iter());
+        words
+            .into_iter()
+            .map(|index| SLIP39_WORDS[index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn from_mnemonic(mnemonic: &String) -> ClientResult<Self> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                SLIP39_WORDS
+                    .binary_search(&word.to_lowercase().as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| crypto::Error::slip39_invalid_share(format!("unknown word `{}`", word)))
+            })
+            .collect::<ClientResult<Vec<_>>>()?;
+        let min_words = METADATA_WORDS + (MIN_SECRET_LENGTH * 8 + RADIX_BITS - 1) / RADIX_BITS;
+        if words.len() < min_words {
+            return Err(crypto::Error::slip39_invalid_share(format!(
+                "share must contain at least {} words",
+                min_words
+            )));
+        }
+        let padding = (RADIX_BITS * (words.len() - METADATA_WORDS)) % 16;
+        if padding > 8 {
+            return Err(crypto::Error::slip39_invalid_share("invalid share length"));
+        }
+        let id_exp = (words[0] as u32) << RADIX_BITS | words[1] as u32;
+        let extendable = (id_exp >> ITERATION_EXP_BITS) & 1 != 0;
+        if rs1024_polymod(&words, extendable) != 1 {
+            return Err(crypto::Error::slip39_invalid_share("invalid checksum"));
+        }
+
+        let params = (words[2] as u32) << RADIX_BITS | words[3] as u32;
+        let nibble = |shift: u32| ((params >> shift) & 0xF) as u8;
+        let share = Share {
+            identifier: (id_exp >> (ITERATION_EXP_BITS + 1)) as u16,
+            extendable,
+            iteration_exp: (id_exp & 0xF) as u8,
+            group_index: nibble(16),
+            group_threshold: nibble(12) + 1,
+            group_count: nibble(8) + 1,
+            member_index: nibble(4),
+            member_threshold: nibble(0) + 1,
+            value: words_to_bytes(&words[4..words.len() - CHECKSUM_WORDS], padding)?,
+        };
+        if share.group_threshold > share.group_count {
+            return Err(crypto::Error::slip39_invalid_share(
+                "group threshold exceeds group count",
+            ));
+        }
+        Ok(share)
+    }
+}
+
+fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+    let word_count = (bytes.len() * 8 + RADIX_BITS - 1) / RADIX_BITS;
+    let padding = word_count * RADIX_BITS - bytes.len() * 8;
+    let bit = |index: usize| -> u16 {
+        if index < padding {
+            0
+        } else {
+            let index = index - padding;
+            ((bytes[index / 8] >> (7 - index % 8)) & 1) as u16
+        }
+    };
+    (0..word_count)
+        .map(|word| (0..RADIX_BITS).fold(0u16, |acc, i| acc << 1 | bit(word * RADIX_BITS + i)))
+        .collect()
+}
+
+fn words_to_bytes(words: &[u16], padding: usize) -> ClientResult<SecretBuf> {
+    let bit = |index: usize| (words[index / RADIX_BITS] >> (RADIX_BITS - 1 - index % RADIX_BITS)) & 1;
+    if (0..padding).any(|index| bit(index) != 0) {
+        return Err(crypto::Error::slip39_invalid_share("invalid padding"));
+    }
+    let len = (words.len() * RADIX_BITS - padding) / 8;
+    Ok(SecretBuf(
+        (0..len)
+            .map(|byte| (0..8).fold(0u8, |acc, i| acc << 1 | bit(padding + byte * 8 + i) as u8))
+            .collect(),
+    ))
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        EXTENDABLE_CUSTOMIZATION
+    } else {
+        CUSTOMIZATION
+    }
+}
+
+fn rs1024_polymod(words: &[u16], extendable: bool) -> u32 {
+    const GEN: [u32; 10] = [
+        0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24,
+        0x3090FC48, 0x21B1F890, 0x3F3F120,
+    ];
+    let mut chk = 1u32;
+    for value in customization(extendable).iter().map(|byte| *byte as u32).chain(words.iter().map(|word| *word as u32)) {
+        let b = chk >> 20;
+        chk = (chk & 0xFFFFF) << 10 ^ value;
+        for (i, gen) in GEN.iter().enumerate() {
+            if (b >> i) & 1 != 0 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn rs1024_checksum(words: &[u16], extendable: bool) -> [u16; CHECKSUM_WORDS] {
+    let mut values = words.to_vec();
+    values.extend_from_slice(&[0; CHECKSUM_WORDS]);
+    let polymod = rs1024_polymod(&values, extendable) ^ 1;
+    [
+        ((polymod >> 20) & 0x3FF) as u16,
+        ((polymod >> 10) & 0x3FF) as u16,
+        (polymod & 0x3FF) as u16,
+    ]
+}
+
+/// Four-round Feistel cipher used to encrypt the master secret with the passphrase.
+/// Extendable backups don't salt the cipher with the identifier.
+fn feistel(
+    secret: &[u8],
+    passphrase: &str,
+    identifier: u16,
+    extendable: bool,
+    iteration_exp: u8,
+    decrypt: bool,
+) -> SecretBuf {
+    let half = secret.len() / 2;
+    let mut left = secret[..half].to_vec();
+    let mut right = secret[half..].to_vec();
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(CUSTOMIZATION);
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATION_COUNT << iteration_exp) / ROUND_COUNT as u32;
+    let rounds: Vec<u8> = if decrypt {
+        (0..ROUND_COUNT).rev().collect()
+    } else {
+        (0..ROUND_COUNT).collect()
+    };
+    for round in rounds {
+        let mut round_salt = salt.clone();
+        round_salt.extend_from_slice(&right);
+        let mut password = vec![round];
+        password.extend_from_slice(passphrase.as_bytes());
+        let mut f = vec![0u8; half];
+        pbkdf2::<Hmac<Sha256>>(&password, &round_salt, iterations, &mut f);
+        let new_right = left.iter().zip(f.iter()).map(|(l, f)| l ^ f).collect();
+        left = std::mem::replace(&mut right, new_right);
+    }
+    right.extend_from_slice(&left);
+    SecretBuf(right)
+}
+
+fn gf256_tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut poly: u16 = 1;
+    for i in 0..255 {
+        exp[i] = poly as u8;
+        log[poly as usize] = i as u8;
+        poly = (poly << 1) ^ poly;
+        if poly & 0x100 != 0 {
+            poly ^= 0x11B;
+        }
+    }
+    (exp, log)
+}
+
+/// Evaluates at `x` the polynomial of the lowest degree passing through the points.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = points.iter().find(|(point_x, _)| *point_x == x) {
+        return value.to_vec();
+    }
+    let (exp, log) = gf256_tables();
+    let log_prod: u32 = points.iter().map(|(point_x, _)| log[(point_x ^ x) as usize] as u32).sum();
+    let mut result = vec![0u8; points[0].1.len()];
+    for (point_x, value) in points {
+        let others: u32 = points
+            .iter()
+            .map(|(other_x, _)| log[(point_x ^ other_x) as usize] as u32)
+            .sum();
+        let log_basis = (log_prod + 255 * 32 - log[(point_x ^ x) as usize] as u32 - others) % 255;
+        for (result, byte) in result.iter_mut().zip(value.iter()) {
+            if *byte != 0 {
+                *result ^= exp[((log[*byte as usize] as u32 + log_basis) % 255) as usize];
+            }
+        }
+    }
+    result
+}
+
+fn share_digest(random_part: &[u8], secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut hmac = Hmac::<Sha256>::new_from_slice(random_part).unwrap();
+    hmac.update(secret);
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&hmac.finalize().into_bytes()[..DIGEST_LENGTH]);
+    digest
+}
+
+fn split_secret(threshold: u8, share_count: u8, secret: &[u8]) -> ClientResult<Vec<(u8, SecretBuf)>> {
+    if threshold == 1 {
+        return Ok((0..share_count).map(|index| (index, SecretBuf(secret.to_vec()))).collect());
+    }
+    let mut rng = rand::thread_rng();
+    let random_share_count = threshold - 2;
+    let mut shares = (0..random_share_count)
+        .map(|index| {
+            let mut value = vec![0u8; secret.len()];
+            rng.fill_bytes(&mut value);
+            (index, SecretBuf(value))
+        })
+        .collect::<Vec<_>>();
+
+    let mut digest_share = vec![0u8; secret.len()];
+    rng.fill_bytes(&mut digest_share[DIGEST_LENGTH..]);
+    let digest = share_digest(&digest_share[DIGEST_LENGTH..], secret);
+    digest_share[..DIGEST_LENGTH].copy_from_slice(&digest);
+    let digest_share = SecretBuf(digest_share);
+
+    let computed = {
+        let mut base = shares
+            .iter()
+            .map(|(index, value)| (*index, value.0.as_slice()))
+            .collect::<Vec<_>>();
+        base.push((DIGEST_INDEX, digest_share.0.as_slice()));
+        base.push((SECRET_INDEX, secret));
+        (random_share_count..share_count)
+            .map(|index| (index, SecretBuf(interpolate(&base, index))))
+            .collect::<Vec<_>>()
+    };
+    shares.extend(computed);
+    Ok(shares)
+}
+
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> ClientResult<SecretBuf> {
+    if threshold == 1 {
+        return Ok(SecretBuf(points[0].1.to_vec()));
+    }
+    let secret = SecretBuf(interpolate(points, SECRET_INDEX));
+    let digest_share = SecretBuf(interpolate(points, DIGEST_INDEX));
+    if share_digest(&digest_share[DIGEST_LENGTH..], &secret) != digest_share[..DIGEST_LENGTH] {
+        return Err(crypto::Error::slip39_invalid_share("invalid digest of the shared secret"));
+    }
+    Ok(secret)
+}
+
+const SLIP39_WORDS: [&str; 1024] = [
+    "academic", "acid", "acne", "acquire", "acrobat", "activity", "actress", "adapt", "adequate",
+    "adjust", "admit", "adorn", "adult", "advance", "advocate", "afraid", "again", "agency",
+    "agree", "aide", "aircraft", "airline", "airport", "ajar", "alarm", "album", "alcohol", "alien",
+    "alive", "alpha", "already", "alto", "aluminum", "always", "amazing", "ambition", "amount",
+    "amuse", "analysis", "anatomy", "ancestor", "ancient", "angel", "angry", "animal", "answer",
+    "antenna", "anxiety", "apart", "aquatic", "arcade", "arena", "argue", "armed", "artist",
+    "artwork", "aspect", "auction", "august", "aunt", "average", "aviation", "avoid", "award",
+    "away", "axis", "axle", "beam", "beard", "beaver", "become", "bedroom", "behavior", "being",
+    "believe", "belong", "benefit", "best", "beyond", "bike", "biology", "birthday", "bishop",
+    "black", "blanket", "blessing", "blimp", "blind", "blue", "body", "bolt", "boring", "born",
+    "both", "boundary", "bracelet", "branch", "brave", "breathe", "briefing", "broken", "brother",
+    "browser", "bucket", "budget", "building", "bulb", "bulge", "bumpy", "bundle", "burden",
+    "burning", "busy", "buyer", "cage", "calcium", "camera", "campus", "canyon", "capacity",
+    "capital", "capture", "carbon", "cards", "careful", "cargo", "carpet", "carve", "category",
+    "cause", "ceiling", "center", "ceramic", "champion", "change", "charity", "check", "chemical",
+    "chest", "chew", "chubby", "cinema", "civil", "class", "clay", "cleanup", "client", "climate",
+    "clinic", "clock", "clogs", "closet", "clothes", "club", "cluster", "coal", "coastal", "coding",
+    "column", "company", "corner", "costume", "counter", "course", "cover", "cowboy", "cradle",
+    "craft", "crazy", "credit", "cricket", "criminal", "crisis", "critical", "crowd", "crucial",
+    "crunch", "crush", "crystal", "cubic", "cultural", "curious", "curly", "custody", "cylinder",
+    "daisy", "damage", "dance", "darkness", "database", "daughter", "deadline", "deal", "debris",
+    "debut", "decent", "decision", "declare", "decorate", "decrease", "deliver", "demand",
+    "density", "deny", "depart", "depend", "depict", "deploy", "describe", "desert", "desire",
+    "desktop", "destroy", "detailed", "detect", "device", "devote", "diagnose", "dictate", "diet",
+    "dilemma", "diminish", "dining", "diploma", "disaster", "discuss", "disease", "dish", "dismiss",
+    "display", "distance", "dive", "divorce", "document", "domain", "domestic", "dominant", "dough",
+    "downtown", "dragon", "dramatic", "dream", "dress", "drift", "drink", "drove", "drug", "dryer",
+    "duckling", "duke", "duration", "dwarf", "dynamic", "early", "earth", "easel", "easy", "echo",
+    "eclipse", "ecology", "edge", "editor", "educate", "either", "elbow", "elder", "election",
+    "elegant", "element", "elephant", "elevator", "elite", "else", "email", "emerald", "emission",
+    "emperor", "emphasis", "employer", "empty", "ending", "endless", "endorse", "enemy", "energy",
+    "enforce", "engage", "enjoy", "enlarge", "entrance", "envelope", "envy", "epidemic", "episode",
+    "equation", "equip", "eraser", "erode", "escape", "estate", "estimate", "evaluate", "evening",
+    "evidence", "evil", "evoke", "exact", "example", "exceed", "exchange", "exclude", "excuse",
+    "execute", "exercise", "exhaust", "exotic", "expand", "expect", "explain", "express", "extend",
+    "extra", "eyebrow", "facility", "fact", "failure", "faint", "fake", "false", "family", "famous",
+    "fancy", "fangs", "fantasy", "fatal", "fatigue", "favorite", "fawn", "fiber", "fiction",
+    "filter", "finance", "findings", "finger", "firefly", "firm", "fiscal", "fishing", "fitness",
+    "flame", "flash", "flavor", "flea", "flexible", "flip", "float", "floral", "fluff", "focus",
+    "forbid", "force", "forecast", "forget", "formal", "fortune", "forward", "founder", "fraction",
+    "fragment", "frequent", "freshman", "friar", "fridge", "friendly", "frost", "froth", "frozen",
+    "fumes", "funding", "furl", "fused", "galaxy", "game", "garbage", "garden", "garlic",
+    "gasoline", "gather", "general", "genius", "genre", "genuine", "geology", "gesture", "glad",
+    "glance", "glasses", "glen", "glimpse", "goat", "golden", "graduate", "grant", "grasp",
+    "gravity", "gray", "greatest", "grief", "grill", "grin", "grocery", "gross", "group", "grownup",
+    "grumpy", "guard", "guest", "guilt", "guitar", "gums", "hairy", "hamster", "hand", "hanger",
+    "harvest", "have", "havoc", "hawk", "hazard", "headset", "health", "hearing", "heat", "helpful",
+    "herald", "herd", "hesitate", "hobo", "holiday", "holy", "home", "hormone", "hospital", "hour",
+    "huge", "human", "humidity", "hunting", "husband", "hush", "husky", "hybrid", "idea",
+    "identify", "idle", "image", "impact", "imply", "improve", "impulse", "include", "income",
+    "increase", "index", "indicate", "industry", "infant", "inform", "inherit", "injury", "inmate",
+    "insect", "inside", "install", "intend", "intimate", "invasion", "involve", "iris", "island",
+    "isolate", "item", "ivory", "jacket", "jerky", "jewelry", "join", "judicial", "juice", "jump",
+    "junction", "junior", "junk", "jury", "justice", "kernel", "keyboard", "kidney", "kind",
+    "kitchen", "knife", "knit", "laden", "ladle", "ladybug", "lair", "lamp", "language", "large",
+    "laser", "laundry", "lawsuit", "leader", "leaf", "learn", "leaves", "lecture", "legal",
+    "legend", "legs", "lend", "length", "level", "liberty", "library", "license", "lift", "likely",
+    "lilac", "lily", "lips", "liquid", "listen", "literary", "living", "lizard", "loan", "lobe",
+    "location", "losing", "loud", "loyalty", "luck", "lunar", "lunch", "lungs", "luxury", "lying",
+    "lyrics", "machine", "magazine", "maiden", "mailman", "main", "makeup", "making", "mama",
+    "manager", "mandate", "mansion", "manual", "marathon", "march", "market", "marvel", "mason",
+    "material", "math", "maximum", "mayor", "meaning", "medal", "medical", "member", "memory",
+    "mental", "merchant", "merit", "method", "metric", "midst", "mild", "military", "mineral",
+    "minister", "miracle", "mixed", "mixture", "mobile", "modern", "modify", "moisture", "moment",
+    "morning", "mortgage", "mother", "mountain", "mouse", "move", "much", "mule", "multiple",
+    "muscle", "museum", "music", "mustang", "nail", "national", "necklace", "negative", "nervous",
+    "network", "news", "nuclear", "numb", "numerous", "nylon", "oasis", "obesity", "object",
+    "observe", "obtain", "ocean", "often", "olympic", "omit", "oral", "orange", "orbit", "order",
+    "ordinary", "organize", "ounce", "oven", "overall", "owner", "paces", "pacific", "package",
+    "paid", "painting", "pajamas", "pancake", "pants", "papa", "paper", "parcel", "parking",
+    "party", "patent", "patrol", "payment", "payroll", "peaceful", "peanut", "peasant", "pecan",
+    "penalty", "pencil", "percent", "perfect", "permit", "petition", "phantom", "pharmacy", "photo",
+    "phrase", "physics", "pickup", "picture", "piece", "pile", "pink", "pipeline", "pistol",
+    "pitch", "plains", "plan", "plastic", "platform", "playoff", "pleasure", "plot", "plunge",
+    "practice", "prayer", "preach", "predator", "pregnant", "premium", "prepare", "presence",
+    "prevent", "priest", "primary", "priority", "prisoner", "privacy", "prize", "problem",
+    "process", "profile", "program", "promise", "prospect", "provide", "prune", "public", "pulse",
+    "pumps", "punish", "puny", "pupal", "purchase", "purple", "python", "quantity", "quarter",
+    "quick", "quiet", "race", "racism", "radar", "railroad", "rainbow", "raisin", "random",
+    "ranked", "rapids", "raspy", "reaction", "realize", "rebound", "rebuild", "recall", "receiver",
+    "recover", "regret", "regular", "reject", "relate", "remember", "remind", "remove", "render",
+    "repair", "repeat", "replace", "require", "rescue", "research", "resident", "response",
+    "result", "retailer", "retreat", "reunion", "revenue", "review", "reward", "rhyme", "rhythm",
+    "rich", "rival", "river", "robin", "rocky", "romantic", "romp", "roster", "round", "royal",
+    "ruin", "ruler", "rumor", "sack", "safari", "salary", "salon", "salt", "satisfy", "satoshi",
+    "saver", "says", "scandal", "scared", "scatter", "scene", "scholar", "science", "scout",
+    "scramble", "screw", "script", "scroll", "seafood", "season", "secret", "security", "segment",
+    "senior", "shadow", "shaft", "shame", "shaped", "sharp", "shelter", "sheriff", "short",
+    "should", "shrimp", "sidewalk", "silent", "silver", "similar", "simple", "single", "sister",
+    "skin", "skunk", "slap", "slavery", "sled", "slice", "slim", "slow", "slush", "smart", "smear",
+    "smell", "smirk", "smith", "smoking", "smug", "snake", "snapshot", "sniff", "society",
+    "software", "soldier", "solution", "soul", "source", "space", "spark", "speak", "species",
+    "spelling", "spend", "spew", "spider", "spill", "spine", "spirit", "spit", "spray", "sprinkle",
+    "square", "squeeze", "stadium", "staff", "standard", "starting", "station", "stay", "steady",
+    "step", "stick", "stilt", "story", "strategy", "strike", "style", "subject", "submit", "sugar",
+    "suitable", "sunlight", "superior", "surface", "surprise", "survive", "sweater", "swimming",
+    "swing", "switch", "symbolic", "sympathy", "syndrome", "system", "tackle", "tactics", "tadpole",
+    "talent", "task", "taste", "taught", "taxi", "teacher", "teammate", "teaspoon", "temple",
+    "tenant", "tendency", "tension", "terminal", "testify", "texture", "thank", "that", "theater",
+    "theory", "therapy", "thorn", "threaten", "thumb", "thunder", "ticket", "tidy", "timber",
+    "timely", "ting", "tofu", "together", "tolerate", "total", "toxic", "tracks", "traffic",
+    "training", "transfer", "trash", "traveler", "treat", "trend", "trial", "tricycle", "trip",
+    "triumph", "trouble", "true", "trust", "twice", "twin", "type", "typical", "ugly", "ultimate",
+    "umbrella", "uncover", "undergo", "unfair", "unfold", "unhappy", "union", "universe", "unkind",
+    "unknown", "unusual", "unwrap", "upgrade", "upstairs", "username", "usher", "usual", "valid",
+    "valuable", "vampire", "vanish", "various", "vegan", "velvet", "venture", "verdict", "verify",
+    "very", "veteran", "vexed", "victim", "video", "view", "vintage", "violence", "viral",
+    "visitor", "visual", "vitamins", "vocal", "voice", "volume", "voter", "voting", "walnut",
+    "warmth", "warn", "watch", "wavy", "wealthy", "weapon", "webcam", "welcome", "welfare",
+    "western", "width", "wildlife", "window", "wine", "wireless", "wisdom", "withdraw", "wits",
+    "wolf", "woman", "work", "worthy", "wrap", "wrist", "writing", "wrote", "year", "yelp", "yield",
+    "yoga", "zero",
+];
@@ -1,15 +1,21 @@
 use super::*;
 use crate::client::ParamsOfAppRequest;
 use crate::crypto::boxes::crypto_box::{
-    BoxEncryptionAlgorithm, ChaCha20ParamsCB, CryptoBoxSecret, ParamsOfCreateCryptoBox,
-    ParamsOfGetEncryptionBoxFromCryptoBox, ParamsOfGetSigningBoxFromCryptoBox, RegisteredCryptoBox,
-    ResultOfGetCryptoBoxInfo, ResultOfGetCryptoBoxSeedPhrase,
+    BoxEncryptionAlgorithm, ChaCha20ParamsCB, CryptoBoxSecret, ParamsOfChangeCryptoBoxPassword,
+    ParamsOfCreateCryptoBox, ParamsOfGetEncryptionBoxFromCryptoBox,
+    ParamsOfGetSigningBoxFromCryptoBox, RegisteredCryptoBox, ResultOfChangeCryptoBoxPassword,
+    ResultOfGetCryptoBoxInfo, ResultOfGetCryptoBoxSeedPhrase, SecretEncryptionKdf,
 };
+use crate::crypto::boxes::encryption_box::aead::{AesGcmParamsEB, ChaCha20Poly1305ParamsEB};
 use crate::crypto::boxes::encryption_box::nacl_box::NaclBoxParamsEB;
 use crate::crypto::boxes::encryption_box::nacl_secret_box::NaclSecretBoxParamsEB;
-use crate::crypto::boxes::encryption_box::ParamsOfCreateEncryptionBox;
-use crate::crypto::encscrypt::{ParamsOfScrypt, ResultOfScrypt};
-use crate::crypto::hash::{ParamsOfHash, ResultOfHash};
+use crate::crypto::boxes::encryption_box::{
+    ParamsOfCreateEncryptionBox, ParamsOfEncryptionBoxDecryptInit, ParamsOfEncryptionBoxEncryptInit,
+    ParamsOfEncryptionStreamUpdate, RegisteredEncryptionStream, ResultOfEncryptionStreamFinish,
+    ResultOfEncryptionStreamUpdate,
+};
+use crate::crypto::encscrypt::{ParamsOfArgon2id, ParamsOfScrypt, ResultOfArgon2id, ResultOfScrypt};
+use crate::crypto::hash::{ParamsOfBlake2b, ParamsOfHash, ParamsOfHmac, ResultOfHash, ResultOfHmac};
 use crate::crypto::hdkey::{
     ParamsOfHDKeyDeriveFromXPrv, ParamsOfHDKeyDeriveFromXPrvPath, ParamsOfHDKeyPublicFromXPrv,
     ParamsOfHDKeySecretFromXPrv, ParamsOfHDKeyXPrvFromMnemonic, ResultOfHDKeyDeriveFromXPrv,
@@ -17,18 +23,31 @@ use crate::crypto::hdkey::{
     ResultOfHDKeyXPrvFromMnemonic,
 };
 use crate::crypto::keys::{
-    strip_secret, KeyPair, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign,
-    ParamsOfVerifySignature, ResultOfConvertPublicKeyToTonSafeFormat, ResultOfSign,
+    strip_secret, KeyPair, ParamsOfConvertEd25519ToX25519Public,
+    ParamsOfConvertEd25519ToX25519Secret, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign,
+    ParamsOfVerifySignature, ResultOfConvertEd25519ToX25519Public,
+    ResultOfConvertEd25519ToX25519Secret, ResultOfConvertPublicKeyToTonSafeFormat, ResultOfSign,
     ResultOfVerifySignature,
 };
+use crate::crypto::kdf::{
+    HkdfHashAlgorithm, ParamsOfHkdfExpand, ParamsOfHkdfExtract, ResultOfHkdfExpand,
+    ResultOfHkdfExtract,
+};
+use crate::crypto::keystore::{
+    KeystoreCipher, KeystoreKdfParams, KeystoreSecret, ParamsOfExportKeystore,
+    ParamsOfImportKeystore, ResultOfExportKeystore, ResultOfImportKeystore,
+};
 use crate::crypto::math::{
     ParamsOfFactorize, ParamsOfGenerateRandomBytes, ParamsOfModularPower, ParamsOfTonCrc16,
     ResultOfFactorize, ResultOfGenerateRandomBytes, ResultOfModularPower, ResultOfTonCrc16,
 };
 use crate::crypto::mnemonic::{
-    ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy, ParamsOfMnemonicFromRandom,
-    ParamsOfMnemonicVerify, ParamsOfMnemonicWords, ResultOfMnemonicFromEntropy,
-    ResultOfMnemonicFromRandom, ResultOfMnemonicVerify, ResultOfMnemonicWords,
+    ParamsOfMnemonicCombine, ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy,
+    ParamsOfMnemonicFromRandom, ParamsOfMnemonicRepair, ParamsOfMnemonicSplit,
+    ParamsOfMnemonicSuggest, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
+    ResultOfMnemonicCombine, ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom,
+    ResultOfMnemonicRepair, ResultOfMnemonicSplit, ResultOfMnemonicSuggest,
+    ResultOfMnemonicVerify, ResultOfMnemonicWords,
 };
 use crate::crypto::nacl::{
     ParamsOfNaclBox, ParamsOfNaclBoxKeyPairFromSecret, ParamsOfNaclBoxOpen, ParamsOfNaclSecretBox,
@@ -36,6 +55,11 @@ use crate::crypto::nacl::{
     ParamsOfNaclSignOpen, ResultOfNaclBox, ResultOfNaclBoxOpen, ResultOfNaclSign,
     ResultOfNaclSignDetached, ResultOfNaclSignOpen,
 };
+use crate::crypto::secp256k1::{
+    ParamsOfSecp256k1PublicKey, ParamsOfSecp256k1Recover, ParamsOfSecp256k1Sign,
+    ParamsOfSecp256k1Verify, ResultOfSecp256k1PublicKey, ResultOfSecp256k1Sign,
+    ResultOfSecp256k1Verify,
+};
 use crate::crypto::{ParamsOfChaCha20, ResultOfChaCha20};
 use crate::json_interface::crypto::{
     ParamsOfAppPasswordProvider, ParamsOfAppSigningBox, ResultOfAppPasswordProvider,
@@ -196,6 +220,209 @@ fn hash() {
         "16fd057308dd358d5a9b3ba2de766b2dfd5e308478fc1f7ba5988db2493852f5",
         result.hash
     );
+
+    let result: ResultOfHash = client
+        .request("crypto.keccak256", ParamsOfHash { data: String::new() })
+        .unwrap();
+    assert_eq!(
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        result.hash
+    );
+
+    let result: ResultOfHash = client
+        .request(
+            "crypto.keccak256",
+            ParamsOfHash {
+                data: base64::encode("Message to hash with keccak 256"),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "405d716542763e768378e189e9aebdd694817da6af64380b5eebbc284916d72b",
+        result.hash
+    );
+
+    let result: ResultOfHash = client
+        .request(
+            "crypto.sha3_256",
+            ParamsOfHash {
+                data: base64::encode("Message to hash with sha3 256"),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "c86f38ac6215e9610783cb9d3ccf6d53fda2a6f47d9b5e9ea9eadafe53ec632b",
+        result.hash
+    );
+
+    let result: ResultOfHash = client
+        .request(
+            "crypto.sha3_512",
+            ParamsOfHash {
+                data: base64::encode("Message to hash with sha3 512"),
+            },
+        )
+        .unwrap();
+    assert_eq!("f72540cc2a76df70f8d3ffe45e2324ba46c047a454d98a2f915176860f4574debbc97e09b95d9fcccf2059d68f3683b1e06b47cb613a239cadcd1d4b27dda5b2", result.hash);
+
+    let result: ResultOfHash = client
+        .request(
+            "crypto.blake2b",
+            ParamsOfBlake2b {
+                data: base64::encode("Message to hash with blake2b"),
+                output_len: None,
+            },
+        )
+        .unwrap();
+    assert_eq!("43540aea7ba9f75a72ac7f6a2b02ddfd215862c492c26267b347da3fcd7893f78f90319c09bdeebcfb803f96988ef7d3b15cb8881518f7b9cc82ba0aaff21ead", result.hash);
+
+    let result: ResultOfHash = client
+        .request(
+            "crypto.blake2b",
+            ParamsOfBlake2b {
+                data: base64::encode("Message to hash with blake2b"),
+                output_len: Some(32),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "2fa717adad103c26659681b239f3b2d289225b901c9d5b5bfcbc44822c9c9661",
+        result.hash
+    );
+
+    let error = client
+        .request::<_, ResultOfHash>(
+            "crypto.blake2b",
+            ParamsOfBlake2b {
+                data: base64::encode("Message to hash with blake2b"),
+                output_len: Some(65),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidOutputLength as u32);
+}
+
+#[test]
+fn hmac() {
+    let client = TestClient::new();
+
+    let result: ResultOfHmac = client
+        .request(
+            "crypto.hmac_sha256",
+            ParamsOfHmac {
+                key: base64::encode("key"),
+                data: base64::encode("The quick brown fox jumps over the lazy dog"),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+        result.hmac
+    );
+
+    let result: ResultOfHmac = client
+        .request(
+            "crypto.hmac_sha512",
+            ParamsOfHmac {
+                key: base64::encode("key"),
+                data: base64::encode("The quick brown fox jumps over the lazy dog"),
+            },
+        )
+        .unwrap();
+    assert_eq!("b42af09057bac1e2d41708e48a902e09b5ff7f12ab428a4fe86653c73dd248fb82f948a549f7b791a5b41915ee4d1ec3935357e4e2317250d0372afa2ebeeb3a", result.hmac);
+}
+
+#[test]
+fn hkdf() {
+    let client = TestClient::new();
+
+    // RFC 5869, test case 1
+    let result: ResultOfHkdfExtract = client
+        .request(
+            "crypto.hkdf_extract",
+            ParamsOfHkdfExtract {
+                ikm: "CwsLCwsLCwsLCwsLCwsLCwsLCwsLCw==".into(),
+                salt: Some("AAECAwQFBgcICQoLDA==".into()),
+                hash_algorithm: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+        result.prk
+    );
+
+    let result: ResultOfHkdfExpand = client
+        .request(
+            "crypto.hkdf_expand",
+            ParamsOfHkdfExpand {
+                prk: result.prk,
+                info: Some("8PHy8/T19vf4+Q==".into()),
+                length: 42,
+                hash_algorithm: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+        result.okm
+    );
+
+    let result: ResultOfHkdfExtract = client
+        .request(
+            "crypto.hkdf_extract",
+            ParamsOfHkdfExtract {
+                ikm: "CwsLCwsLCwsLCwsLCwsLCwsLCwsLCw==".into(),
+                salt: None,
+                hash_algorithm: Some(HkdfHashAlgorithm::Sha256),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+        result.prk
+    );
+
+    let result: ResultOfHkdfExtract = client
+        .request(
+            "crypto.hkdf_extract",
+            ParamsOfHkdfExtract {
+                ikm: "CwsLCwsLCwsLCwsLCwsLCwsLCwsLCw==".into(),
+                salt: Some("AAECAwQFBgcICQoLDA==".into()),
+                hash_algorithm: Some(HkdfHashAlgorithm::Sha512),
+            },
+        )
+        .unwrap();
+    assert_eq!("665799823737ded04a88e47e54a5890bb2c3d247c7a4254a8e61350723590a26c36238127d8661b88cf80ef802d57e2f7cebcf1e00e083848be19929c61b4237", result.prk);
+
+    let okm: ResultOfHkdfExpand = client
+        .request(
+            "crypto.hkdf_expand",
+            ParamsOfHkdfExpand {
+                prk: result.prk.clone(),
+                info: Some("8PHy8/T19vf4+Q==".into()),
+                length: 42,
+                hash_algorithm: Some(HkdfHashAlgorithm::Sha512),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb",
+        okm.okm
+    );
+
+    let error = client
+        .request::<_, ResultOfHkdfExpand>(
+            "crypto.hkdf_expand",
+            ParamsOfHkdfExpand {
+                prk: result.prk,
+                info: None,
+                length: 255 * 64 + 1,
+                hash_algorithm: Some(HkdfHashAlgorithm::Sha512),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidOutputLength as u32);
 }
 
 #[test]
@@ -246,6 +473,159 @@ fn keys() {
         public: "1869b7ef29d58026217e9cf163cbfbd0de889bdf1bf4daebf5433a312f5b8d6e".into(),
     }).unwrap();
     assert_eq!(text_from_base64(&result.unsigned), "Test Message");
+
+    let result: ResultOfConvertEd25519ToX25519Public = client
+        .request(
+            "crypto.convert_ed25519_to_x25519_public",
+            ParamsOfConvertEd25519ToX25519Public {
+                public: "1869b7ef29d58026217e9cf163cbfbd0de889bdf1bf4daebf5433a312f5b8d6e".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(result.public, "c6e81c97899347aa346e6502a614cad6108f1494d6b790a3fd7be89e67d2db2b");
+
+    let result: ResultOfConvertEd25519ToX25519Secret = client
+        .request(
+            "crypto.convert_ed25519_to_x25519_secret",
+            ParamsOfConvertEd25519ToX25519Secret {
+                secret: "56b6a77093d6fdf14e593f36275d872d75de5b341942376b2a08759f3cbae78f".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(result.secret, "a82a861588c5813d931cb1e44ae1280e196b622b774dc3437c1532a7767c5a5c");
+
+    let result: KeyPair = client
+        .request(
+            "crypto.nacl_box_keypair_from_secret_key",
+            ParamsOfNaclBoxKeyPairFromSecret {
+                secret: result.secret.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(result.public, "c6e81c97899347aa346e6502a614cad6108f1494d6b790a3fd7be89e67d2db2b");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn secp256k1() {
+    let client = TestClient::new();
+
+    let keys: KeyPair = client
+        .request_no_params("crypto.secp256k1_generate_random_keys")
+        .unwrap();
+    assert_eq!(keys.public.len(), 66);
+    assert_eq!(keys.secret.len(), 64);
+
+    let result: ResultOfSecp256k1PublicKey = client
+        .request(
+            "crypto.secp256k1_public_key",
+            ParamsOfSecp256k1PublicKey {
+                key: "0000000000000000000000000000000000000000000000000000000000000001".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.public,
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+    assert_eq!(
+        result.public_uncompressed,
+        "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+         483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+    );
+
+    let hash = "4ee2ad5f9a3f1d6b8a0c2b1f1e4d3c2b1a09f8e7d6c5b4a39281706f5e4d3c2b".to_string();
+    let signed: ResultOfSecp256k1Sign = client
+        .request(
+            "crypto.secp256k1_sign",
+            ParamsOfSecp256k1Sign {
+                hash: hash.clone(),
+                secret: keys.secret.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(signed.signature.len(), 128);
+    assert!(signed.recovery_id <= 1);
+
+    let verify = |signature: String, public: String| {
+        client
+            .request::<_, ResultOfSecp256k1Verify>(
+                "crypto.secp256k1_verify",
+                ParamsOfSecp256k1Verify { hash: hash.clone(), signature, public },
+            )
+            .unwrap()
+            .succeeded
+    };
+    assert!(verify(signed.signature.clone(), keys.public.clone()));
+    assert!(!verify(signed.signature.clone(), result.public.clone()));
+
+    // The same signature with `s` replaced by `n - s` is valid for ECDSA, but rejected
+    let order = num_bigint::BigUint::parse_bytes(
+        b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        16,
+    ).unwrap();
+    let s = num_bigint::BigUint::parse_bytes(signed.signature[64..].as_bytes(), 16).unwrap();
+    let high_s_signature = format!("{}{:0>64}", &signed.signature[..64], (order - s).to_str_radix(16));
+    assert!(!verify(high_s_signature, keys.public.clone()));
+
+    let recovered: ResultOfSecp256k1PublicKey = client
+        .request(
+            "crypto.secp256k1_recover",
+            ParamsOfSecp256k1Recover {
+                hash: hash.clone(),
+                signature: format!("{}{:02x}", signed.signature, 27 + signed.recovery_id),
+                recovery_id: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(recovered.public, keys.public);
+    assert!(verify(signed.signature.clone(), recovered.public_uncompressed));
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.get_secp256k1_signing_box",
+            ParamsOfGetSecp256k1SigningBox { secret: keys.secret.clone() },
+        )
+        .await
+        .unwrap();
+    let public: ResultOfSigningBoxGetPublicKey = client
+        .request_async("crypto.signing_box_get_public_key", signing_box.clone())
+        .await
+        .unwrap();
+    assert_eq!(public.pubkey, keys.public);
+    let box_signed: ResultOfSigningBoxSign = client
+        .request_async(
+            "crypto.signing_box_sign",
+            ParamsOfSigningBoxSign {
+                signing_box: signing_box.handle.clone(),
+                unsigned: base64_from_hex(&hash),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        box_signed.signature,
+        format!("{}{:02x}", signed.signature, signed.recovery_id)
+    );
+
+    // ABI messages are signed with Ed25519 only
+    let error = client
+        .request_async::<_, crate::abi::ResultOfEncodeMessage>(
+            "abi.encode_message",
+            crate::abi::ParamsOfEncodeMessage {
+                abi: TestClient::abi("GiverV2", Some(2)),
+                address: Some(
+                    "0:ece57bcc6c530283becbbd8a3b24d3c5987cdddc3c8b7b33be6e4a6312490415".into(),
+                ),
+                call_set: crate::abi::CallSet::some_with_function("getMessages"),
+                signer: crate::abi::Signer::SigningBox {
+                    handle: signing_box.handle.clone(),
+                },
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SigningBoxNotSupportedByAbi as u32);
 }
 
 #[test]
@@ -269,6 +649,176 @@ fn scrypt() {
     assert_eq!(result.key, "52e7fcf91356eca55fc5d52f16f5d777e3521f54e3c570c9bbb7df58fc15add73994e5db42be368de7ebed93c9d4f21f9be7cc453358d734b04a057d0ed3626d");
 }
 
+#[test]
+fn argon2id() {
+    let client = TestClient::new();
+
+    let result: ResultOfArgon2id = client
+        .request(
+            "crypto.argon2id",
+            ParamsOfArgon2id {
+                password: base64::encode("password"),
+                salt: base64::encode("somesalt"),
+                memory_cost: 64,
+                iterations: 2,
+                parallelism: 1,
+                dk_len: 32,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.key, "16a1a498734609dd01456da406de9f3d9da93e6c86c300a12fc1465214ce4922");
+
+    let result: ResultOfArgon2id = client
+        .request(
+            "crypto.argon2id",
+            ParamsOfArgon2id {
+                password: base64::encode("password"),
+                salt: base64::encode("somesalt"),
+                memory_cost: 256,
+                iterations: 2,
+                parallelism: 2,
+                dk_len: 32,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.key, "6d093c501fd5999645e0ea3bf620d7b8be7fd2db59c20d9fff9539da2bf57037");
+
+    let error = client
+        .request::<_, ResultOfArgon2id>(
+            "crypto.argon2id",
+            ParamsOfArgon2id {
+                password: base64::encode("password"),
+                salt: base64::encode("somesalt"),
+                memory_cost: 64,
+                iterations: 0,
+                parallelism: 1,
+                dk_len: 32,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Argon2Failed as u32);
+}
+
+#[test]
+fn keystore() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    let password = base64::encode("keystore password");
+    let kdf_params = KeystoreKdfParams {
+        log_n: 10,
+        r: 8,
+        p: 1,
+    };
+    let secrets = [
+        KeystoreSecret::KeyPair {
+            keys: KeyPair::new(
+                "1869b7ef29d58026217e9cf163cbfbd0de889bdf1bf4daebf5433a312f5b8d6e".into(),
+                "56b6a77093d6fdf14e593f36275d872d75de5b341942376b2a08759f3cbae78f".into(),
+            ),
+        },
+        KeystoreSecret::SeedPhrase {
+            phrase: "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo"
+                .into(),
+            dictionary: 1,
+            word_count: 12,
+        },
+    ];
+    for cipher in [KeystoreCipher::ChaCha20Poly1305, KeystoreCipher::NaclSecretBox].iter() {
+        for secret in secrets.iter() {
+            let exported: ResultOfExportKeystore = client
+                .request(
+                    "crypto.export_keystore",
+                    ParamsOfExportKeystore {
+                        secret: secret.clone(),
+                        password: password.clone(),
+                        cipher: Some(*cipher),
+                        kdf_params: Some(kdf_params.clone()),
+                    },
+                )
+                .unwrap();
+            let json: serde_json::Value = serde_json::from_str(&exported.keystore).unwrap();
+            assert_eq!(json["version"], 1);
+            assert_eq!(json["crypto"]["kdf"], "scrypt");
+            assert_eq!(json["crypto"]["kdf_params"]["log_n"], 10);
+
+            let imported: ResultOfImportKeystore = client
+                .request(
+                    "crypto.import_keystore",
+                    ParamsOfImportKeystore {
+                        keystore: exported.keystore.clone(),
+                        password: password.clone(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(&imported.secret, secret);
+
+            let error = client
+                .request::<_, ResultOfImportKeystore>(
+                    "crypto.import_keystore",
+                    ParamsOfImportKeystore {
+                        keystore: exported.keystore.clone(),
+                        password: base64::encode("wrong password"),
+                    },
+                )
+                .unwrap_err();
+            assert_eq!(error.code, ErrorCode::KeystoreDecryptionFailed as u32);
+        }
+    }
+
+    let error = client
+        .request::<_, ResultOfImportKeystore>(
+            "crypto.import_keystore",
+            ParamsOfImportKeystore {
+                keystore: json!({ "version": 2, "crypto": {} }).to_string(),
+                password: password.clone(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidKeystore as u32);
+
+    // Scrypt parameters of the crafted keystore are rejected before the key derivation
+    let exported: ResultOfExportKeystore = client
+        .request(
+            "crypto.export_keystore",
+            ParamsOfExportKeystore {
+                secret: secrets[0].clone(),
+                password: password.clone(),
+                cipher: None,
+                kdf_params: Some(kdf_params),
+            },
+        )
+        .unwrap();
+    for (param, value) in [("log_n", 40), ("r", 1 << 20), ("p", 1 << 20)].iter() {
+        let mut keystore: serde_json::Value = serde_json::from_str(&exported.keystore).unwrap();
+        keystore["crypto"]["kdf_params"][param] = json!(value);
+        let error = client
+            .request::<_, ResultOfImportKeystore>(
+                "crypto.import_keystore",
+                ParamsOfImportKeystore {
+                    keystore: keystore.to_string(),
+                    password: password.clone(),
+                },
+            )
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::InvalidKeystore as u32);
+    }
+    // Each parameter is within its usual range, but together they take 4 GiB
+    let mut keystore: serde_json::Value = serde_json::from_str(&exported.keystore).unwrap();
+    keystore["crypto"]["kdf_params"]["log_n"] = json!(20);
+    keystore["crypto"]["kdf_params"]["r"] = json!(32);
+    let error = client
+        .request::<_, ResultOfImportKeystore>(
+            "crypto.import_keystore",
+            ParamsOfImportKeystore {
+                keystore: keystore.to_string(),
+                password: password.clone(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidKeystore as u32);
+}
+
 #[test]
 fn nacl() {
     TestClient::init_log();
@@ -520,6 +1070,8 @@ fn mnemonic() {
         path: None,
         dictionary: Some(0),
         word_count: Some(24),
+        derivation_scheme: None,
+        passphrase: None,
     }).unwrap();
     let result: ResultOfConvertPublicKeyToTonSafeFormat = client
         .request(
@@ -539,6 +1091,8 @@ fn mnemonic() {
         path: Some("m".into()),
         dictionary: Some(0),
         word_count: Some(24),
+        derivation_scheme: None,
+        passphrase: None,
     }).unwrap();
     let result: ResultOfConvertPublicKeyToTonSafeFormat = client
         .request(
@@ -562,6 +1116,8 @@ fn mnemonic() {
             path: None,
             dictionary: None,
             word_count: None,
+            derivation_scheme: None,
+            passphrase: None,
         },
     ).unwrap();
     let result: ResultOfConvertPublicKeyToTonSafeFormat = client
@@ -629,6 +1185,8 @@ fn mnemonic() {
                 path: None,
                 dictionary: None,
                 word_count: None,
+                derivation_scheme: None,
+                passphrase: None,
             },
         )
         .unwrap();
@@ -657,6 +1215,8 @@ fn hdkey() {
             ParamsOfHDKeyXPrvFromMnemonic {
                 dictionary: None,
                 word_count: None,
+                derivation_scheme: None,
+                passphrase: None,
                 phrase:
                     "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo"
                         .into(),
@@ -698,6 +1258,7 @@ fn hdkey() {
                 xprv: master.xprv.clone(),
                 child_index: 0,
                 hardened: false,
+                derivation_scheme: None,
             },
         )
         .unwrap();
@@ -735,6 +1296,7 @@ fn hdkey() {
             ParamsOfHDKeyDeriveFromXPrvPath {
                 xprv: master.xprv.clone(),
                 path: "m/44'/60'/0'/0'".into(),
+                derivation_scheme: None,
             },
         )
         .unwrap();
@@ -767,24 +1329,447 @@ fn hdkey() {
     );
 }
 
-#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-async fn test_signing_box() {
-    let client = std::sync::Arc::new(TestClient::new());
-    let client_copy = client.clone();
+#[test]
+fn hdkey_slip10_ed25519() {
+    TestClient::init_log();
+    let client = TestClient::new();
 
-    let keys = client.generate_sign_keys();
+    // SLIP-0010 test vector 1 for ed25519
+    let master = crate::crypto::hdkey::HDPrivateKey::from_seed(
+        &hex::decode("000102030405060708090a0b0c0d0e0f").unwrap(),
+        HDKeyDerivationScheme::Slip10Ed25519,
+    )
+    .unwrap();
+    assert_eq!(
+        hex::encode(master.secret().0),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+    let child = master
+        .derive(0, true, true)
+        .unwrap();
+    assert_eq!(
+        hex::encode(child.secret().0),
+        "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+    );
 
-    let keys_box_handle = client
-        .request_async::<_, RegisteredSigningBox>("crypto.get_signing_box", keys.clone())
-        .await
-        .unwrap()
-        .handle
-        .0;
+    let phrase =
+        "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo".to_string();
+    let path = "m/44'/396'/0'/0'/0'".to_string();
 
-    // external signing box uses keys box inside
-    let callback = move |request: crate::client::ParamsOfAppRequest, _: u32| {
-        let client = client_copy.clone();
-        tokio::spawn(async move {
+    let keys: KeyPair = client
+        .request(
+            "crypto.mnemonic_derive_sign_keys",
+            ParamsOfMnemonicDeriveSignKeys {
+                phrase: phrase.clone(),
+                path: Some(path.clone()),
+                dictionary: None,
+                word_count: None,
+                derivation_scheme: Some(HDKeyDerivationScheme::Slip10Ed25519),
+                passphrase: None,
+            },
+        )
+        .unwrap();
+
+    let master: ResultOfHDKeyXPrvFromMnemonic = client
+        .request(
+            "crypto.hdkey_xprv_from_mnemonic",
+            ParamsOfHDKeyXPrvFromMnemonic {
+                phrase: phrase.clone(),
+                dictionary: None,
+                word_count: None,
+                derivation_scheme: Some(HDKeyDerivationScheme::Slip10Ed25519),
+                passphrase: None,
+            },
+        )
+        .unwrap();
+    let derived: ResultOfHDKeyDeriveFromXPrvPath = client
+        .request(
+            "crypto.hdkey_derive_from_xprv_path",
+            ParamsOfHDKeyDeriveFromXPrvPath {
+                xprv: master.xprv.clone(),
+                path: path.clone(),
+                // the scheme is taken from the extended key
+                derivation_scheme: None,
+            },
+        )
+        .unwrap();
+    let secret: ResultOfHDKeySecretFromXPrv = client
+        .request(
+            "crypto.hdkey_secret_from_xprv",
+            ParamsOfHDKeySecretFromXPrv { xprv: derived.xprv },
+        )
+        .unwrap();
+    assert_eq!(keys.secret, secret.secret);
+
+    let bip32_keys: KeyPair = client
+        .request(
+            "crypto.mnemonic_derive_sign_keys",
+            ParamsOfMnemonicDeriveSignKeys {
+                phrase: phrase.clone(),
+                path: Some(path.clone()),
+                dictionary: None,
+                word_count: None,
+                derivation_scheme: None,
+                passphrase: None,
+            },
+        )
+        .unwrap();
+    assert_ne!(keys.secret, bip32_keys.secret);
+
+    let error = client
+        .request::<_, ResultOfHDKeyDeriveFromXPrv>(
+            "crypto.hdkey_derive_from_xprv",
+            ParamsOfHDKeyDeriveFromXPrv {
+                xprv: master.xprv.clone(),
+                child_index: 0,
+                hardened: true,
+                derivation_scheme: Some(HDKeyDerivationScheme::Bip32),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip32InvalidKey as u32);
+
+    let error = client
+        .request::<_, ResultOfHDKeyDeriveFromXPrvPath>(
+            "crypto.hdkey_derive_from_xprv_path",
+            ParamsOfHDKeyDeriveFromXPrvPath {
+                xprv: master.xprv,
+                path: "m/44'/396'/0'/0/0".into(),
+                derivation_scheme: Some(HDKeyDerivationScheme::Slip10Ed25519),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip32InvalidDerivePath as u32);
+}
+
+#[test]
+fn mnemonic_passphrase() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about".to_string();
+
+    // BIP-39 test vector with "TREZOR" passphrase
+    let master: ResultOfHDKeyXPrvFromMnemonic = client
+        .request(
+            "crypto.hdkey_xprv_from_mnemonic",
+            ParamsOfHDKeyXPrvFromMnemonic {
+                phrase: phrase.clone(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                derivation_scheme: None,
+                passphrase: Some("TREZOR".into()),
+            },
+        )
+        .unwrap();
+    assert_eq!(master.xprv, "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF");
+
+    let derive_keys = |passphrase: Option<&str>| -> KeyPair {
+        client
+            .request(
+                "crypto.mnemonic_derive_sign_keys",
+                ParamsOfMnemonicDeriveSignKeys {
+                    phrase: phrase.clone(),
+                    path: None,
+                    dictionary: Some(1),
+                    word_count: Some(12),
+                    derivation_scheme: None,
+                    passphrase: passphrase.map(|s| s.to_string()),
+                },
+            )
+            .unwrap()
+    };
+    let keys = derive_keys(Some("TREZOR"));
+    assert_ne!(keys.secret, derive_keys(None).secret);
+    assert_eq!(derive_keys(None).secret, derive_keys(Some("")).secret);
+
+    let error = client
+        .request::<_, KeyPair>(
+            "crypto.mnemonic_derive_sign_keys",
+            ParamsOfMnemonicDeriveSignKeys {
+                phrase: "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo".into(),
+                path: None,
+                dictionary: Some(0),
+                word_count: Some(12),
+                derivation_scheme: None,
+                passphrase: Some("TREZOR".into()),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::MnemonicPassphraseNotSupported as u32);
+}
+
+#[test]
+fn mnemonic_split_combine() {
+    TestClient::init_log();
+    let client = TestClient::new();
+
+    let phrases = [
+        (
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            1,
+            12,
+        ),
+        (
+            "unit follow zone decline glare flower crisp vocal adapt magic much mesh cherry teach mechanic rain float vicious solution assume hedgehog rail sort chuckle",
+            0,
+            24,
+        ),
+    ];
+    for (phrase, dictionary, word_count) in phrases.iter() {
+        let result: ResultOfMnemonicSplit = client
+            .request(
+                "crypto.mnemonic_split",
+                ParamsOfMnemonicSplit {
+                    phrase: phrase.to_string(),
+                    dictionary: Some(*dictionary),
+                    word_count: Some(*word_count),
+                    threshold: 3,
+                    share_count: 5,
+                    passphrase: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(result.shares.len(), 5);
+
+        let combine = |shares: Vec<String>| {
+            client.request::<_, ResultOfMnemonicCombine>(
+                "crypto.mnemonic_combine",
+                ParamsOfMnemonicCombine {
+                    shares,
+                    dictionary: Some(*dictionary),
+                    word_count: Some(*word_count),
+                    passphrase: None,
+                },
+            )
+        };
+        let combined = combine(result.shares[..3].to_vec()).unwrap();
+        assert_eq!(combined.phrase, *phrase);
+        let combined = combine(vec![
+            result.shares[4].clone(),
+            result.shares[1].clone(),
+            result.shares[3].clone(),
+        ])
+        .unwrap();
+        assert_eq!(combined.phrase, *phrase);
+
+        let error = combine(result.shares[..2].to_vec()).unwrap_err();
+        assert_eq!(error.code, ErrorCode::Slip39InvalidShare as u32);
+
+        let mut words: Vec<&str> = result.shares[0].split(" ").collect();
+        words[5] = if words[5] == "academic" { "acid" } else { "academic" };
+        let error = combine(vec![
+            words.join(" "),
+            result.shares[1].clone(),
+            result.shares[2].clone(),
+        ])
+        .unwrap_err();
+        assert_eq!(error.code, ErrorCode::Slip39InvalidShare as u32);
+    }
+
+    let error = client
+        .request::<_, ResultOfMnemonicSplit>(
+            "crypto.mnemonic_split",
+            ParamsOfMnemonicSplit {
+                phrase: phrases[0].0.to_string(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                threshold: 4,
+                share_count: 3,
+                passphrase: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Slip39InvalidParameters as u32);
+
+    // SLIP-0039 test vector 1, master secret bb54aac4b89dc868ba37d9cc21b2cece
+    let expected: ResultOfMnemonicFromEntropy = client
+        .request(
+            "crypto.mnemonic_from_entropy",
+            ParamsOfMnemonicFromEntropy {
+                entropy: "bb54aac4b89dc868ba37d9cc21b2cece".into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+            },
+        )
+        .unwrap();
+    let combined: ResultOfMnemonicCombine = client
+        .request(
+            "crypto.mnemonic_combine",
+            ParamsOfMnemonicCombine {
+                shares: vec!["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard".into()],
+                dictionary: Some(1),
+                word_count: Some(12),
+                passphrase: Some("TREZOR".into()),
+            },
+        )
+        .unwrap();
+    assert_eq!(combined.phrase, expected.phrase);
+
+    let expected: ResultOfMnemonicFromEntropy = client
+        .request(
+            "crypto.mnemonic_from_entropy",
+            ParamsOfMnemonicFromEntropy {
+                entropy: "1679b4516e0ee5954351d288a838f45e".into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+            },
+        )
+        .unwrap();
+    let combined: ResultOfMnemonicCombine = client
+        .request(
+            "crypto.mnemonic_combine",
+            ParamsOfMnemonicCombine {
+                shares: vec!["testify swimming academic academic column loyalty smear include exotic bedroom exotic wrist lobe cover grief golden smart junior estimate learn".into()],
+                dictionary: Some(1),
+                word_count: Some(12),
+                passphrase: Some("TREZOR".into()),
+            },
+        )
+        .unwrap();
+    assert_eq!(combined.phrase, expected.phrase);
+}
+
+#[test]
+fn mnemonic_suggest_and_repair() {
+    TestClient::init_log();
+    let client = TestClient::new();
+    let phrase = "abandon math mimic master filter design carbon crystal rookie group knife young";
+
+    let result: ResultOfMnemonicSuggest = client
+        .request(
+            "crypto.mnemonic_suggest",
+            ParamsOfMnemonicSuggest {
+                phrase: "aban abuot zoo".into(),
+                dictionary: Some(1),
+                max_distance: None,
+                max_suggestions: Some(3),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.words,
+        vec![
+            MnemonicWordSuggestions {
+                word: "aban".into(),
+                valid: false,
+                suggestions: vec!["abandon".into(), "able".into(), "again".into()],
+            },
+            MnemonicWordSuggestions {
+                word: "abuot".into(),
+                valid: false,
+                suggestions: vec!["about".into(), "abuse".into(), "adult".into()],
+            },
+            MnemonicWordSuggestions {
+                word: "zoo".into(),
+                valid: true,
+                suggestions: vec!["book".into(), "box".into(), "boy".into()],
+            },
+        ]
+    );
+
+    let repair = |phrase: &str| -> crate::error::ClientResult<Vec<String>> {
+        client
+            .request::<_, ResultOfMnemonicRepair>(
+                "crypto.mnemonic_repair",
+                ParamsOfMnemonicRepair {
+                    phrase: phrase.into(),
+                    dictionary: Some(1),
+                    word_count: Some(12),
+                    max_distance: None,
+                    max_phrases: None,
+                },
+            )
+            .map(|result| result.phrases)
+    };
+
+    assert_eq!(repair(phrase).unwrap(), vec![phrase.to_string()]);
+    assert_eq!(
+        repair("abandon math mimic master filter design carbon crystal rookie group knife yuong")
+            .unwrap(),
+        vec![phrase.to_string()]
+    );
+    assert_eq!(
+        repair("abandon math mimic mastre filter design carbon crystal rookie group knife yuong")
+            .unwrap(),
+        vec![
+            phrase.to_string(),
+            "abandon math mimic matter filter design carbon crystal rookie group knife wrong"
+                .to_string(),
+        ]
+    );
+    // All the words are in the dictionary, but the checksum does not match
+    assert_eq!(
+        repair("abandon math mimic master filter design carbon crystal rookie group knit young")
+            .unwrap(),
+        vec![
+            "abandon math mimic master filter design carbon crystal rookie group kit young"
+                .to_string(),
+            phrase.to_string(),
+            "abandon math mimic master filter design carbon crystal rookie group net young"
+                .to_string(),
+        ]
+    );
+
+    let error = repair("abandon math mimic").unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip39InvalidWordCount as u32);
+
+    let error = client
+        .request::<_, ResultOfMnemonicRepair>(
+            "crypto.mnemonic_repair",
+            ParamsOfMnemonicRepair {
+                phrase: "ab ab ab ".repeat(8),
+                dictionary: Some(0),
+                word_count: Some(24),
+                max_distance: None,
+                max_phrases: None,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::MnemonicRepairTooManyCandidates as u32);
+
+    // All the words of the TON phrase are in the dictionary, each of them is substituted
+    let ton_phrase = "unit follow zone decline glare flower crisp vocal adapt magic much mesh cherry teach mechanic rain float vicious solution assume hedgehog rail sort chuckle";
+    let result: ResultOfMnemonicRepair = client
+        .request(
+            "crypto.mnemonic_repair",
+            ParamsOfMnemonicRepair {
+                phrase: ton_phrase.replace(" sort ", " sport "),
+                dictionary: Some(0),
+                word_count: Some(24),
+                max_distance: None,
+                max_phrases: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.phrases,
+        vec![
+            ton_phrase.to_string(),
+            ton_phrase.replace(" rain ", " van ").replace(" sort ", " sport "),
+        ]
+    );
+}
+
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_signing_box() {
+    let client = std::sync::Arc::new(TestClient::new());
+    let client_copy = client.clone();
+
+    let keys = client.generate_sign_keys();
+
+    let keys_box_handle = client
+        .request_async::<_, RegisteredSigningBox>("crypto.get_signing_box", keys.clone())
+        .await
+        .unwrap()
+        .handle
+        .0;
+
+    // external signing box uses keys box inside
+    let callback = move |request: crate::client::ParamsOfAppRequest, _: u32| {
+        let client = client_copy.clone();
+        tokio::spawn(async move {
             match serde_json::from_value(request.request_data).unwrap() {
                 ParamsOfAppSigningBox::GetPublicKey => {
                     let result: ResultOfSigningBoxGetPublicKey = client
@@ -886,6 +1871,147 @@ async fn test_signing_box() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_policy_signing_box() {
+    use crate::abi::{
+        CallSet, FunctionHeader, ParamsOfCalcFunctionId, ParamsOfEncodeMessage,
+        ResultOfCalcFunctionId, ResultOfEncodeMessage, Signer,
+    };
+    use crate::crypto::{ParamsOfGetPolicySigningBox, SigningBoxPolicy, SigningBoxRateLimit};
+    use crate::tests::EVENTS;
+
+    let client = TestClient::new();
+    let keys = client.generate_sign_keys();
+    let abi = TestClient::abi(EVENTS, Some(2));
+    let address = "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309";
+
+    let keys_box: RegisteredSigningBox = client
+        .request_async("crypto.get_signing_box", keys.clone())
+        .await
+        .unwrap();
+    let function_id: ResultOfCalcFunctionId = client
+        .request_async(
+            "abi.calc_function_id",
+            ParamsOfCalcFunctionId {
+                abi: abi.clone(),
+                function_name: "returnValue".into(),
+                output: None,
+            },
+        )
+        .await
+        .unwrap();
+    let policy_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.get_policy_signing_box",
+            ParamsOfGetPolicySigningBox {
+                signing_box: keys_box.handle.clone(),
+                abi: abi.clone(),
+                policy: SigningBoxPolicy {
+                    allowed_addresses: Some(vec![address.into()]),
+                    allowed_function_ids: Some(vec![function_id.function_id]),
+                    max_value: Some("100".into()),
+                    value_param: Some("id".into()),
+                    rate_limit: Some(SigningBoxRateLimit {
+                        max_signatures: 1,
+                        period: 3600,
+                    }),
+                },
+            },
+        )
+        .await
+        .unwrap();
+
+    let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+        .request_async("crypto.signing_box_get_public_key", policy_box.clone())
+        .await
+        .unwrap();
+    assert_eq!(box_pubkey.pubkey, keys.public);
+
+    // Wrapped signing box is owned by the policy box and can not be used directly
+    let error = client
+        .request_async::<_, ResultOfSigningBoxSign>(
+            "crypto.signing_box_sign",
+            ParamsOfSigningBoxSign {
+                signing_box: keys_box.handle.clone(),
+                unsigned: base64::encode(&[0u8; 32]),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SigningBoxNotRegistered as u32);
+
+    let encode = |address: &str, function_name: &str, id: &str, signer: Signer| {
+        client.request_async::<_, ResultOfEncodeMessage>(
+            "abi.encode_message",
+            ParamsOfEncodeMessage {
+                abi: abi.clone(),
+                address: Some(address.into()),
+                call_set: Some(CallSet {
+                    function_name: function_name.into(),
+                    header: Some(FunctionHeader {
+                        pubkey: None,
+                        time: Some(1599458364291),
+                        expire: Some(1599458404),
+                    }),
+                    input: Some(json!({ "id": id })),
+                }),
+                signer,
+                ..Default::default()
+            },
+        )
+    };
+    let policy_signer = Signer::SigningBox {
+        handle: policy_box.handle.clone(),
+    };
+
+    let error = encode(
+        address,
+        "returnValue",
+        "1",
+        Signer::SigningBox { handle: keys_box.handle.clone() },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SigningBoxNotRegistered as u32);
+
+    let error = client
+        .request_async::<_, ResultOfSigningBoxSign>(
+            "crypto.signing_box_sign",
+            ParamsOfSigningBoxSign {
+                signing_box: policy_box.handle.clone(),
+                unsigned: base64::encode(&[0u8; 32]),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SigningBoxPolicyViolation as u32);
+
+    let violations = [
+        (address, "emitValue", "1"),
+        (address, "returnValue", "101"),
+        ("0:1111111111111111111111111111111111111111111111111111111111111111", "returnValue", "1"),
+    ];
+    for (address, function_name, id) in violations.iter() {
+        let error = encode(address, function_name, id, policy_signer.clone())
+            .await
+            .unwrap_err();
+        assert_eq!(error.code, ErrorCode::SigningBoxPolicyViolation as u32);
+    }
+
+    let signed = encode(address, "returnValue", "100", policy_signer.clone())
+        .await
+        .unwrap();
+    let expected = encode(address, "returnValue", "100", Signer::Keys { keys: keys.clone() })
+        .await
+        .unwrap();
+    assert_eq!(signed.message, expected.message);
+
+    let error = encode(address, "returnValue", "100", policy_signer)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::SigningBoxPolicyViolation as u32);
+}
+
 #[test]
 fn test_strip_secret() {
     assert_eq!(strip_secret(""), r#""""#);
@@ -1064,6 +2190,271 @@ async fn test_chacha20_encryption_box() {
     );
 }
 
+#[tokio::test]
+async fn test_aead_encryption_box() {
+    let client = Arc::new(TestClient::new());
+
+    let create = |algorithm: EncryptionAlgorithm| {
+        let client = client.clone();
+        async move {
+            client
+                .request_async::<_, RegisteredEncryptionBox>(
+                    "crypto.create_encryption_box",
+                    ParamsOfCreateEncryptionBox { algorithm },
+                )
+                .await
+                .unwrap()
+                .handle
+        }
+    };
+    let encrypt = |encryption_box: EncryptionBoxHandle, data: String| {
+        let client = client.clone();
+        async move {
+            client
+                .request_async::<_, ResultOfEncryptionBoxEncrypt>(
+                    "crypto.encryption_box_encrypt",
+                    ParamsOfEncryptionBoxEncrypt { encryption_box, data },
+                )
+                .await
+                .map(|result| result.data)
+        }
+    };
+    let decrypt = |encryption_box: EncryptionBoxHandle, data: String| {
+        let client = client.clone();
+        async move {
+            client
+                .request_async::<_, ResultOfEncryptionBoxDecrypt>(
+                    "crypto.encryption_box_decrypt",
+                    ParamsOfEncryptionBoxDecrypt { encryption_box, data },
+                )
+                .await
+                .map(|result| result.data)
+        }
+    };
+
+    // GCM test case 2 from the McGrew & Viega specification, the nonce is prepended
+    let aes_box = create(EncryptionAlgorithm::AesGcm(AesGcmParamsEB {
+        key: "00".repeat(16),
+        aad: None,
+    })).await;
+    let encrypted = hex::decode(format!(
+        "{}{}",
+        "00".repeat(12),
+        "0388dace60b6a392f328c2b971b2fe78ab6e47d42cec13bdf53a67b21257bddf",
+    )).unwrap();
+    assert_eq!(
+        decrypt(aes_box, base64::encode(&encrypted)).await.unwrap(),
+        base64::encode(&[0u8; 16]),
+    );
+
+    let key = "01".repeat(32);
+    let nonce = hex::decode("ff".repeat(12)).unwrap();
+    let aad = base64::encode("header");
+    let decrypted = base64::encode("Message");
+    let boxes = vec![
+        (
+            EncryptionAlgorithm::AesGcm(AesGcmParamsEB {
+                key: key.clone(),
+                aad: Some(aad.clone()),
+            }),
+            "CBf9LdlhzGnCef/503NInn+D1ctAa6Q=",
+        ),
+        (
+            EncryptionAlgorithm::ChaCha20Poly1305(ChaCha20Poly1305ParamsEB {
+                key: key.clone(),
+                aad: Some(aad.clone()),
+            }),
+            "016Tr9eE6p4K+9ObjfBXTcW9m0QFOCo=",
+        ),
+    ];
+    for (algorithm, expected) in boxes {
+        let encryption_box = create(algorithm).await;
+        let mut encrypted = nonce.clone();
+        encrypted.extend_from_slice(&base64::decode(expected).unwrap());
+        assert_eq!(
+            decrypt(encryption_box.clone(), base64::encode(&encrypted)).await.unwrap(),
+            decrypted,
+        );
+
+        let mut tampered = encrypted.clone();
+        tampered[12] ^= 1;
+        let error = decrypt(encryption_box.clone(), base64::encode(&tampered)).await.unwrap_err();
+        assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+        // Each encryption uses a fresh random nonce
+        let first = encrypt(encryption_box.clone(), decrypted.clone()).await.unwrap();
+        let second = encrypt(encryption_box.clone(), decrypted.clone()).await.unwrap();
+        assert_ne!(
+            base64::decode(&first).unwrap()[..12],
+            base64::decode(&second).unwrap()[..12],
+        );
+        assert_eq!(decrypt(encryption_box.clone(), first).await.unwrap(), decrypted);
+        assert_eq!(decrypt(encryption_box, second).await.unwrap(), decrypted);
+    }
+
+    let error = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::ChaCha20Poly1305(ChaCha20Poly1305ParamsEB {
+                    key: "01".repeat(16),
+                    aad: None,
+                }),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidKeySize as u32);
+}
+
+#[tokio::test]
+async fn test_encryption_box_stream() {
+    let client = TestClient::new();
+
+    let encryption_box = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::AesGcm(AesGcmParamsEB {
+                    key: "01".repeat(32),
+                    aad: Some(base64::encode("header")),
+                }),
+            },
+        )
+        .await
+        .unwrap()
+        .handle;
+
+    let decrypted: Vec<u8> = (0..100u8).collect();
+
+    let stream: RegisteredEncryptionStream = client
+        .request_async(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: encryption_box.clone(),
+                chunk_size: Some(16),
+            },
+        )
+        .await
+        .unwrap();
+    let mut encrypted = Vec::new();
+    for part in [&decrypted[..7], &decrypted[7..57], &decrypted[57..]].iter() {
+        let result: ResultOfEncryptionStreamUpdate = client
+            .request_async(
+                "crypto.encryption_box_encrypt_update",
+                ParamsOfEncryptionStreamUpdate {
+                    stream: stream.stream.clone(),
+                    data: base64::encode(part),
+                },
+            )
+            .await
+            .unwrap();
+        encrypted.append(&mut base64::decode(&result.data).unwrap());
+    }
+    let result: ResultOfEncryptionStreamFinish = client
+        .request_async("crypto.encryption_box_encrypt_finish", stream.clone())
+        .await
+        .unwrap();
+    encrypted.append(&mut base64::decode(&result.data).unwrap());
+    // header, 6 full chunks and the last chunk of 4 bytes, each with 16-byte tag
+    assert_eq!(encrypted.len(), 12 + 6 * 32 + 20);
+
+    let error = client
+        .request_async::<_, ResultOfEncryptionStreamFinish>(
+            "crypto.encryption_box_encrypt_finish",
+            stream,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::EncryptionStreamNotRegistered as u32);
+
+    let decrypt = |encrypted: Vec<u8>| {
+        let client = &client;
+        let encryption_box = encryption_box.clone();
+        async move {
+            let stream: RegisteredEncryptionStream = client
+                .request_async(
+                    "crypto.encryption_box_decrypt_init",
+                    ParamsOfEncryptionBoxDecryptInit { encryption_box },
+                )
+                .await?;
+            let mut decrypted = Vec::new();
+            for part in encrypted.chunks(13) {
+                let result: ResultOfEncryptionStreamUpdate = client
+                    .request_async(
+                        "crypto.encryption_box_decrypt_update",
+                        ParamsOfEncryptionStreamUpdate {
+                            stream: stream.stream.clone(),
+                            data: base64::encode(part),
+                        },
+                    )
+                    .await?;
+                decrypted.append(&mut base64::decode(&result.data).unwrap());
+            }
+            let result: ResultOfEncryptionStreamFinish = client
+                .request_async("crypto.encryption_box_decrypt_finish", stream)
+                .await?;
+            decrypted.append(&mut base64::decode(&result.data).unwrap());
+            crate::error::ClientResult::Ok(decrypted)
+        }
+    };
+
+    assert_eq!(decrypt(encrypted.clone()).await.unwrap(), decrypted);
+
+    let truncated = encrypted[..encrypted.len() - 20].to_vec();
+    let error = decrypt(truncated).await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+    let mut reordered = encrypted.clone();
+    let (first, second) = reordered[12..76].split_at_mut(32);
+    first.swap_with_slice(second);
+    let error = decrypt(reordered).await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+    // chunk size in the header is limited by 16 MiB
+    let mut oversized = encrypted.clone();
+    oversized[1..5].copy_from_slice(&((16u32 << 20) + 1).to_be_bytes());
+    let error = decrypt(oversized).await.unwrap_err();
+    assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+    let error = client
+        .request_async::<_, RegisteredEncryptionStream>(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: encryption_box.clone(),
+                chunk_size: Some((16 << 20) + 1),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::EncryptDataError as u32);
+
+    let nacl_box = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::NaclSecretBox(NaclSecretBoxParamsEB {
+                    key: "01".repeat(32),
+                    nonce: "cd7f99924bf422544046e83595dd5803f17536f5c9a11746".into(),
+                }),
+            },
+        )
+        .await
+        .unwrap()
+        .handle;
+    let error = client
+        .request_async::<_, RegisteredEncryptionStream>(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: nacl_box,
+                chunk_size: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::EncryptionStreamNotSupported as u32);
+}
+
 #[tokio::test]
 async fn test_nacl_encryption_box() {
     let client = Arc::new(TestClient::new());
@@ -1081,6 +2472,7 @@ async fn test_nacl_encryption_box() {
                     their_public: THEIR_PUBLIC.to_string(),
                     secret: SECRET.to_string(),
                     nonce: NONCE.to_string(),
+                    ed25519_keys: None,
                 }),
             },
         )
@@ -1140,6 +2532,96 @@ async fn test_nacl_encryption_box() {
     );
 }
 
+#[tokio::test]
+async fn test_nacl_encryption_box_ed25519_keys() {
+    let client = Arc::new(TestClient::new());
+    const NONCE: &str = "cd7f99924bf422544046e83595dd5803f17536f5c9a11746";
+    let keys: KeyPair = client.request_no_params("crypto.generate_random_sign_keys").unwrap();
+    let their_keys: KeyPair = client.request_no_params("crypto.generate_random_sign_keys").unwrap();
+
+    let box_handle = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::NaclBox(NaclBoxParamsEB {
+                    their_public: their_keys.public.clone(),
+                    secret: keys.secret.clone(),
+                    nonce: NONCE.to_string(),
+                    ed25519_keys: Some(true),
+                }),
+            },
+        )
+        .await
+        .unwrap()
+        .handle;
+
+    let decrypted: String = base64::encode("Test Message");
+    let encrypted: ResultOfEncryptionBoxEncrypt = client
+        .request_async(
+            "crypto.encryption_box_encrypt",
+            ParamsOfEncryptionBoxEncrypt {
+                encryption_box: box_handle.clone(),
+                data: decrypted.clone(),
+            },
+        )
+        .await
+        .unwrap();
+
+    // Receiver opens the box with X25519 keys converted from the ed25519 ones
+    let public: ResultOfConvertEd25519ToX25519Public = client
+        .request(
+            "crypto.convert_ed25519_to_x25519_public",
+            ParamsOfConvertEd25519ToX25519Public { public: keys.public.clone() },
+        )
+        .unwrap();
+    let their_secret: ResultOfConvertEd25519ToX25519Secret = client
+        .request(
+            "crypto.convert_ed25519_to_x25519_secret",
+            ParamsOfConvertEd25519ToX25519Secret { secret: their_keys.secret.clone() },
+        )
+        .unwrap();
+    let result: ResultOfNaclBoxOpen = client
+        .request(
+            "crypto.nacl_box_open",
+            ParamsOfNaclBoxOpen {
+                encrypted: encrypted.data,
+                nonce: NONCE.to_string(),
+                their_public: public.public.clone(),
+                secret: their_secret.secret.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(result.decrypted, decrypted);
+
+    let result: ResultOfEncryptionBoxGetInfo = client
+        .request_async(
+            "crypto.encryption_box_get_info",
+            ParamsOfEncryptionBoxGetInfo {
+                encryption_box: box_handle,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.info.public, Some(public.public.into()));
+
+    let error = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::NaclBox(NaclBoxParamsEB {
+                    // y = 2 is not a curve point
+                    their_public: format!("02{}", "00".repeat(31)),
+                    secret: keys.secret.clone(),
+                    nonce: NONCE.to_string(),
+                    ed25519_keys: Some(true),
+                }),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::InvalidPublicKey as u32);
+}
+
 #[tokio::test]
 async fn test_nacl_secret_encryption_box() {
     let client = Arc::new(TestClient::new());
@@ -1272,9 +2754,11 @@ async fn test_crypto_boxes() -> ton_types::Result<()> {
             "crypto.create_crypto_box",
             ParamsOfCreateCryptoBox {
                 secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
                 secret: CryptoBoxSecret::RandomSeedPhrase {
                     dictionary: Default::default(),
                     wordcount: 12,
+                    passphrase: None,
                 },
             },
             password_provider(&client, &password_hash, || ()),
@@ -1310,6 +2794,7 @@ async fn test_crypto_boxes() -> ton_types::Result<()> {
             "crypto.create_crypto_box",
             ParamsOfCreateCryptoBox {
                 secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
                 secret: CryptoBoxSecret::EncryptedSecret {
                     encrypted_secret: crypto_box_info.encrypted_secret.clone(),
                 },
@@ -1332,10 +2817,12 @@ async fn test_crypto_boxes() -> ton_types::Result<()> {
             "crypto.create_crypto_box",
             ParamsOfCreateCryptoBox {
                 secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
                 secret: CryptoBoxSecret::PredefinedSeedPhrase {
                     phrase: seed_phrase.phrase.clone(),
                     dictionary: 0,
                     wordcount: 12,
+                    passphrase: None,
                 },
             },
             password_provider(&client, &password_hash, || ()),
@@ -1354,6 +2841,224 @@ async fn test_crypto_boxes() -> ton_types::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_change_crypto_box_password() -> ton_types::Result<()> {
+    let client = Arc::new(TestClient::new());
+    let old_password_hash =
+        Arc::new("1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF".to_string());
+    let new_password_hash =
+        Arc::new("FEDCBA0987654321FEDCBA0987654321FEDCBA0987654321FEDCBA0987654321".to_string());
+    let salt = "123123123";
+    let phrase = "abandon math mimic master filter design carbon crystal rookie group knife young";
+
+    let old_provider_calls = Arc::new(AtomicUsize::new(0));
+    let calls = Arc::clone(&old_provider_calls);
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::PredefinedSeedPhrase {
+                    phrase: phrase.to_string(),
+                    dictionary: 1,
+                    wordcount: 12,
+                    passphrase: None,
+                },
+            },
+            password_provider(&client, &old_password_hash, move || {
+                calls.fetch_add(1, Ordering::Relaxed);
+            }),
+        )
+        .await?;
+
+    let result: ResultOfChangeCryptoBoxPassword = client
+        .request_async_callback(
+            "crypto.change_crypto_box_password",
+            ParamsOfChangeCryptoBoxPassword {
+                handle,
+                secret_encryption_kdf: None,
+            },
+            password_provider(&client, &new_password_hash, || ()),
+        )
+        .await?;
+    assert_ne!(result.secret_encryption_salt, salt);
+
+    let crypto_box_info: ResultOfGetCryptoBoxInfo = client
+        .request_async("crypto.get_crypto_box_info", RegisteredCryptoBox { handle })
+        .await?;
+    assert_eq!(crypto_box_info.encrypted_secret, result.encrypted_secret);
+
+    // Crypto box keeps the handle and asks the new password provider only
+    let old_calls = old_provider_calls.load(Ordering::Relaxed);
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase",
+            RegisteredCryptoBox { handle },
+        )
+        .await?;
+    assert_eq!(seed_phrase.phrase, phrase);
+    assert_eq!(old_provider_calls.load(Ordering::Relaxed), old_calls);
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: result.secret_encryption_salt.clone(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::EncryptedSecret {
+                    encrypted_secret: result.encrypted_secret.clone(),
+                },
+            },
+            password_provider(&client, &new_password_hash, || ()),
+        )
+        .await?;
+
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase",
+            RegisteredCryptoBox { handle },
+        )
+        .await?;
+    assert_eq!(seed_phrase.phrase, phrase);
+
+    let result: crate::error::ClientResult<ResultOfChangeCryptoBoxPassword> = client
+        .request_async_callback(
+            "crypto.change_crypto_box_password",
+            ParamsOfChangeCryptoBoxPassword {
+                handle: CryptoBoxHandle(handle.0 + 100),
+                secret_encryption_kdf: None,
+            },
+            password_provider(&client, &new_password_hash, || ()),
+        )
+        .await;
+    assert_eq!(result.unwrap_err().code, ErrorCode::CryptoBoxNotRegistered as u32);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_crypto_box_argon2id() -> ton_types::Result<()> {
+    let client = Arc::new(TestClient::new());
+    let password_hash =
+        Arc::new("1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF".to_string());
+    let salt = "123123123";
+    let phrase = "abandon math mimic master filter design carbon crystal rookie group knife young";
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: Some(SecretEncryptionKdf::Argon2id),
+                secret: CryptoBoxSecret::PredefinedSeedPhrase {
+                    phrase: phrase.to_string(),
+                    dictionary: 1,
+                    wordcount: 12,
+                    passphrase: None,
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    let argon2id_info: ResultOfGetCryptoBoxInfo = client
+        .request_async("crypto.get_crypto_box_info", RegisteredCryptoBox { handle })
+        .await?;
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::PredefinedSeedPhrase {
+                    phrase: phrase.to_string(),
+                    dictionary: 1,
+                    wordcount: 12,
+                    passphrase: None,
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    let scrypt_info: ResultOfGetCryptoBoxInfo = client
+        .request_async("crypto.get_crypto_box_info", RegisteredCryptoBox { handle })
+        .await?;
+    assert_eq!(argon2id_info.secret_encryption_kdf, SecretEncryptionKdf::Argon2id);
+    assert_eq!(scrypt_info.secret_encryption_kdf, SecretEncryptionKdf::Scrypt);
+
+    // Secret encrypted with Argon2id key is restored when the same KDF is specified
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: Some(argon2id_info.secret_encryption_kdf),
+                secret: CryptoBoxSecret::EncryptedSecret {
+                    encrypted_secret: argon2id_info.encrypted_secret.clone(),
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase",
+            RegisteredCryptoBox { handle },
+        )
+        .await?;
+    assert_eq!(seed_phrase.phrase, phrase);
+
+    // Migration of the scrypt encrypted secret to Argon2id
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::EncryptedSecret {
+                    encrypted_secret: scrypt_info.encrypted_secret.clone(),
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    let result: ResultOfChangeCryptoBoxPassword = client
+        .request_async_callback(
+            "crypto.change_crypto_box_password",
+            ParamsOfChangeCryptoBoxPassword {
+                handle,
+                secret_encryption_kdf: Some(SecretEncryptionKdf::Argon2id),
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    assert_eq!(result.secret_encryption_kdf, SecretEncryptionKdf::Argon2id);
+
+    let RegisteredCryptoBox { handle } = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: result.secret_encryption_salt.clone(),
+                secret_encryption_kdf: Some(result.secret_encryption_kdf),
+                secret: CryptoBoxSecret::EncryptedSecret {
+                    encrypted_secret: result.encrypted_secret.clone(),
+                },
+            },
+            password_provider(&client, &password_hash, || ()),
+        )
+        .await?;
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase",
+            RegisteredCryptoBox { handle },
+        )
+        .await?;
+    assert_eq!(seed_phrase.phrase, phrase);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_crypto_box_signing_boxes() -> ton_types::Result<()> {
     let client = Arc::new(TestClient::new());
@@ -1370,9 +3075,11 @@ async fn test_crypto_box_signing_boxes() -> ton_types::Result<()> {
             "crypto.create_crypto_box",
             ParamsOfCreateCryptoBox {
                 secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
                 secret: CryptoBoxSecret::RandomSeedPhrase {
                     dictionary: Default::default(),
                     wordcount: 12,
+                    passphrase: None,
                 },
             },
             password_provider(&client, &password_hash, move || {
@@ -1472,6 +3179,100 @@ async fn test_crypto_box_signing_boxes() -> ton_types::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_crypto_box_passphrase() -> ton_types::Result<()> {
+    let client = Arc::new(TestClient::new());
+    let password_hash =
+        Arc::new("1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF".to_string());
+    let salt = "123123123";
+    let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    let crypto_box: RegisteredCryptoBox = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::PredefinedSeedPhrase {
+                    phrase: phrase.to_string(),
+                    dictionary: 1,
+                    wordcount: 12,
+                    passphrase: Some("TREZOR".to_string()),
+                },
+            },
+            password_provider(&client, &password_hash, || {}),
+        )
+        .await?;
+
+    let seed_phrase: ResultOfGetCryptoBoxSeedPhrase = client
+        .request_async(
+            "crypto.get_crypto_box_seed_phrase",
+            RegisteredCryptoBox {
+                handle: crypto_box.handle.clone(),
+            },
+        )
+        .await?;
+    assert_eq!(seed_phrase.phrase, phrase);
+    assert_eq!(seed_phrase.passphrase, Some("TREZOR".to_string()));
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.get_signing_box_from_crypto_box",
+            ParamsOfGetSigningBoxFromCryptoBox {
+                handle: crypto_box.handle.0,
+                hdpath: None,
+                secret_lifetime: None,
+            },
+        )
+        .await?;
+    let ResultOfSigningBoxGetPublicKey { pubkey } = client
+        .request_async(
+            "crypto.signing_box_get_public_key",
+            RegisteredSigningBox {
+                handle: signing_box.handle.clone(),
+            },
+        )
+        .await?;
+
+    let keys: KeyPair = client
+        .request_async(
+            "crypto.mnemonic_derive_sign_keys",
+            ParamsOfMnemonicDeriveSignKeys {
+                phrase: phrase.to_string(),
+                path: None,
+                dictionary: Some(1),
+                word_count: Some(12),
+                derivation_scheme: None,
+                passphrase: Some("TREZOR".to_string()),
+            },
+        )
+        .await?;
+    assert_eq!(pubkey, keys.public);
+
+    // TON phrases don't support passphrase, the box is not created
+    let result: crate::error::ClientResult<RegisteredCryptoBox> = client
+        .request_async_callback(
+            "crypto.create_crypto_box",
+            ParamsOfCreateCryptoBox {
+                secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
+                secret: CryptoBoxSecret::RandomSeedPhrase {
+                    dictionary: 0,
+                    wordcount: 24,
+                    passphrase: Some("TREZOR".to_string()),
+                },
+            },
+            password_provider(&client, &password_hash, || {}),
+        )
+        .await;
+    assert_eq!(
+        result.unwrap_err().code,
+        ErrorCode::MnemonicPassphraseNotSupported as u32
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_crypto_box_encryption_boxes() -> ton_types::Result<()> {
     let client = Arc::new(TestClient::new());
@@ -1489,9 +3290,11 @@ async fn test_crypto_box_encryption_boxes() -> ton_types::Result<()> {
             "crypto.create_crypto_box",
             ParamsOfCreateCryptoBox {
                 secret_encryption_salt: salt.to_string(),
+                secret_encryption_kdf: None,
                 secret: CryptoBoxSecret::RandomSeedPhrase {
                     dictionary: Default::default(),
                     wordcount: 12,
+                    passphrase: None,
                 },
             },
             password_provider(&client, &password_hash, move || {
@@ -1605,6 +3408,49 @@ async fn test_crypto_box_encryption_boxes() -> ton_types::Result<()> {
         assert_eq!(callback_calls_counter.load(Ordering::Relaxed), 5);
     }
 
+    // streams holding the derived key are removed with the crypto box secret
+    let aead_box: RegisteredEncryptionBox = client
+        .request_async(
+            "crypto.get_encryption_box_from_crypto_box",
+            ParamsOfGetEncryptionBoxFromCryptoBox {
+                handle: crypto_box.handle.0,
+                hdpath: None,
+                algorithm: BoxEncryptionAlgorithm::AesGcm(AesGcmParamsCB { aad: None }),
+                secret_lifetime: None,
+            },
+        )
+        .await?;
+    let stream: RegisteredEncryptionStream = client
+        .request_async(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: aead_box.handle,
+                chunk_size: None,
+            },
+        )
+        .await?;
+
+    client
+        .request_async(
+            "crypto.clear_crypto_box_secret_cache",
+            RegisteredCryptoBox {
+                handle: crypto_box.handle.clone(),
+            },
+        )
+        .await?;
+
+    let error = client
+        .request_async::<_, ResultOfEncryptionStreamUpdate>(
+            "crypto.encryption_box_encrypt_update",
+            ParamsOfEncryptionStreamUpdate {
+                stream: stream.stream,
+                data: base64::encode("data"),
+            },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::EncryptionStreamNotRegistered as u32);
+
     Ok(())
 }
 
@@ -1623,9 +3469,11 @@ async fn test_crypto_box_derive_key_cache() -> ton_types::Result<()> {
                 "crypto.create_crypto_box",
                 ParamsOfCreateCryptoBox {
                     secret_encryption_salt: salt.to_string(),
+                    secret_encryption_kdf: None,
                     secret: CryptoBoxSecret::PredefinedSeedPhrase {
                         dictionary: 1,
                         wordcount: 12,
+                        passphrase: None,
                         phrase: phrase.into(),
                     },
                 },
@@ -1642,9 +3490,11 @@ async fn test_crypto_box_derive_key_cache() -> ton_types::Result<()> {
                 "crypto.create_crypto_box",
                 ParamsOfCreateCryptoBox {
                     secret_encryption_salt: format!("{}{}", salt, i),
+                    secret_encryption_kdf: None,
                     secret: CryptoBoxSecret::PredefinedSeedPhrase {
                         dictionary: 1,
                         wordcount: 12,
+                        passphrase: None,
                         phrase: phrase.into(),
                     },
                 },
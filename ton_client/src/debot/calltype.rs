@@ -5,6 +5,7 @@ use crate::abi::Signer;
 use crate::boc::internal::{deserialize_object_from_base64, serialize_object_to_base64};
 use crate::boc::{get_boc_hash, parse_message, ParamsOfParse, ParamsOfGetBocHash};
 use crate::crypto::{KeyPair, SigningBoxHandle, get_signing_box};
+use crate::crypto::boxes::signing_box::SigningBoxMessage;
 use crate::encoding::decode_abi_number;
 use crate::error::{ClientError, ClientResult};
 use crate::processing::{
@@ -218,7 +219,11 @@ async fn decode_and_fix_ext_msg(
                 new_body.clone()
             };
             let hash = sdata.into_cell().map_err(msg_err)?.repr_hash().as_slice().to_vec();
-            let signature = signer.sign(ton.clone(), &hash).await?;
+            let signing_message = SigningBoxMessage {
+                dst: msg.dst(),
+                unsigned_body: new_body.clone().into_cell().map_err(msg_err)?,
+            };
+            let signature = signer.sign_message(ton.clone(), &signing_message, &hash).await?;
             if let Some(signature) = signature {
                 signed_body
                     .append_bit_one()
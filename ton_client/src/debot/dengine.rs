@@ -722,6 +722,7 @@ impl DEngine {
             },
             signer: Signer::None,
             processing_try_index: None,
+            boc_serialization: None,
         };
 
         let result = encode_message(ton.clone(), msg_params).await?;
@@ -775,6 +776,7 @@ impl DEngine {
                 None => Signer::None,
             },
             processing_try_index: None,
+            boc_serialization: None,
         };
 
         let browser = self.browser.clone();
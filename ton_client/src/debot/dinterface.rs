@@ -18,7 +18,6 @@ use ton_abi::{Contract, ParamType};
 use ton_types::SliceData;
 
 use crate::boc::internal::deserialize_cell_from_boc;
-use ton_sdk::AbiContract;
 use ton_abi::token::Detokenizer;
 
 async fn decode_msg(
@@ -26,8 +25,7 @@ async fn decode_msg(
     msg_body: String,
     abi: Abi,
 ) -> ClientResult<(String, Value)> {
-    let abi = abi.json_string()?;
-    let abi = AbiContract::load(abi.as_bytes()).map_err(|e| Error::invalid_json(e))?;
+    let abi = abi.abi(&client)?;
     let (_, body) = deserialize_cell_from_boc(&client, &msg_body, "message body").await?;
     let body: SliceData = body.into();
     let input = abi.decode_input(body, true, false)
@@ -7,7 +7,6 @@ use crate::debot::TonClient;
 use crate::encoding::decode_abi_bigint;
 use serde_json::Value;
 use std::sync::Arc;
-use ton_abi::Contract;
 use crate::abi::Signer;
 use crate::boc::{parse_message, ParamsOfParse};
 use crate::debot::DEngine;
@@ -110,8 +109,7 @@ impl MsgInterface {
         )
         .await
         .map_err(|e| format!("failed to decode message: {}", e))?;
-        let abi_str = self.debot_abi.json_string().unwrap();
-        let contract = Contract::load(abi_str.as_bytes()).map_err(|e| format!("{}", e))?;
+        let contract = self.debot_abi.abi(&self.ton).map_err(|e| format!("{}", e))?;
         let answer_id = contract
             .function(&result.name)
             .map_err(|e| format!("{}", e))?
@@ -152,8 +150,7 @@ impl MsgInterface {
         )
         .await
         .map_err(|e| format!("failed to decode message: {}", e))?;
-        let abi_str = self.debot_abi.json_string().unwrap();
-        let contract = Contract::load(abi_str.as_bytes()).map_err(|e| format!("{}", e))?;
+        let contract = self.debot_abi.abi(&self.ton).map_err(|e| format!("{}", e))?;
         let answer_id = contract
             .function(&result.name)
             .map_err(|e| format!("{}", e))?
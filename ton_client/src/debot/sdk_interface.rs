@@ -438,6 +438,8 @@ impl SdkInterface {
                 path: if path == "" { None } else { Some(path) },
                 dictionary: None,
                 word_count: None,
+                derivation_scheme: None,
+                passphrase: None,
             },
         )
         .map_err(|e| format!("{}", e))?;
@@ -475,6 +477,8 @@ impl SdkInterface {
                 phrase,
                 dictionary: None,
                 word_count: None,
+                derivation_scheme: None,
+                passphrase: None,
             },
         )
         .map_err(|e| format!("{}", e))?;
@@ -503,6 +507,7 @@ impl SdkInterface {
                 xprv,
                 child_index,
                 hardened,
+                derivation_scheme: None,
             },
         )
         .map_err(|e| format!("{}", e))?;
@@ -518,7 +523,11 @@ impl SdkInterface {
         let path = get_arg(args, "path")?;
         let result = hdkey_derive_from_xprv_path(
             self.ton.clone(),
-            ParamsOfHDKeyDeriveFromXPrvPath { xprv, path },
+            ParamsOfHDKeyDeriveFromXPrvPath {
+                xprv,
+                path,
+                derivation_scheme: None,
+            },
         )
         .map_err(|e| format!("{}", e))?;
         Ok((
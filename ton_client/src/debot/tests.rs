@@ -487,6 +487,7 @@ async fn init_debot(client: Arc<TestClient>) -> DebotData {
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -503,6 +504,7 @@ async fn init_debot(client: Arc<TestClient>) -> DebotData {
             }),
             signer: Signer::Keys { keys: keys.clone() },
             processing_try_index: None,
+            boc_serialization: None,
             address: None,
             call_set: CallSet::some_with_function("constructor"),
         },
@@ -517,12 +519,13 @@ async fn init_debot(client: Arc<TestClient>) -> DebotData {
             }),
             signer: Signer::Keys { keys: keys.clone() },
             processing_try_index: None,
+            boc_serialization: None,
             address: None,
             call_set: Some(CallSet {
                 function_name: "constructor".to_owned(),
                 header: None,
                 input: Some(json!({
-                    "targetAbi": hex::encode(&target_abi.json_string().unwrap().as_bytes()),
+                    "targetAbi": hex::encode(&target_abi.json_string(&client.context()).unwrap().as_bytes()),
                     "targetAddr": target_addr,
                 }))
             }),
@@ -537,7 +540,7 @@ async fn init_debot(client: Arc<TestClient>) -> DebotData {
         debot_abi.clone(),
         "setAbi",
         json!({
-            "debotAbi": hex::encode(&debot_abi.json_string().unwrap().as_bytes())
+            "debotAbi": hex::encode(&debot_abi.json_string(&client.context()).unwrap().as_bytes())
         }),
         Signer::None,
     ).await.unwrap();
@@ -546,7 +549,7 @@ async fn init_debot(client: Arc<TestClient>) -> DebotData {
         debot_addr,
         target_addr,
         keys,
-        abi: debot_abi.json_string().unwrap(),
+        abi: debot_abi.json_string(&client.context()).unwrap(),
     };
     *debot = Some(data.clone());
     data
@@ -568,6 +571,7 @@ async fn init_debot2(client: Arc<TestClient>) -> DebotData {
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc(TEST_DEBOT2, Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set,
     };
@@ -576,11 +580,11 @@ async fn init_debot2(client: Arc<TestClient>) -> DebotData {
         debot_addr.clone(),
         debot_abi.clone(),
         "setAbi",
-        json!({ "debotAbi": hex::encode(&debot_abi.json_string().unwrap().as_bytes()) }),
+        json!({ "debotAbi": hex::encode(&debot_abi.json_string(&client.context()).unwrap().as_bytes()) }),
         Signer::Keys { keys: keys.clone() },
     ).await.unwrap();
     let target_addr = String::new();
-    DebotData { debot_addr, target_addr, keys, abi: debot_abi.json_string().unwrap(), }
+    DebotData { debot_addr, target_addr, keys, abi: debot_abi.json_string(&client.context()).unwrap(), }
 }
 
 async fn init_debot4(client: Arc<TestClient>) -> DebotData {
@@ -592,6 +596,7 @@ async fn init_debot4(client: Arc<TestClient>) -> DebotData {
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc(TEST_DEBOT_TARGET, Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -600,7 +605,7 @@ async fn init_debot4(client: Arc<TestClient>) -> DebotData {
     let call_set = CallSet::some_with_function_and_input(
         "constructor",
         json!({
-            "targetAbi": hex::encode(&target_abi.json_string().unwrap().as_bytes()),
+            "targetAbi": hex::encode(&target_abi.json_string(&client.context()).unwrap().as_bytes()),
             "targetAddr": target_addr,
         }),
     );
@@ -609,6 +614,7 @@ async fn init_debot4(client: Arc<TestClient>) -> DebotData {
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc(TEST_DEBOT4, Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set,
     };
@@ -618,7 +624,7 @@ async fn init_debot4(client: Arc<TestClient>) -> DebotData {
         debot_abi.clone(),
         "setAbi",
         json!({
-            "debotAbi": hex::encode(&debot_abi.json_string().unwrap().as_bytes())
+            "debotAbi": hex::encode(&debot_abi.json_string(&client.context()).unwrap().as_bytes())
         }),
         Signer::Keys { keys: keys.clone() },
     ).await.unwrap();
@@ -637,7 +643,7 @@ async fn init_debot4(client: Arc<TestClient>) -> DebotData {
         debot_addr,
         target_addr,
         keys,
-        abi: debot_abi.json_string().unwrap(),
+        abi: debot_abi.json_string(&client.context()).unwrap(),
     }
 }
 
@@ -655,6 +661,7 @@ async fn init_simple_debot(client: Arc<TestClient>, name: &str) -> DebotData {
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc(name, Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set,
     };
@@ -663,14 +670,14 @@ async fn init_simple_debot(client: Arc<TestClient>, name: &str) -> DebotData {
         debot_addr.clone(),
         debot_abi.clone(),
         "setABI",
-        json!({ "dabi": hex::encode(&debot_abi.json_string().unwrap().as_bytes()) }),
+        json!({ "dabi": hex::encode(&debot_abi.json_string(&client.context()).unwrap().as_bytes()) }),
         Signer::Keys { keys: keys.clone() },
     ).await.unwrap();
     DebotData {
         debot_addr,
         target_addr: String::new(),
         keys,
-        abi: debot_abi.json_string().unwrap(),
+        abi: debot_abi.json_string(&client.context()).unwrap(),
     }
 }
 
@@ -699,12 +706,12 @@ async fn init_debot5(client: Arc<TestClient>, count: u32) -> (String, String) {
                 debot_addr.clone(),
                 debot_abi.clone(),
                 "setABI",
-                json!({ "dabi": hex::encode(&debot_abi.json_string().unwrap().as_bytes()) }),
+                json!({ "dabi": hex::encode(&debot_abi.json_string(&client.context()).unwrap().as_bytes()) }),
                 Signer::Keys { keys: keys.clone() },
             ).await.unwrap();
         }
     }
-    (addrs[0].clone(), debot_abi.json_string().unwrap())
+    (addrs[0].clone(), debot_abi.json_string(&client.context()).unwrap())
 }
 
 async fn init_debot_pair(client: Arc<TestClient>, debot1: &str, debot2: &str) -> (String, String, String) {
@@ -720,6 +727,7 @@ async fn init_debot_pair(client: Arc<TestClient>, debot1: &str, debot2: &str) ->
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -734,6 +742,7 @@ async fn init_debot_pair(client: Arc<TestClient>, debot1: &str, debot2: &str) ->
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc(debot1, Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set,
     };
@@ -744,7 +753,7 @@ async fn init_debot_pair(client: Arc<TestClient>, debot1: &str, debot2: &str) ->
         debot1_addr.clone(),
         debot1_abi.clone(),
         "setAbi",
-        json!({ "debotAbi": hex::encode(&debot1_abi.json_string().unwrap().as_bytes()) }),
+        json!({ "debotAbi": hex::encode(&debot1_abi.json_string(&client.context()).unwrap().as_bytes()) }),
         Signer::Keys { keys: keys.clone() },
     );
 
@@ -752,13 +761,13 @@ async fn init_debot_pair(client: Arc<TestClient>, debot1: &str, debot2: &str) ->
         debot2_addr.clone(),
         debot2_abi.clone(),
         "setAbi",
-        json!({ "debotAbi": hex::encode(&debot2_abi.json_string().unwrap().as_bytes()) }),
+        json!({ "debotAbi": hex::encode(&debot2_abi.json_string(&client.context()).unwrap().as_bytes()) }),
         Signer::Keys { keys: keys.clone() },
     );
 
     let (_, _) = futures::join!(future1, future2);
 
-    (debot1_addr, debot2_addr, debot1_abi.json_string().unwrap())
+    (debot1_addr, debot2_addr, debot1_abi.json_string(&client.context()).unwrap())
 }
 
 async fn init_hello_debot(client: Arc<TestClient>) -> DebotData {
@@ -798,7 +807,7 @@ async fn get_code_hash_from_tvc(client: Arc<TestClient>, name: &str) -> String {
     let debot_tvc = TestClient::tvc(name, Some(2));
     let result: ResultOfGetCodeFromTvc = client.request_async(
         "boc.get_code_from_tvc",
-        ParamsOfGetCodeFromTvc { tvc: debot_tvc }
+        ParamsOfGetCodeFromTvc { tvc: debot_tvc, ..Default::default() }
     ).await.unwrap();
 
     let result: ResultOfGetBocHash = client.request_async(
@@ -1445,6 +1454,7 @@ async fn test_debot_custom_header() {
         deploy_set: DeploySet::some_with_tvc(TestClient::tvc("CustomReplayProtection", Some(2))),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set,
     };
@@ -1632,6 +1642,7 @@ async fn assert_get_method(
         deploy_set: None,
         signer: Signer::None,
         processing_try_index: None,
+        boc_serialization: None,
         address: Some(addr.clone()),
         call_set: CallSet::some_with_function_and_input(func, params),
     };
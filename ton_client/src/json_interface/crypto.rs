@@ -16,7 +16,10 @@ use std::sync::Arc;
 
 use crate::client::{AppObject, ClientContext, Error};
 use crate::crypto::{EncryptionBoxInfo, RegisteredEncryptionBox, RegisteredSigningBox, SigningBox};
-use crate::crypto::boxes::crypto_box::{AppPasswordProvider, ParamsOfCreateCryptoBox, RegisteredCryptoBox, ResultOfGetPassword};
+use crate::crypto::boxes::crypto_box::{
+    AppPasswordProvider, ParamsOfChangeCryptoBoxPassword, ParamsOfCreateCryptoBox,
+    RegisteredCryptoBox, ResultOfChangeCryptoBoxPassword, ResultOfGetPassword,
+};
 use crate::crypto::boxes::encryption_box::EncryptionBox;
 use crate::crypto::internal::hex_decode_secret_const;
 use crate::encoding::base64_decode;
@@ -271,3 +274,25 @@ pub(crate) async fn create_crypto_box(
         Arc::new(ExternalPasswordProvider { app_object: password_provider }),
     ).await
 }
+
+/// Changes Crypto Box password.
+///
+/// Decrypts the secret with the password from the current `password_provider`
+/// and encrypts it with a fresh random salt and the password from the new `password_provider`.
+/// Crypto box keeps its handle and uses the new password provider for further operations.
+/// Pass `secret_encryption_kdf` to switch the key derivation function, e.g. to `Argon2id`.
+///
+/// Store the returned `encrypted_secret` and `secret_encryption_salt` and use them
+/// for all the further crypto box initializations.
+#[api_function]
+pub(crate) async fn change_crypto_box_password(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfChangeCryptoBoxPassword,
+    password_provider: AppObject<ParamsOfAppPasswordProvider, ResultOfAppPasswordProvider>,
+) -> ClientResult<ResultOfChangeCryptoBoxPassword> {
+    crate::crypto::boxes::crypto_box::change_crypto_box_password(
+        context,
+        params,
+        Arc::new(ExternalPasswordProvider { app_object: password_provider }),
+    ).await
+}
@@ -69,12 +69,22 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::crypto::ChaCha20ParamsEB>();
     module.register_type::<crate::crypto::NaclBoxParamsEB>();
     module.register_type::<crate::crypto::NaclSecretBoxParamsEB>();
+    module.register_type::<crate::crypto::AesGcmParamsEB>();
+    module.register_type::<crate::crypto::ChaCha20Poly1305ParamsEB>();
     module.register_type::<crate::crypto::CryptoBoxSecret>();
     module.register_type::<crate::crypto::CryptoBoxHandle>();
+    module.register_type::<crate::crypto::SecretEncryptionKdf>();
     module.register_type::<crate::crypto::BoxEncryptionAlgorithm>();
     module.register_type::<crate::crypto::ChaCha20ParamsCB>();
     module.register_type::<crate::crypto::NaclBoxParamsCB>();
     module.register_type::<crate::crypto::NaclSecretBoxParamsCB>();
+    module.register_type::<crate::crypto::AesGcmParamsCB>();
+    module.register_type::<crate::crypto::ChaCha20Poly1305ParamsCB>();
+    module.register_type::<crate::crypto::HDKeyDerivationScheme>();
+    module.register_type::<crate::crypto::MnemonicWordSuggestions>();
+    module.register_type::<crate::crypto::KeystoreSecret>();
+    module.register_type::<crate::crypto::KeystoreCipher>();
+    module.register_type::<crate::crypto::KeystoreKdfParams>();
 
     // Math
 
@@ -95,6 +105,14 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::convert_public_key_to_ton_safe_format,
         crate::crypto::keys::convert_public_key_to_ton_safe_format_api,
     );
+    module.register_sync_fn(
+        crate::crypto::convert_ed25519_to_x25519_public,
+        crate::crypto::keys::convert_ed25519_to_x25519_public_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::convert_ed25519_to_x25519_secret,
+        crate::crypto::keys::convert_ed25519_to_x25519_secret_api,
+    );
 
     module.register_sync_fn_without_args(
         crate::crypto::generate_random_sign_keys,
@@ -106,10 +124,42 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::keys::verify_signature_api,
     );
 
+    // Secp256k1
+
+    module.register_sync_fn_without_args(
+        crate::crypto::secp256k1_generate_random_keys,
+        crate::crypto::secp256k1::secp256k1_generate_random_keys_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_public_key,
+        crate::crypto::secp256k1::secp256k1_public_key_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_sign,
+        crate::crypto::secp256k1::secp256k1_sign_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_verify,
+        crate::crypto::secp256k1::secp256k1_verify_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_recover,
+        crate::crypto::secp256k1::secp256k1_recover_api,
+    );
+
     // Sha
 
     module.register_sync_fn(crate::crypto::sha256, crate::crypto::hash::sha256_api);
     module.register_sync_fn(crate::crypto::sha512, crate::crypto::hash::sha512_api);
+    module.register_sync_fn(crate::crypto::keccak256, crate::crypto::hash::keccak256_api);
+    module.register_sync_fn(crate::crypto::sha3_256, crate::crypto::hash::sha3_256_api);
+    module.register_sync_fn(crate::crypto::sha3_512, crate::crypto::hash::sha3_512_api);
+    module.register_sync_fn(crate::crypto::blake2b, crate::crypto::hash::blake2b_api);
+
+    // Hmac
+
+    module.register_sync_fn(crate::crypto::hmac_sha256, crate::crypto::hash::hmac_sha256_api);
+    module.register_sync_fn(crate::crypto::hmac_sha512, crate::crypto::hash::hmac_sha512_api);
 
     // Scrypt
 
@@ -117,6 +167,27 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::encscrypt::scrypt,
         crate::crypto::encscrypt::scrypt_api,
     );
+    module.register_sync_fn(
+        crate::crypto::encscrypt::argon2id,
+        crate::crypto::encscrypt::argon2id_api,
+    );
+
+    // Hkdf
+
+    module.register_type::<crate::crypto::HkdfHashAlgorithm>();
+    module.register_sync_fn(crate::crypto::hkdf_extract, crate::crypto::kdf::hkdf_extract_api);
+    module.register_sync_fn(crate::crypto::hkdf_expand, crate::crypto::kdf::hkdf_expand_api);
+
+    // Keystore
+
+    module.register_sync_fn(
+        crate::crypto::export_keystore,
+        crate::crypto::keystore::export_keystore_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::import_keystore,
+        crate::crypto::keystore::import_keystore_api,
+    );
 
     // NaCl
 
@@ -182,6 +253,22 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::mnemonic_derive_sign_keys,
         crate::crypto::mnemonic::mnemonic_derive_sign_keys_api,
     );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_split,
+        crate::crypto::mnemonic::mnemonic_split_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_combine,
+        crate::crypto::mnemonic::mnemonic_combine_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_suggest,
+        crate::crypto::mnemonic::mnemonic_suggest_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_repair,
+        crate::crypto::mnemonic::mnemonic_repair_api,
+    );
 
     // HDKey
 
@@ -232,6 +319,10 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::get_crypto_box_seed_phrase,
         crate::crypto::boxes::crypto_box::get_crypto_box_seed_phrase_api,
     );
+    module.register_async_fn_with_app_object(
+        super::crypto::change_crypto_box_password,
+        super::crypto::change_crypto_box_password_api,
+    );
     module.register_async_fn(
         crate::crypto::get_signing_box_from_crypto_box,
         crate::crypto::boxes::crypto_box::get_signing_box_from_crypto_box_api,
@@ -254,6 +345,10 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::get_signing_box,
         crate::crypto::boxes::signing_box::get_signing_box_api,
     );
+    module.register_async_fn(
+        crate::crypto::get_secp256k1_signing_box,
+        crate::crypto::boxes::signing_box::get_secp256k1_signing_box_api,
+    );
     module.register_async_fn(
         crate::crypto::signing_box_get_public_key,
         crate::crypto::boxes::signing_box::signing_box_get_public_key_api,
@@ -266,6 +361,12 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::remove_signing_box,
         crate::crypto::boxes::signing_box::remove_signing_box_api,
     );
+    module.register_type::<crate::crypto::SigningBoxPolicy>();
+    module.register_type::<crate::crypto::SigningBoxRateLimit>();
+    module.register_async_fn(
+        crate::crypto::get_policy_signing_box,
+        crate::crypto::boxes::policy_signing_box::get_policy_signing_box_api,
+    );
 
     // Encryption box
     module.register_async_fn_with_app_object_no_args(
@@ -288,6 +389,31 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::encryption_box_decrypt,
         crate::crypto::boxes::encryption_box::encryption_box_decrypt_api,
     );
+    module.register_type::<crate::crypto::EncryptionStreamHandle>();
+    module.register_async_fn(
+        crate::crypto::encryption_box_encrypt_init,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_init_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::encryption_box_encrypt_update,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_update_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::encryption_box_encrypt_finish,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_finish_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_decrypt_init,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_init_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::encryption_box_decrypt_update,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_update_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::encryption_box_decrypt_finish,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_finish_api,
+    );
     module.register_async_fn(
         crate::crypto::create_encryption_box,
         crate::crypto::boxes::encryption_box::create_encryption_box_api,
@@ -381,6 +507,14 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
         crate::abi::calc_function_id,
         crate::abi::function_id::calc_function_id_api,
     );
+    module.register_sync_fn(
+        crate::abi::register_abi,
+        crate::abi::registry::register_abi_api,
+    );
+    module.register_sync_fn(
+        crate::abi::remove_abi,
+        crate::abi::registry::remove_abi_api,
+    );
     module.register();
 }
 
@@ -392,6 +526,7 @@ pub(crate) struct BocModule;
 fn register_boc(handlers: &mut RuntimeHandlers) {
     let mut module = ModuleReg::new::<BocModule>(handlers);
     module.register_type::<crate::boc::BocCacheType>();
+    module.register_type::<crate::boc::BocSerializationOptions>();
     module.register_error_code::<crate::boc::ErrorCode>();
     module.register_async_fn(
         crate::boc::parse_message,
@@ -414,6 +549,14 @@ fn register_boc(handlers: &mut RuntimeHandlers) {
         crate::boc::get_blockchain_config,
         crate::boc::blockchain_config::get_blockchain_config_api,
     );
+    module.register_async_fn(
+        crate::boc::parse_blockchain_config,
+        crate::boc::blockchain_config::parse_blockchain_config_api,
+    );
+    module.register_async_fn(
+        crate::boc::update_blockchain_config,
+        crate::boc::blockchain_config::update_blockchain_config_api,
+    );
     module.register_async_fn(
         crate::boc::get_boc_hash,
         crate::boc::common::get_boc_hash_api,
@@ -430,6 +573,7 @@ fn register_boc(handlers: &mut RuntimeHandlers) {
     module.register_async_fn(crate::boc::cache_set, crate::boc::cache::cache_set_api);
     module.register_async_fn(crate::boc::cache_unpin, crate::boc::cache::cache_unpin_api);
     module.register_type::<BuilderOp>();
+    module.register_type::<crate::boc::BuilderDictEntry>();
     module.register_async_fn(crate::boc::encode_boc, crate::boc::encode::encode_boc_api);
     module.register_async_fn(
         crate::boc::get_code_salt,
@@ -449,6 +593,42 @@ fn register_boc(handlers: &mut RuntimeHandlers) {
         crate::boc::get_compiler_version,
         crate::boc::tvc::get_compiler_version_api,
     );
+    module.register_async_fn(crate::boc::query_cell, crate::boc::reader::query_cell_api);
+    module.register_async_fn(crate::boc::parse_tlb, crate::boc::tlb::parse_tlb_api);
+    module.register_async_fn(crate::boc::encode_tlb, crate::boc::tlb::encode_tlb_api);
+    module.register_type::<crate::boc::CellDiff>();
+    module.register_type::<crate::boc::SharedSubtree>();
+    module.register_async_fn(crate::boc::diff, crate::boc::diff::diff_api);
+    module.register_async_fn(crate::boc::cell_to_text, crate::boc::text::cell_to_text_api);
+    module.register_async_fn(crate::boc::cell_from_text, crate::boc::text::cell_from_text_api);
+    module.register_async_fn(
+        crate::boc::create_merkle_proof,
+        crate::boc::merkle::create_merkle_proof_api,
+    );
+    module.register_async_fn(
+        crate::boc::verify_merkle_proof,
+        crate::boc::merkle::verify_merkle_proof_api,
+    );
+    module.register_async_fn(
+        crate::boc::create_shardstate_iterator,
+        crate::boc::shardstate_iterator::create_shardstate_iterator_api,
+    );
+    module.register_async_fn(
+        crate::boc::shardstate_iterator_next,
+        crate::boc::shardstate_iterator::shardstate_iterator_next_api,
+    );
+    module.register_async_fn(
+        crate::boc::remove_shardstate_iterator,
+        crate::boc::shardstate_iterator::remove_shardstate_iterator_api,
+    );
+    module.register_async_fn(
+        crate::boc::add_libraries,
+        crate::boc::libraries::add_libraries_api,
+    );
+    module.register_async_fn(
+        crate::boc::remove_libraries,
+        crate::boc::libraries::remove_libraries_api,
+    );
     module.register();
 }
 
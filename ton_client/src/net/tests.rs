@@ -386,6 +386,7 @@ async fn subscribe_for_transactions_with_addresses() {
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -551,6 +552,7 @@ async fn subscribe_for_transactions_with_addresses() {
                     deploy_set: None,
                     signer: Signer::Keys { keys },
                     processing_try_index: None,
+                    boc_serialization: None,
                     address: Some(msg.address),
                     call_set: CallSet::some_with_function("touch"),
                 },
@@ -138,6 +138,7 @@ async fn test_wait_message() {
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
     };
 
     let encoded = client.encode_message(encode_params.clone()).await.unwrap();
@@ -217,6 +218,7 @@ async fn test_process_message() {
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
     };
 
     let encoded = client.encode_message(encode_params.clone()).await.unwrap();
@@ -274,6 +276,7 @@ async fn test_process_message() {
                     ),
                     signer: Signer::Keys { keys: keys.clone() },
                     processing_try_index: None,
+                    boc_serialization: None,
                 },
                 send_events: true,
             },
@@ -337,6 +340,7 @@ async fn test_error_resolving() {
         }),
         signer: Signer::Keys { keys: keys.clone() },
         processing_try_index: None,
+        boc_serialization: None,
         address: None,
         call_set: CallSet::some_with_function("constructor"),
     };
@@ -352,6 +356,7 @@ async fn test_error_resolving() {
         deploy_set: None,
         signer: Signer::Keys { keys },
         processing_try_index: None,
+        boc_serialization: None,
         address: Some(address.clone()),
         call_set: Some(CallSet {
             function_name: "sendAllMoney".to_owned(),
@@ -522,6 +527,7 @@ async fn test_retries() {
                 call_set: CallSet::some_with_function("constructor"),
                 signer: Signer::Keys { keys: keys.clone() },
                 processing_try_index: None,
+                boc_serialization: None,
                 address: None,
             },
             None,
@@ -544,6 +550,7 @@ async fn test_retries() {
                             call_set: CallSet::some_with_function("touch"),
                             deploy_set: None,
                             processing_try_index: None,
+                            boc_serialization: None,
                             signer: Signer::Keys { keys },
                         },
                         send_events: false,
@@ -572,6 +579,7 @@ async fn test_fees() {
                 call_set: CallSet::some_with_function("constructor"),
                 signer: Signer::Keys { keys: keys.clone() },
                 processing_try_index: None,
+                boc_serialization: None,
                 address: None,
             },
             None,
@@ -591,6 +599,7 @@ async fn test_fees() {
         ),
         deploy_set: None,
         processing_try_index: None,
+        boc_serialization: None,
         signer: Signer::Keys { keys },
     };
 
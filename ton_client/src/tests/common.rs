@@ -117,6 +117,7 @@ async fn test_clock_sync() {
             call_set: CallSet::some_with_function("touch"),
             deploy_set: None,
             processing_try_index: None,
+            boc_serialization: None,
             signer: Signer::None,
         })
         .await
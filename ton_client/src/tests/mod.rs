@@ -665,6 +665,7 @@ impl TestClient {
                         input: Some(input),
                     }),
                     processing_try_index: None,
+                    boc_serialization: None,
                     signer,
                 },
                 send_events: false,
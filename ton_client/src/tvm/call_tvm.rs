@@ -14,6 +14,7 @@
 
 use super::types::ResolvedExecutionOptions;
 use crate::error::ClientResult;
+use crate::boc::libraries::{libraries_to_hashmap, resolve_library};
 use crate::tvm::Error;
 use std::sync::Arc;
 use ton_block::{
@@ -76,11 +77,12 @@ pub(crate) fn call_tvm(
         // TODO: for now use maximum available capabilities
         // options.blockchain_config.capabilites()
         capabilities
-    ).setup(
-        SliceData::from(code),
+    ).setup_with_libraries(
+        SliceData::from(resolve_library(&options.libraries, code)?),
         Some(ctrls),
         Some(stack),
         Some(gas),
+        vec![libraries_to_hashmap(&options.libraries)?],
     );
 
     engine.modify_behavior(options.behavior_modifiers);
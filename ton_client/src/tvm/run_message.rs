@@ -20,6 +20,7 @@ use crate::boc::internal::{
     serialize_cell_to_boc, serialize_object_to_base64, serialize_object_to_boc,
     serialize_object_to_cell
 };
+use crate::boc::libraries::libraries_to_hashmap;
 use crate::client::ClientContext;
 use crate::error::ClientResult;
 use crate::processing::{parsing::decode_output, DecodedOutput};
@@ -383,6 +384,7 @@ async fn call_executor<F>(
 where
     F: futures::Future<Output = ClientResult<(MsgAddressInt, u64)>>,
 {
+    let state_libs = libraries_to_hashmap(&options.libraries)?;
     let executor = OrdinaryTransactionExecutor::new(
         Arc::try_unwrap(options.blockchain_config)
             .unwrap_or_else(|arc| arc.as_ref().clone())
@@ -393,6 +395,7 @@ where
         last_tr_lt: Arc::new(AtomicU64::new(options.transaction_lt)),
         behavior_modifiers: Some(options.behavior_modifiers),
         seed_block: UInt256::rand(),
+        state_libs,
         ..ExecuteParams::default()
     };
     let transaction = match executor.execute_with_libs_and_params(Some(&msg), &mut account_root, params) {
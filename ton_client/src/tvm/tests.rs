@@ -219,6 +219,7 @@ async fn test_run_message<F>(
                 ),
                 signer: Signer::Keys { keys: keys.clone() },
                 processing_try_index: None,
+                boc_serialization: None,
                 address: None,
             },
             None,
@@ -247,6 +248,7 @@ async fn test_run_message<F>(
             signer: Signer::Keys { keys: keys.clone() },
             deploy_set: None,
             processing_try_index: None,
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -268,6 +270,7 @@ async fn test_run_message<F>(
             address: Some(address.clone()),
             deploy_set: None,
             processing_try_index: None,
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -360,6 +363,7 @@ async fn test_run_account_uninit() {
                 ..Default::default()
             }),
             processing_try_index: None,
+            boc_serialization: None,
             signer: Signer::Keys { keys: keys.clone() },
         })
         .await
@@ -407,6 +411,7 @@ async fn profile_tvm() {
             address: Some(address.clone()),
             deploy_set: None,
             processing_try_index: None,
+            boc_serialization: None,
         })
         .await
         .unwrap();
@@ -450,6 +455,7 @@ async fn profile_tvm() {
                     address: Some(address.clone()),
                     deploy_set: None,
                     processing_try_index: None,
+                    boc_serialization: None,
                 })
                 .await
                 .unwrap();
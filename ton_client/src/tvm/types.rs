@@ -22,9 +22,11 @@ use crate::{
     },
     net::{OrderBy, ParamsOfQueryCollection, ServerLink, SortDirection},
 };
+use std::collections::HashMap;
 use std::sync::Arc;
 use ton_block::Deserializable;
 use ton_executor::BlockchainConfig;
+use ton_types::{Cell, UInt256};
 use ton_vm::executor::BehaviorModifiers;
 
 #[derive(Serialize, Deserialize, ApiType, Clone, Default)]
@@ -48,6 +50,8 @@ pub(crate) struct ResolvedExecutionOptions {
     pub block_lt: u64,
     pub transaction_lt: u64,
     pub behavior_modifiers: BehaviorModifiers,
+    /// Library cells from the client library store
+    pub libraries: HashMap<UInt256, Cell>,
 }
 
 pub(crate) async fn blockchain_config_from_boc(context: &ClientContext, b64: &str) -> ClientResult<BlockchainConfig> {
@@ -82,6 +86,7 @@ impl ResolvedExecutionOptions {
             blockchain_config: config,
             transaction_lt,
             behavior_modifiers,
+            libraries: context.libraries.snapshot(),
         })
     }
 }
@@ -13,28 +13,81 @@
 
 use crate::error::SdkError;
 use crate::json_helper;
-use crate::{AbiContract, MessageId};
+use crate::{AbiContract, AbiFunction, MessageId};
 
 use chrono::prelude::Utc;
 use ed25519_dalek::{Keypair, PublicKey};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::convert::Into;
 use std::io::{Read, Seek, Cursor};
+use std::sync::Arc;
 use ton_abi::json_abi::DecodedMessage;
+use ton_abi::token::Tokenizer;
+use ton_abi::{Token, TokenValue};
 use ton_block::{AccountIdPrefixFull, Deserializable, ExternalInboundMessageHeader, GetRepresentationHash,
     Message as TvmMessage, MsgAddressInt, Serializable, ShardIdent, StateInit,
     InternalMessageHeader, CurrencyCollection};
 use ton_types::cells_serialization::deserialize_tree_of_cells;
-use ton_types::{error, fail, AccountId, Result, SliceData};
+use ton_types::{error, fail, AccountId, BuilderData, Result, SliceData};
 
 pub struct Contract {}
 
+// Function call with the already loaded ABI contract.
+//
+// `ton_abi::encode_function_call` and `ton_abi::prepare_function_call_for_sign` take the ABI
+// as JSON and parse it on every call, so their tokenization of the header and input and
+// the `pubkey` header insertion are repeated here for the loaded contract. Everything else
+// is delegated to `ton_abi::Function`. Keep this in sync with `ton_abi` when updating it.
 #[derive(Clone, Debug)]
 pub struct FunctionCallSet {
     pub func: String,
     pub header: Option<String>,
     pub input: String,
-    pub abi: String,
+    pub abi: Arc<AbiContract>,
+}
+
+impl FunctionCallSet {
+    fn tokenize(&self) -> Result<(&AbiFunction, HashMap<String, TokenValue>, Vec<Token>)> {
+        let function = self.abi.function(&self.func)?;
+        let header = match &self.header {
+            Some(header) => Tokenizer::tokenize_optional_params(
+                &function.header_params(),
+                &serde_json::from_str(header)?,
+            )?,
+            None => HashMap::new(),
+        };
+        let input = Tokenizer::tokenize_all_params(
+            &function.input_params(),
+            &serde_json::from_str(&self.input)?,
+        )?;
+        Ok((function, header, input))
+    }
+
+    // Encodes function call into message body using parsed ABI.
+    // Signs the body if key pair is provided.
+    pub fn encode_input(
+        &self,
+        internal: bool,
+        key_pair: Option<&Keypair>,
+        address: Option<MsgAddressInt>,
+    ) -> Result<BuilderData> {
+        let (function, mut header, input) = self.tokenize()?;
+        if key_pair.is_some() && !header.contains_key("pubkey") {
+            header.insert(
+                "pubkey".to_owned(),
+                TokenValue::PublicKey(key_pair.map(|pair| pair.public)),
+            );
+        }
+        function.encode_input(&header, &input, internal, key_pair, address)
+    }
+
+    // Encodes function call into message body with reserved place for signature.
+    // Returns body and data to sign.
+    pub fn encode_unsigned(&self, address: Option<MsgAddressInt>) -> Result<(BuilderData, Vec<u8>)> {
+        let (function, header, input) = self.tokenize()?;
+        function.create_unsigned_call(&header, &input, false, true, address)
+    }
 }
 
 pub struct SdkMessage {
@@ -279,7 +332,7 @@ impl Contract {
     // ------- Call constructing functions -------
 
     // Packs given inputs by abi into an external inbound Message struct.
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     // Returns message's bag of cells and identifier.
     pub fn construct_call_ext_in_message_json(
         address: MsgAddressInt,
@@ -287,15 +340,7 @@ impl Contract {
         key_pair: Option<&Keypair>,
     ) -> Result<SdkMessage> {
         // pack params into bag of cells via ABI
-        let msg_body = ton_abi::encode_function_call(
-            params.abi,
-            params.func,
-            params.header,
-            params.input,
-            false,
-            key_pair,
-            Some(address.to_string()),
-        )?;
+        let msg_body = params.encode_input(false, key_pair, Some(address.clone()))?;
 
         let msg = Self::create_ext_in_message(address.clone(), msg_body.into_cell()?.into())?;
         let (body, id) = Self::serialize_message(&msg)?;
@@ -308,7 +353,7 @@ impl Contract {
     }
 
     // Packs given inputs by abi into an internal Message struct.
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     // Returns message's bag of cells and identifier.
     pub fn construct_call_int_message_json(
         address: MsgAddressInt,
@@ -319,15 +364,7 @@ impl Contract {
         params: FunctionCallSet,
     ) -> Result<SdkMessage> {
         // pack params into bag of cells via ABI
-        let msg_body = ton_abi::encode_function_call(
-            params.abi,
-            params.func,
-            None,
-            params.input,
-            true,
-            None,
-            Some(address.to_string()),
-        )?;
+        let msg_body = params.encode_input(true, None, Some(address.clone()))?;
 
         Self::construct_int_message_with_body(
             address,
@@ -359,19 +396,13 @@ impl Contract {
 
     // Packs given inputs by abi into Message struct without sign and returns data to sign.
     // Sign should be then added with `add_sign_to_message` function
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     pub fn get_call_message_bytes_for_signing(
         address: MsgAddressInt,
         params: FunctionCallSet,
     ) -> Result<MessageToSign> {
         // pack params into bag of cells via ABI
-        let (msg_body, data_to_sign) = ton_abi::prepare_function_call_for_sign(
-            params.abi,
-            params.func,
-            params.header,
-            params.input,
-            Some(address.to_string()),
-        )?;
+        let (msg_body, data_to_sign) = params.encode_unsigned(Some(address.clone()))?;
 
         let msg = Self::create_ext_in_message(address, msg_body.into_cell()?.into())?;
 
@@ -384,7 +415,7 @@ impl Contract {
     // ------- Deploy constructing functions -------
 
     // Packs given image and input into Message struct.
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     // Returns message's bag of cells and identifier.
     pub fn construct_deploy_message_json(
         params: FunctionCallSet,
@@ -392,15 +423,7 @@ impl Contract {
         key_pair: Option<&Keypair>,
         workchain_id: i32,
     ) -> Result<SdkMessage> {
-        let msg_body = ton_abi::encode_function_call(
-            params.abi,
-            params.func,
-            params.header,
-            params.input,
-            false,
-            key_pair,
-            Some(image.msg_address(workchain_id).to_string()),
-        )?;
+        let msg_body = params.encode_input(false, key_pair, Some(image.msg_address(workchain_id)))?;
 
         let cell: SliceData = msg_body.into_cell()?.into();
         let msg = Self::create_ext_deploy_message(Some(cell), image, workchain_id)?;
@@ -459,19 +482,13 @@ impl Contract {
 
     // Packs given image and input into Message struct without signature and returns data to sign.
     // Signature should be then added with `add_sign_to_message` function
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     pub fn get_deploy_message_bytes_for_signing(
         params: FunctionCallSet,
         image: ContractImage,
         workchain_id: i32,
     ) -> Result<MessageToSign> {
-        let (msg_body, data_to_sign) = ton_abi::prepare_function_call_for_sign(
-            params.abi,
-            params.func,
-            params.header,
-            params.input,
-            Some(image.msg_address(workchain_id).to_string()),
-        )?;
+        let (msg_body, data_to_sign) = params.encode_unsigned(Some(image.msg_address(workchain_id)))?;
 
         let cell: SliceData = msg_body.into_cell()?.into();
         let msg = Self::create_ext_deploy_message(Some(cell), image, workchain_id)?;
@@ -483,7 +500,7 @@ impl Contract {
     }
 
     // Packs given image and input into Message struct with internal header and returns data.
-    // Works with json representation of input and abi.
+    // Works with json representation of input and parsed abi.
     pub fn get_int_deploy_message_bytes(
         src: Option<MsgAddressInt>,
         params: FunctionCallSet,
@@ -492,15 +509,7 @@ impl Contract {
         ihr_disabled: bool,
         bounce: bool,
     ) -> Result<Vec<u8>> {
-        let msg_body = ton_abi::encode_function_call(
-            params.abi,
-            params.func,
-            None,
-            params.input,
-            true,
-            None,
-            Some(image.msg_address(workchain_id).to_string()),
-        )?;
+        let msg_body = params.encode_input(true, None, Some(image.msg_address(workchain_id)))?;
 
         let cell: SliceData = msg_body.into_cell()?.into();
         let msg = Self::create_int_deploy_message(src, Some(cell), image, workchain_id, ihr_disabled, bounce)?;
@@ -518,31 +527,8 @@ impl Contract {
         public_key: Option<&[u8]>,
         message: &[u8],
     ) -> Result<SdkMessage> {
-        let mut slice = Self::deserialize_tree_to_slice(message)?;
-
-        let mut message: TvmMessage = TvmMessage::construct_from(&mut slice)?;
-
-        let body = message.body().ok_or(error!(SdkError::InvalidData {
-            msg: "No message body".to_owned()
-        }))?;
-
-        let signed_body = ton_abi::add_sign_to_function_call(abi, signature, public_key, body)?;
-        message.set_body(signed_body.into_cell()?.into());
-
-        let address = match message.dst_ref() {
-            Some(address) => address.clone(),
-            None => fail!(SdkError::InternalError {
-                msg: "No address in signed message".to_owned()
-            })
-        };
-        let (body, id) = Self::serialize_message(&message)?;
-
-        Ok(SdkMessage {
-            id,
-            address,
-            serialized_message: body,
-            message,
-        })
+        let abi = AbiContract::load(abi.as_bytes())?;
+        Self::attach_signature(&abi, signature, public_key, message)
     }
 
     // Add sign to message, returned by `get_deploy_message_bytes_for_signing` or